@@ -0,0 +1,95 @@
+// src/activity.rs
+// The bot's short-term memory:
+// keeps the last few notable events (signals, trades, errors) in RAM
+// so /log can answer "what just happened?" without shell access
+
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+
+/// What kind of thing happened
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventKind {
+    Signal,
+    TradeOpened,
+    TradeClosed,
+    Error,
+}
+
+impl EventKind {
+    fn emoji(&self) -> &'static str {
+        match self {
+            EventKind::Signal => "📡",
+            EventKind::TradeOpened => "📈",
+            EventKind::TradeClosed => "📉",
+            EventKind::Error => "❌",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ActivityEvent {
+    pub at: DateTime<Utc>,
+    pub kind: EventKind,
+    pub message: String,
+}
+
+/// Bounded ring buffer of recent events; the oldest entry is dropped when full
+pub struct ActivityLog {
+    events: VecDeque<ActivityEvent>,
+    capacity: usize,
+    secrets: Vec<String>,
+}
+
+impl ActivityLog {
+    /// `secrets` are scrubbed from every message before it is stored
+    pub fn new(capacity: usize, secrets: Vec<String>) -> Self {
+        Self {
+            events: VecDeque::with_capacity(capacity),
+            capacity,
+            secrets: secrets.into_iter().filter(|s| !s.is_empty()).collect(),
+        }
+    }
+
+    pub fn record(&mut self, kind: EventKind, message: impl Into<String>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut message = message.into();
+        for secret in &self.secrets {
+            message = message.replace(secret.as_str(), "***");
+        }
+
+        if self.events.len() == self.capacity {
+            self.events.pop_front();
+        }
+        self.events.push_back(ActivityEvent { at: Utc::now(), kind, message });
+    }
+
+    /// The last `n` events, newest first
+    pub fn recent(&self, n: usize) -> impl Iterator<Item = &ActivityEvent> {
+        self.events.iter().rev().take(n)
+    }
+}
+
+/// Render events for Telegram
+pub fn format_events<'a>(events: impl Iterator<Item = &'a ActivityEvent>) -> String {
+    let mut response = "📜 Recent Activity:\n\n".to_string();
+    let mut empty = true;
+
+    for event in events {
+        empty = false;
+        response.push_str(&format!(
+            "{} {} {}\n",
+            event.at.format("%H:%M:%S"),
+            event.kind.emoji(),
+            event.message
+        ));
+    }
+
+    if empty {
+        "📭 No activity recorded yet".to_string()
+    } else {
+        response
+    }
+}
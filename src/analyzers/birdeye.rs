@@ -0,0 +1,112 @@
+// src/analyzers/birdeye.rs
+// Fills in the holder and supply data DEX Screener doesn't have, for Solana tokens
+use anyhow::Result;
+use log::{info, warn};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::time::Duration;
+
+const BASE_URL: &str = "https://public-api.birdeye.so";
+
+pub struct BirdeyeClient {
+    client: Client,
+    api_key: String,
+}
+
+/// Holder and supply figures for a token; any of them may be missing
+#[derive(Debug, Clone, Default)]
+pub struct HolderData {
+    pub holder_count: Option<u32>,
+    pub total_supply: Option<Decimal>,
+    pub circulating_supply: Option<Decimal>,
+    pub top_10_holders_percentage: Option<Decimal>,
+}
+
+impl BirdeyeClient {
+    pub fn new(api_key: &str) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(15))
+            .user_agent("CryptoBot/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            api_key: api_key.to_string(),
+        }
+    }
+
+    /// Fetch holder count, supply and top-10 concentration for a Solana token
+    pub async fn get_holder_data(&self, address: &str) -> Result<HolderData> {
+        info!("🦅 Fetching Birdeye holder data for {}", address);
+
+        let overview: TokenOverview = self.get(&format!("/defi/token_overview?address={}", address)).await?;
+        let total_supply = overview.supply.and_then(|s| Decimal::try_from(s).ok());
+
+        // Concentration is a nice-to-have; the overview alone is still useful
+        let top_10_holders_percentage = match self.get::<HolderList>(&format!("/defi/v3/token/holder?address={}&offset=0&limit=10", address)).await {
+            Ok(holders) => total_supply.filter(|s| !s.is_zero()).map(|supply| {
+                let top_10: Decimal = holders.items.iter()
+                    .filter_map(|h| Decimal::try_from(h.ui_amount).ok())
+                    .sum();
+                (top_10 / supply * Decimal::from(100)).round_dp(2)
+            }),
+            Err(e) => {
+                warn!("Failed to fetch Birdeye top holders for {}: {}", address, e);
+                None
+            }
+        };
+
+        Ok(HolderData {
+            holder_count: overview.holder,
+            total_supply,
+            circulating_supply: overview.circulating_supply.and_then(|s| Decimal::try_from(s).ok()),
+            top_10_holders_percentage,
+        })
+    }
+
+    async fn get<T: for<'de> Deserialize<'de>>(&self, path: &str) -> Result<T> {
+        let response = self.client
+            .get(format!("{}{}", BASE_URL, path))
+            .header("X-API-KEY", &self.api_key)
+            .header("x-chain", "solana")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Birdeye API error: {}", response.status()));
+        }
+
+        let body: BirdeyeResponse<T> = response.json().await?;
+        match (body.success, body.data) {
+            (true, Some(data)) => Ok(data),
+            _ => Err(anyhow::anyhow!("Birdeye API returned no data")),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BirdeyeResponse<T> {
+    success: bool,
+    data: Option<T>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TokenOverview {
+    holder: Option<u32>,
+    supply: Option<f64>,
+    circulating_supply: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HolderList {
+    #[serde(default)]
+    items: Vec<Holder>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Holder {
+    ui_amount: f64,
+}
@@ -0,0 +1,188 @@
+// src/analyzers/exit_analyzer.rs
+// The sell-side counterpart of TokenAnalyzer: each monitor cycle it scores how strongly
+// a held token's own data (momentum, volume, holders) says to get out
+use anyhow::Result;
+use log::{debug, info};
+use rust_decimal::Decimal;
+use std::sync::Arc;
+
+use crate::activity::EventKind;
+use crate::config::TradingConfig;
+use crate::models::{SimulatedTrade, TokenMetrics};
+use crate::strategies::current_metrics;
+use crate::strategies::risk_management::close_position;
+use crate::AppState;
+
+/// What to do with a held position
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExitAction {
+    Hold,
+    /// Sell this share of the original position and keep the rest
+    TrimPartial(Decimal),
+    ExitNow,
+}
+
+/// An exit decision with the evidence behind it
+#[derive(Debug, Clone)]
+pub struct ExitRecommendation {
+    pub action: ExitAction,
+    /// Exit pressure, 0 (all good) to 100 (everything says sell)
+    pub score: Decimal,
+    pub reasons: Vec<String>,
+}
+
+pub struct ExitAnalyzer {
+    exit_score: Decimal,
+    trim_score: Decimal,
+    trim_fraction: Decimal,
+}
+
+impl ExitAnalyzer {
+    pub fn new(exit_score: f64, trim_score: f64, trim_fraction: f64) -> Self {
+        Self {
+            exit_score: Decimal::try_from(exit_score).unwrap_or(Decimal::from(75)),
+            trim_score: Decimal::try_from(trim_score).unwrap_or(Decimal::from(50)),
+            trim_fraction: Decimal::try_from(trim_fraction).unwrap_or(Decimal::try_from(0.5).unwrap()),
+        }
+    }
+
+    /// The analyzer the trading config asks for, or None when it's switched off
+    pub fn from_config(trading: &TradingConfig) -> Option<Self> {
+        trading.exit_score_threshold
+            .map(|exit_score| Self::new(exit_score, trading.trim_score_threshold, trading.exit_trim_fraction))
+    }
+
+    /// Score a held token from its snapshots since entry (oldest first, the last one current).
+    /// A trim only applies to a position that hasn't been scaled out of yet, so it happens once.
+    pub fn analyze(&self, trade: &SimulatedTrade, since_entry: &[TokenMetrics]) -> ExitRecommendation {
+        let mut score = Decimal::ZERO;
+        let mut reasons = Vec::new();
+
+        if let Some(latest) = since_entry.last() {
+            score += momentum_pressure(latest, &mut reasons);
+            score += volume_pressure(since_entry, latest, &mut reasons);
+            score += holder_pressure(since_entry, latest, &mut reasons);
+        }
+        let score = score.min(Decimal::from(100));
+
+        let action = if score >= self.exit_score {
+            ExitAction::ExitNow
+        } else if score >= self.trim_score && trade.remaining_fraction == Decimal::ONE {
+            ExitAction::TrimPartial(self.trim_fraction)
+        } else {
+            ExitAction::Hold
+        };
+
+        ExitRecommendation { action, score, reasons }
+    }
+
+    /// Score every active trade on current data and sell what the scores call for
+    pub async fn check_exit_signals(&self, state: &Arc<AppState>) -> Result<()> {
+        for trade in state.db.get_active_trades().await? {
+            let trade_id = match trade.id {
+                Some(id) => id,
+                None => continue,
+            };
+
+            // Refreshed first if stale, so the latest snapshot is the current one
+            let current_price = match current_metrics(state, &trade.token_address).await? {
+                Some(metrics) => match metrics.price_usd {
+                    Some(price) if price > Decimal::ZERO => price,
+                    _ => continue,
+                },
+                None => continue,
+            };
+            let since_entry = state.db.get_metrics_history(&trade.token_address, trade.entry_time).await?;
+
+            let recommendation = self.analyze(&trade, &since_entry);
+            let reason = format!("Exit score {:.0}: {}", recommendation.score, recommendation.reasons.join(", "));
+            match recommendation.action {
+                ExitAction::Hold => {
+                    if !recommendation.reasons.is_empty() {
+                        debug!("🧭 Holding {} ({})", trade.token_address, reason);
+                    }
+                }
+                ExitAction::ExitNow => close_position(state, &trade, current_price, &reason).await?,
+                ExitAction::TrimPartial(fraction) => {
+                    let remaining = trade.remaining_fraction - fraction;
+                    let profit_usd = trade.partial_profit_loss(current_price, fraction);
+                    state.db.partial_close_trade(trade_id, remaining, trade.realized_profit_loss + profit_usd, trade.targets_hit).await?;
+
+                    info!("✂️ Trimmed {:.0}% of {} (${:.2}): {}", fraction * Decimal::from(100), trade.token_address, profit_usd, reason);
+                    state.record_event(EventKind::TradeClosed, format!(
+                        "{} trimmed {:.0}% (${:.2}): {}", trade.token_address, fraction * Decimal::from(100), profit_usd, reason
+                    )).await;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Falling price over the last hour and five minutes (up to 40)
+fn momentum_pressure(latest: &TokenMetrics, reasons: &mut Vec<String>) -> Decimal {
+    let mut score = Decimal::ZERO;
+
+    if let Some(change) = latest.price_change_1h_pct {
+        if change <= Decimal::from(-25) {
+            score += Decimal::from(25);
+            reasons.push(format!("price {:.0}% in 1h", change));
+        } else if change <= Decimal::from(-10) {
+            score += Decimal::from(12);
+            reasons.push(format!("price {:.0}% in 1h", change));
+        }
+    }
+
+    if let Some(change) = latest.price_change_5m_pct {
+        if change <= Decimal::from(-10) {
+            score += Decimal::from(15);
+            reasons.push(format!("price {:.0}% in 5m", change));
+        } else if change <= Decimal::from(-5) {
+            score += Decimal::from(8);
+            reasons.push(format!("price {:.0}% in 5m", change));
+        }
+    }
+
+    score
+}
+
+/// Hourly volume drying up against its peak since entry (up to 30)
+fn volume_pressure(since_entry: &[TokenMetrics], latest: &TokenMetrics, reasons: &mut Vec<String>) -> Decimal {
+    let peak = since_entry.iter().filter_map(|m| m.volume_1h_usd).max();
+    let (peak, now) = match (peak, latest.volume_1h_usd) {
+        (Some(peak), Some(now)) if peak > Decimal::ZERO => (peak, now),
+        _ => return Decimal::ZERO,
+    };
+
+    let ratio = now / peak;
+    let score = if ratio < Decimal::try_from(0.25).unwrap() {
+        Decimal::from(30)
+    } else if ratio < Decimal::try_from(0.5).unwrap() {
+        Decimal::from(15)
+    } else {
+        return Decimal::ZERO;
+    };
+    reasons.push(format!("1h volume {:.0}% of its peak", ratio * Decimal::from(100)));
+    score
+}
+
+/// Holders leaving since their peak after entry (up to 30)
+fn holder_pressure(since_entry: &[TokenMetrics], latest: &TokenMetrics, reasons: &mut Vec<String>) -> Decimal {
+    let peak = since_entry.iter().filter_map(|m| m.holder_count).max();
+    let (peak, now) = match (peak, latest.holder_count) {
+        (Some(peak), Some(now)) if peak > 0 => (peak, now),
+        _ => return Decimal::ZERO,
+    };
+
+    let drop = Decimal::from(peak.saturating_sub(now)) / Decimal::from(peak);
+    let score = if drop >= Decimal::try_from(0.2).unwrap() {
+        Decimal::from(30)
+    } else if drop >= Decimal::try_from(0.1).unwrap() {
+        Decimal::from(15)
+    } else {
+        return Decimal::ZERO;
+    };
+    reasons.push(format!("holders down {:.0}% from peak", drop * Decimal::from(100)));
+    score
+}
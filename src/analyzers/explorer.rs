@@ -0,0 +1,97 @@
+// src/analyzers/explorer.rs
+// Asks the block explorer when and by whom an EVM contract was deployed,
+// so a months-old token that's merely new to us isn't treated as a fresh launch
+use anyhow::Result;
+use chrono::{DateTime, TimeZone, Utc};
+use log::info;
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+
+use crate::models::Chain;
+
+const BASE_URL: &str = "https://api.etherscan.io/v2/api";
+
+/// When and by whom a contract was created
+#[derive(Debug, Clone)]
+pub struct ContractCreation {
+    pub deployed_at: DateTime<Utc>,
+    pub deployer: Option<String>,
+}
+
+pub struct EtherscanClient {
+    client: Client,
+    api_key: String,
+}
+
+impl EtherscanClient {
+    pub fn new(api_key: &str) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(15))
+            .user_agent("CryptoBot/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            api_key: api_key.to_string(),
+        }
+    }
+
+    /// When and by whom the contract was created. `None` for chains without an explorer
+    /// or contracts the explorer doesn't know.
+    pub async fn get_contract_creation(&self, chain: &Chain, address: &str) -> Result<Option<ContractCreation>> {
+        let chain_id = match etherscan_chain_id(chain) {
+            Some(id) => id,
+            None => return Ok(None),
+        };
+        info!("🏗️ Fetching contract creation of {} on {}", address, chain);
+
+        let url = format!(
+            "{}?chainid={}&module=contract&action=getcontractcreation&contractaddresses={}&apikey={}",
+            BASE_URL, chain_id, address, self.api_key
+        );
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Etherscan API error: {}", response.status()));
+        }
+
+        let body: CreationResponse = response.json().await?;
+        let creations = match body.result {
+            serde_json::Value::Array(items) => items,
+            // Unknown contracts come back as status 0 with a null result
+            serde_json::Value::Null => return Ok(None),
+            other if body.status == "0" => return Err(anyhow::anyhow!("Etherscan error: {} ({})", body.message, other)),
+            _ => Vec::new(),
+        };
+
+        Ok(creations.into_iter()
+            .filter_map(|item| serde_json::from_value::<RawCreation>(item).ok())
+            .find_map(|c| Some(ContractCreation {
+                deployed_at: Utc.timestamp_opt(c.timestamp.parse().ok()?, 0).single()?,
+                deployer: c.contract_creator,
+            })))
+    }
+}
+
+/// Chain IDs for the Etherscan V2 multichain API, on the chains it covers
+pub fn etherscan_chain_id(chain: &Chain) -> Option<u32> {
+    match chain {
+        Chain::Ethereum | Chain::Bsc | Chain::Polygon | Chain::Arbitrum | Chain::Avalanche | Chain::Base => chain.chain_id(),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CreationResponse {
+    status: String,
+    message: String,
+    result: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawCreation {
+    timestamp: String,
+    #[serde(rename = "contractCreator")]
+    contract_creator: Option<String>,
+}
@@ -3,57 +3,94 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use log::{info, warn};
 
-pub struct HoneypotChecker {
-    client: Client,
+use crate::http::{HttpClient, HttpFetch};
+use crate::models::Chain;
+
+pub struct HoneypotChecker<H = HttpClient> {
+    http: H,
 }
 
 impl HoneypotChecker {
     pub fn new() -> Self {
-        Self {
-            client: Client::new(),
-        }
+        Self::with_http(HttpClient::new(Client::new()))
     }
+}
+
+impl<H: HttpFetch> HoneypotChecker<H> {
+    /// A checker that asks honeypot.is through `http`, e.g. canned responses
+    pub fn with_http(http: H) -> Self {
+        Self { http }
+    }
+
+    /// Check if a token is a honeypot (and what it taxes) using external API.
+    /// API or network failures are returned as errors: an unknown result is not a safe one.
+    /// `None` when honeypot.is can't simulate trades on the chain.
+    pub async fn check_honeypot(&self, chain: &Chain, address: &str) -> Result<Option<HoneypotReport>> {
+        let chain_id = match self.get_chain_id(chain) {
+            Some(id) => id,
+            None => {
+                info!("🍯 Honeypot check not available on {}, skipping {}", chain, address);
+                return Ok(None);
+            }
+        };
 
-    /// Check if a token is a honeypot using external API
-    pub async fn check_honeypot(&self, chain: &str, address: &str) -> Result<bool> {
         // Using honeypot.is API (free tier)
         let url = format!("https://api.honeypot.is/v2/IsHoneypot?address={}&chainID={}", 
-                         address, self.get_chain_id(chain));
+                         address, chain_id);
 
         info!("🍯 Checking honeypot status for {} on {}", address, chain);
 
-        match self.client.get(&url).send().await {
-            Ok(response) => {
-                if response.status().is_success() {
-                    let result: HoneypotResponse = response.json().await?;
-                    Ok(result.honeypot_result.is_honeypot)
-                } else {
-                    warn!("Honeypot API returned error: {}", response.status());
-                    Ok(false) // Default to safe if API fails
-                }
+        match self.http.get_json(&url).await {
+            Ok(body) => {
+                let result: HoneypotResponse = serde_json::from_str(&body)?;
+                Ok(Some(HoneypotReport {
+                    is_honeypot: result.honeypot_result.is_honeypot,
+                    buy_tax_pct: result.simulation_result.as_ref().and_then(|s| s.buy_tax),
+                    sell_tax_pct: result.simulation_result.as_ref().and_then(|s| s.sell_tax),
+                }))
             }
             Err(e) => {
                 warn!("Failed to check honeypot: {}", e);
-                Ok(false) // Default to safe if network fails
+                Err(anyhow::anyhow!("Honeypot API error: {}", e))
             }
         }
     }
 
-    fn get_chain_id(&self, chain: &str) -> u32 {
-        match chain.to_lowercase().as_str() {
-            "ethereum" => 1,
-            "bsc" => 56,
-            "polygon" => 137,
-            "solana" => 101, // Custom ID for Solana
-            _ => 1, // Default to Ethereum
+    pub fn supports_chain(&self, chain: &Chain) -> bool {
+        self.get_chain_id(chain).is_some()
+    }
+
+    /// Chain IDs honeypot.is can simulate on (it has no Solana support)
+    fn get_chain_id(&self, chain: &Chain) -> Option<u32> {
+        match chain {
+            Chain::Ethereum | Chain::Bsc | Chain::Base => chain.chain_id(),
+            _ => None,
         }
     }
 }
 
+/// What a simulated buy + sell revealed about a token
+#[derive(Debug, Clone)]
+pub struct HoneypotReport {
+    pub is_honeypot: bool,
+    pub buy_tax_pct: Option<f64>,
+    pub sell_tax_pct: Option<f64>,
+}
+
 #[derive(Debug, Deserialize)]
 struct HoneypotResponse {
     #[serde(rename = "honeypotResult")]
     honeypot_result: HoneypotResult,
+    #[serde(rename = "simulationResult")]
+    simulation_result: Option<SimulationResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimulationResult {
+    #[serde(rename = "buyTax")]
+    buy_tax: Option<f64>,
+    #[serde(rename = "sellTax")]
+    sell_tax: Option<f64>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -1,3 +1,7 @@
 pub mod token_analyzer;
 pub mod honeypot_checker;
 pub mod whale_analyzer;
+pub mod birdeye;
+pub mod explorer;
+pub mod solana_rpc;
+pub mod exit_analyzer;
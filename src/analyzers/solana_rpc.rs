@@ -0,0 +1,142 @@
+// src/analyzers/solana_rpc.rs
+// Reads a Solana token's mint account: who (if anyone) can still mint or freeze it
+use anyhow::Result;
+use log::info;
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::json;
+use std::time::Duration;
+
+pub struct SolanaRpcClient {
+    client: Client,
+    url: String,
+}
+
+/// The authorities set on a mint; `None` means the authority was revoked
+#[derive(Debug, Clone)]
+pub struct MintAuthorities {
+    pub mint_authority: Option<String>,
+    pub freeze_authority: Option<String>,
+}
+
+impl SolanaRpcClient {
+    pub fn new(url: &str) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            url: url.to_string(),
+        }
+    }
+
+    /// Mint and freeze authority of a token, via `getAccountInfo`.
+    /// `None` when the address isn't an SPL token mint (or doesn't exist).
+    pub async fn get_mint_authorities(&self, mint: &str) -> Result<Option<MintAuthorities>> {
+        info!("🔑 Reading mint authorities for {}", mint);
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "getAccountInfo",
+            "params": [mint, { "encoding": "jsonParsed" }],
+        });
+        let response = self.client.post(&self.url).json(&request).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Solana RPC error: {}", response.status()));
+        }
+
+        parse_mint_authorities(&response.text().await?)
+    }
+}
+
+/// The authorities in a `getAccountInfo` (jsonParsed) response
+fn parse_mint_authorities(body: &str) -> Result<Option<MintAuthorities>> {
+    let response: RpcResponse = serde_json::from_str(body)?;
+    if let Some(error) = response.error {
+        return Err(anyhow::anyhow!("Solana RPC error {}: {}", error.code, error.message));
+    }
+
+    // Accounts the node can't parse come back as raw base64 instead of an object
+    let parsed = response.result
+        .and_then(|result| result.value)
+        .and_then(|account| serde_json::from_value::<ParsedData>(account.data).ok());
+
+    Ok(match parsed {
+        Some(ParsedData { parsed: ParsedAccount { kind, info } }) if kind == "mint" => Some(MintAuthorities {
+            mint_authority: info.mint_authority,
+            freeze_authority: info.freeze_authority,
+        }),
+        _ => None,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse {
+    result: Option<RpcResult>,
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResult {
+    value: Option<AccountInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountInfo {
+    data: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParsedData {
+    parsed: ParsedAccount,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParsedAccount {
+    #[serde(rename = "type")]
+    kind: String,
+    info: MintInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct MintInfo {
+    #[serde(rename = "mintAuthority")]
+    mint_authority: Option<String>,
+    #[serde(rename = "freezeAuthority")]
+    freeze_authority: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_authorities_from_a_parsed_mint() {
+        let body = r#"{"jsonrpc":"2.0","result":{"context":{"slot":1},"value":{"data":{"parsed":{"info":
+            {"decimals":6,"freezeAuthority":null,"isInitialized":true,"mintAuthority":"Auth1111111111111111111111111111111111111111","supply":"1000"},
+            "type":"mint"},"program":"spl-token","space":82},"executable":false,"lamports":1,"owner":"TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"}},"id":1}"#;
+
+        let authorities = parse_mint_authorities(body).unwrap().unwrap();
+        assert_eq!(authorities.mint_authority.as_deref(), Some("Auth1111111111111111111111111111111111111111"));
+        assert_eq!(authorities.freeze_authority, None);
+    }
+
+    #[test]
+    fn anything_but_a_mint_has_no_authorities() {
+        let missing = r#"{"jsonrpc":"2.0","result":{"context":{"slot":1},"value":null},"id":1}"#;
+        let raw = r#"{"jsonrpc":"2.0","result":{"context":{"slot":1},"value":{"data":["AAAA","base64"],"owner":"11111111111111111111111111111111"}},"id":1}"#;
+
+        assert!(parse_mint_authorities(missing).unwrap().is_none());
+        assert!(parse_mint_authorities(raw).unwrap().is_none());
+        assert!(parse_mint_authorities(r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"Invalid param"},"id":1}"#).is_err());
+    }
+}
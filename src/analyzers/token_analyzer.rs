@@ -1,25 +1,45 @@
 use anyhow::Result;
-use chrono::Utc;
-use log::{info, warn};
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn};
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use std::sync::Arc;
 
-use crate::models::{Token, TokenMetrics, AnalysisResult, RiskLevel, Recommendation, TradingSignal, SignalType};
+use crate::activity::EventKind;
+use crate::analyzers::explorer::{etherscan_chain_id, EtherscanClient};
+use crate::analyzers::honeypot_checker::HoneypotChecker;
+use crate::analyzers::solana_rpc::SolanaRpcClient;
+use crate::config::{SecurityCheck, TradingConfig, VolatilityMeasure};
+use crate::models::{Chain, Token, TokenMetrics, AnalysisResult, RiskLevel, Recommendation, TradingSignal, SignalType, ANALYZER_TRADE};
+use crate::scanners::dex_screener::DexScreenerScanner;
+use crate::utils::{max_drawdown, price_volatility};
 use crate::AppState;
 
 pub struct TokenAnalyzer {
     // Configuration thresholds
-    min_liquidity: Decimal,
     max_top_holder_pct: Decimal,
     min_holders: u32,
+    min_buys_24h: u32,
+    max_volume_per_buy: Decimal,
+    consistency_tolerance: Decimal,
+    trading: TradingConfig,
 }
 
 impl TokenAnalyzer {
-    pub fn new(state: &AppState) -> Self {
+    /// Snapshot of the live trading parameters; build one per analysis so /set changes apply to the next
+    pub async fn new(state: &AppState) -> Self {
+        Self::with_trading(state.trading.read().await.clone())
+    }
+
+    /// An analyzer for fixed trading parameters (e.g. a backtest's)
+    pub fn with_trading(trading: TradingConfig) -> Self {
         Self {
-            min_liquidity: Decimal::try_from(state.config.trading.min_liquidity_usd).unwrap_or(Decimal::from(10000)),
-            max_top_holder_pct: Decimal::try_from(state.config.trading.max_top_holder_percentage).unwrap_or(Decimal::from(30)),
-            min_holders: state.config.trading.min_holders,
+            max_top_holder_pct: Decimal::try_from(trading.max_top_holder_percentage).unwrap_or(Decimal::from(30)),
+            min_holders: trading.min_holders,
+            min_buys_24h: trading.min_buys_24h,
+            max_volume_per_buy: Decimal::try_from(trading.max_volume_per_buy_usd).unwrap_or(Decimal::from(10000)),
+            consistency_tolerance: Decimal::try_from(trading.price_consistency_tolerance).unwrap_or(Decimal::try_from(0.25).unwrap()),
+            trading,
         }
     }
 
@@ -27,8 +47,20 @@ impl TokenAnalyzer {
     pub async fn analyze_token(&self, state: &Arc<AppState>, token: &Token) -> Result<AnalysisResult> {
         info!("🔬 Starting deep analysis of {} ({})", token.symbol, token.name);
 
+        let token = &with_contract_creation(state, token).await;
+        if state.db.is_blacklisted(&token.address).await? {
+            info!("⛔ {} is blacklisted, not analyzing", token.symbol);
+            return Ok(self.create_blacklisted_result(&token.address, "Token is blacklisted"));
+        }
+        if let Some(deployer) = &token.deployer {
+            if state.db.is_blacklisted(deployer).await? {
+                info!("⛔ {} was deployed by blacklisted wallet {}, not analyzing", token.symbol, deployer);
+                return Ok(self.create_blacklisted_result(&token.address, &format!("Deployed by blacklisted {}", deployer)));
+            }
+        }
+
         // Get the latest metrics for this token
-        let metrics = match state.db.get_latest_metrics(&token.address).await? {
+        let mut metrics = match state.db.get_latest_metrics(&token.address).await? {
             Some(metrics) => metrics,
             None => {
                 warn!("No metrics found for token {}", token.symbol);
@@ -39,61 +71,167 @@ impl TokenAnalyzer {
         // Start with base score
         let mut score = Decimal::from(50); // Start neutral (0-100 scale)
         let mut flags = Vec::new();
-        let mut risk_level = RiskLevel::Medium;
+
+        // Scanners can't simulate trades or read mint accounts, so those verdicts are filled in here
+        let mut enriched = false;
+        if metrics.is_honeypot.is_none() {
+            if !run_honeypot_check(state, token, &mut metrics).await {
+                flags.push(format!("⚠️ {}: Honeypot check unavailable", ENRICHMENT_FAILED_FLAG));
+            } else {
+                enriched |= metrics.is_honeypot.is_some();
+            }
+        }
+        if metrics.is_mintable.is_none() {
+            if !run_authority_check(state, token, &mut metrics).await {
+                flags.push(format!("⚠️ {}: Mint authority check unavailable", ENRICHMENT_FAILED_FLAG));
+            } else {
+                enriched |= metrics.is_mintable.is_some();
+            }
+        }
+        if enriched {
+            state.db.update_security_verdicts(&metrics).await?;
+        }
+
+        // 0. DATA QUALITY CHECK (no points, but blocks trading on corrupted or stale snapshots)
+        self.check_data_consistency(token, &metrics, &mut flags);
+        self.check_data_age(&metrics, &mut flags);
+
+        // Snapshots from the last hour, for the trend-based steps
+        let history = match state.db.get_metrics_history(&token.address, Utc::now() - chrono::Duration::hours(1)).await {
+            Ok(history) => history,
+            Err(e) => {
+                warn!("Failed to load metrics history for {}: {}", token.symbol, e);
+                Vec::new()
+            }
+        };
+
+        // 1-6, 8-9. Everything the snapshot and its history can tell us
+        score += self.score_metrics(token, &metrics, &history, Utc::now(), &mut flags);
+
+        // 7. SYMBOL IMPERSONATION CHECK (penalty only)
+        score += self.analyze_symbol_reuse(state, token, &mut flags).await?;
+
+        let result = self.conclude(&token.address, score, flags, &metrics);
+
+        info!("📊 Analysis complete for {}: Score={}, Safe={}, Risk={:?}", 
+              token.symbol, result.score, result.is_safe, result.risk_level);
+
+        // Keep the score history
+        if let Err(e) = state.db.save_analysis_result(&result).await {
+            warn!("Failed to save analysis result for {}: {}", token.symbol, e);
+        }
+
+        // Generate trading signal if this looks promising
+        self.generate_trading_signal(state, token, &result).await?;
+
+        Ok(result)
+    }
+
+    /// Analyze a stored snapshot as of its own timestamp, without any lookups: for replaying
+    /// history. `history` holds the snapshots from the hour before it, oldest first.
+    /// The symbol impersonation check needs the live token table, so it's skipped.
+    pub fn analyze_snapshot(&self, token: &Token, metrics: &TokenMetrics, history: &[TokenMetrics]) -> AnalysisResult {
+        let mut flags = Vec::new();
+        self.check_data_consistency(token, metrics, &mut flags);
+
+        let score = Decimal::from(50) + self.score_metrics(token, metrics, history, metrics.timestamp, &mut flags);
+        self.conclude(&token.address, score, flags, metrics)
+    }
+
+    /// Points from every step that only needs the snapshot, its recent history and the time
+    fn score_metrics(&self, token: &Token, metrics: &TokenMetrics, history: &[TokenMetrics],
+                     now: DateTime<Utc>, flags: &mut Vec<String>) -> Decimal {
+        let mut score = Decimal::ZERO;
 
         // 1. LIQUIDITY ANALYSIS (25 points max)
-        score += self.analyze_liquidity(&metrics, &mut flags);
+        score += self.analyze_liquidity(token, metrics, flags);
 
         // 2. HOLDER DISTRIBUTION ANALYSIS (20 points max)
-        score += self.analyze_holder_distribution(&metrics, &mut flags);
+        score += self.analyze_holder_distribution(metrics, flags);
 
         // 3. VOLUME ANALYSIS (15 points max)
-        score += self.analyze_volume(&metrics, &mut flags);
+        score += self.analyze_volume(metrics, flags);
 
         // 4. PRICE STABILITY ANALYSIS (15 points max)
-        score += self.analyze_price_stability(state, &token.address, &mut flags).await;
+        score += self.analyze_price_stability(history, flags);
 
         // 5. CONTRACT SECURITY ANALYSIS (15 points max)
-        score += self.analyze_contract_security(&metrics, &mut flags);
+        score += self.analyze_contract_security(token, metrics, flags);
 
         // 6. MARKET TIMING ANALYSIS (10 points max)
-        score += self.analyze_market_timing(&token, &mut flags);
+        score += self.analyze_market_timing(token, now, flags);
+
+        // 8. HOLDER GROWTH ANALYSIS (5 points max)
+        score += self.analyze_holder_growth(metrics, history, flags);
+
+        // 9. SHORT-TERM MOMENTUM ANALYSIS (5 points max)
+        score += self.analyze_momentum(metrics, flags);
 
+        score
+    }
+
+    /// Turn a score and its flags into risk, safety, upside and a recommendation
+    fn conclude(&self, token_address: &str, score: Decimal, flags: Vec<String>, metrics: &TokenMetrics) -> AnalysisResult {
         // Determine risk level based on score and flags
-        risk_level = self.calculate_risk_level(score, &flags);
+        let risk_level = self.calculate_risk_level(score, &flags);
 
         // Determine if it's safe to trade
         let is_safe = score >= Decimal::from(70) && !self.has_critical_flags(&flags);
 
         // Calculate potential multiplier based on analysis
-        let potential_multiplier = self.calculate_potential_multiplier(score, &metrics, &flags);
+        let potential_multiplier = self.calculate_potential_multiplier(score, metrics, &flags);
 
         // Make recommendation
         let recommendation = self.make_recommendation(score, &risk_level, is_safe);
 
-        let result = AnalysisResult {
-            token_address: token.address.clone(),
+        AnalysisResult {
+            token_address: token_address.to_string(),
             score,
             is_safe,
             risk_level,
             flags,
             potential_multiplier,
             recommendation,
-        };
+        }
+    }
 
-        info!("📊 Analysis complete for {}: Score={}, Safe={}, Risk={:?}", 
-              token.symbol, result.score, result.is_safe, result.risk_level);
+    fn check_data_consistency(&self, token: &Token, metrics: &TokenMetrics, flags: &mut Vec<String>) {
+        let mut reason = None;
 
-        // Generate trading signal if this looks promising
-        if result.is_safe && result.score >= Decimal::from(75) {
-            self.generate_trading_signal(state, token, &result).await?;
+        if metrics.price_consistent == Some(false) {
+            reason = Some("price does not reconcile with pool liquidity".to_string());
         }
 
-        Ok(result)
+        // Market cap / price implies a supply, which can't exceed the real one
+        if let (Some(price), Some(market_cap)) = (metrics.price_usd, metrics.market_cap_usd) {
+            let supply = metrics.circulating_supply.or(metrics.total_supply);
+            if let Some(supply) = supply.filter(|s| *s > Decimal::ZERO && price > Decimal::ZERO) {
+                let implied_supply = market_cap / price;
+                if implied_supply > supply * (Decimal::ONE + self.consistency_tolerance) {
+                    reason = Some(format!("market cap implies {:.0} tokens but supply is {:.0}", implied_supply, supply));
+                }
+            }
+        }
+
+        if let Some(reason) = reason {
+            warn!("❓ Inconsistent data for {}: {}", token.symbol, reason);
+            flags.push(format!("❓ INCONSISTENT_DATA: {}", reason));
+        }
     }
 
-    fn analyze_liquidity(&self, metrics: &TokenMetrics, flags: &mut Vec<String>) -> Decimal {
+    /// A quiet token's last snapshot can be an hour old; don't trade on that price
+    fn check_data_age(&self, metrics: &TokenMetrics, flags: &mut Vec<String>) {
+        let age = Utc::now().signed_duration_since(metrics.timestamp);
+        if age.num_minutes() > self.trading.max_metrics_age_minutes {
+            flags.push(format!("🕰️ STALE_METRICS: Latest snapshot is {} old", crate::utils::format_duration(age)));
+        }
+    }
+
+    fn analyze_liquidity(&self, token: &Token, metrics: &TokenMetrics, flags: &mut Vec<String>) -> Decimal {
         let mut score = Decimal::ZERO;
+        // Launchpad chains run on far thinner pools than Ethereum
+        let min_liquidity = Decimal::try_from(self.trading.min_liquidity_for(token.chain.as_str()))
+            .unwrap_or(Decimal::from(10000));
 
         if let Some(liquidity) = metrics.liquidity_usd {
             if liquidity >= Decimal::from(100000) {
@@ -102,7 +240,7 @@ impl TokenAnalyzer {
                 score += Decimal::from(20); // Good liquidity
             } else if liquidity >= Decimal::from(20000) {
                 score += Decimal::from(15); // Decent liquidity
-            } else if liquidity >= self.min_liquidity {
+            } else if liquidity >= min_liquidity {
                 score += Decimal::from(10); // Minimum acceptable
             } else {
                 flags.push("🚨 LOW_LIQUIDITY: May be hard to sell".to_string());
@@ -179,24 +317,157 @@ impl TokenAnalyzer {
             }
         }
 
+        // Lots of volume from only a handful of buys is a wash trading pattern
+        if let Some(buys) = metrics.buy_count_24h {
+            if buys < self.min_buys_24h {
+                flags.push(format!("🛒 FEW_BUYERS: Only {} buys in 24h", buys));
+                score -= Decimal::from(5);
+            }
+
+            if let Some(volume_24h) = metrics.volume_24h_usd {
+                let volume_per_buy = volume_24h / Decimal::from(buys.max(1));
+                if volume_per_buy > self.max_volume_per_buy {
+                    flags.push(format!("🔁 WASH_TRADING: ${:.0} volume per buy across {} buys", volume_per_buy, buys));
+                    score -= Decimal::from(10);
+                }
+            }
+        }
+
         score
     }
 
-    async fn analyze_price_stability(&self, state: &Arc<AppState>, token_address: &str, flags: &mut Vec<String>) -> Decimal {
-        // For now, we'll implement basic price stability analysis
-        // In a full implementation, we'd look at historical price data
-        
-        // TODO: Implement price history analysis
-        // For now, give neutral score
-        let score = Decimal::from(7); // Neutral score
-        
-        info!("💹 Price stability analysis: +{} points", score);
+    fn analyze_price_stability(&self, history: &[TokenMetrics], flags: &mut Vec<String>) -> Decimal {
+        let neutral = Decimal::from(7);
+
+        let prices: Vec<f64> = history.iter()
+            .filter_map(|m| m.price_usd.and_then(|p| p.to_f64()))
+            .collect();
+
+        // Too few snapshots to judge yet
+        let stddev = match price_volatility(&prices, VolatilityMeasure::StdDev) {
+            Some(stddev) => stddev,
+            None => {
+                info!("💹 Price stability analysis: +{} points (not enough history)", neutral);
+                return neutral;
+            }
+        };
+        let drawdown = max_drawdown(&prices);
+        let threshold = self.trading.high_volatility_pct;
+
+        // Calmer is safer
+        let mut score: i64 = if stddev <= threshold * 0.25 {
+            12
+        } else if stddev <= threshold * 0.5 {
+            9
+        } else if stddev <= threshold {
+            6
+        } else {
+            flags.push(format!("📉 HIGH_VOLATILITY: {:.1}% price stddev over the last hour", stddev));
+            2
+        };
+
+        if drawdown > threshold {
+            score -= 3;
+        }
+        if prices.last() > prices.first() {
+            score += 3;
+        }
+        let score = Decimal::from(score.clamp(0, 15));
+
+        info!("💹 Price stability analysis: +{} points (stddev {:.1}%, drawdown {:.1}%)", score, stddev, drawdown);
         score
     }
 
-    fn analyze_contract_security(&self, metrics: &TokenMetrics, flags: &mut Vec<String>) -> Decimal {
+    /// Whether holders are joining or leaving: the latest count against the oldest one from the last hour
+    fn analyze_holder_growth(&self, metrics: &TokenMetrics, history: &[TokenMetrics], flags: &mut Vec<String>) -> Decimal {
+        let latest = match metrics.holder_count {
+            Some(holders) => holders,
+            None => return Decimal::ZERO,
+        };
+
+        let earliest = history.iter()
+            .filter(|m| m.timestamp < metrics.timestamp)
+            .find_map(|m| m.holder_count.map(|holders| (holders, m.timestamp)));
+
+        let (previous, since) = match earliest {
+            Some(earliest) if earliest.0 > 0 => earliest,
+            _ => {
+                info!("👥 Holder growth analysis: +0 points (not enough history)");
+                return Decimal::ZERO;
+            }
+        };
+        let growth_pct = (latest as f64 - previous as f64) / previous as f64 * 100.0;
+        let minutes = metrics.timestamp.signed_duration_since(since).num_minutes();
+
+        let score = if growth_pct >= 10.0 {
+            Decimal::from(5)
+        } else if growth_pct > 0.0 {
+            Decimal::from(3)
+        } else if growth_pct == 0.0 {
+            Decimal::ZERO
+        } else {
+            flags.push(format!("👥 HOLDERS_DECLINING: {} → {} holders in {}m", previous, latest, minutes));
+            Decimal::from(-5)
+        };
+
+        info!("👥 Holder growth analysis: {:+} points ({:+.1}% over {}m)", score, growth_pct, minutes);
+        score
+    }
+
+    /// Reward a steady climb over the last hour, but not a parabolic one that's likely to
+    /// reverse, and flag a token that's dumping right now
+    fn analyze_momentum(&self, metrics: &TokenMetrics, flags: &mut Vec<String>) -> Decimal {
         let mut score = Decimal::ZERO;
 
+        if let Some(change_1h) = metrics.price_change_1h_pct {
+            if change_1h > Decimal::ZERO && change_1h <= Decimal::from(MAX_HEALTHY_1H_GAIN_PCT) {
+                score += Decimal::from(5);
+            }
+        }
+
+        if let Some(change_5m) = metrics.price_change_5m_pct {
+            if change_5m <= -Decimal::from(DUMP_5M_DROP_PCT) {
+                flags.push(format!("📉 DUMPING: Price {:.1}% in the last 5m", change_5m));
+                score -= Decimal::from(10);
+            }
+        }
+
+        info!("🚀 Momentum analysis: {:+} points", score);
+        score
+    }
+
+    /// Scam tokens love borrowing a popular ticker. The penalty scales with how famous the
+    /// symbol is, since obscure generic tickers are legitimately shared.
+    async fn analyze_symbol_reuse(&self, state: &Arc<AppState>, token: &Token, flags: &mut Vec<String>) -> Result<Decimal> {
+        let symbol = token.symbol.to_lowercase();
+        let weight = self.trading.protected_symbols.get(&symbol).copied();
+        // The token itself is already saved, so anything beyond 1 is another address
+        let others = (state.db.count_tokens_with_symbol(&token.symbol).await? - 1).max(0);
+
+        if weight.is_none() && others == 0 {
+            return Ok(Decimal::ZERO);
+        }
+
+        let penalty = self.trading.symbol_impersonation_penalty * weight.unwrap_or(1.0);
+        let reason = match weight {
+            Some(_) => format!("{} is a well-known ticker", token.symbol),
+            None => format!("{} is shared with {} other tracked token(s)", token.symbol, others),
+        };
+        flags.push(format!("🎭 SYMBOL_IMPERSONATION: {}", reason));
+
+        info!("🎭 Symbol reuse analysis: -{} points", penalty);
+        Ok(-Decimal::try_from(penalty).unwrap_or(Decimal::ZERO))
+    }
+
+    fn analyze_contract_security(&self, token: &Token, metrics: &TokenMetrics, flags: &mut Vec<String>) -> Decimal {
+        let mut score = Decimal::ZERO;
+
+        // No security check covers this chain, so a clean result means nothing
+        if self.trading.security_checks_for(token.chain.as_str()).is_empty() {
+            flags.push(format!("❓ SECURITY_UNCHECKED: No security checks available on {}", token.chain));
+            score -= Decimal::from(10);
+        }
+
         // Check if contract is verified
         if let Some(verified) = metrics.contract_verified {
             if verified {
@@ -237,14 +508,30 @@ impl TokenAnalyzer {
             }
         }
 
+        // Renounced ownership means nobody can change the rules after we buy
+        if metrics.ownership_renounced == Some(true) {
+            flags.push("✅ OWNERSHIP_RENOUNCED: Owner set to the zero address".to_string());
+            score += Decimal::try_from(self.trading.ownership_renounced_bonus).unwrap_or(Decimal::ZERO);
+        }
+
+        // An owner who can pause, blacklist or change tax can trap holders at any time
+        if metrics.owner_privileged == Some(true) {
+            flags.push("⚠️ OWNER_PRIVILEGED: Owner can pause, blacklist or change tax".to_string());
+            score -= Decimal::try_from(self.trading.owner_privileged_penalty).unwrap_or(Decimal::ZERO);
+        }
+
         info!("🔐 Contract security analysis: +{} points", score);
         score
     }
 
-    fn analyze_market_timing(&self, token: &Token, flags: &mut Vec<String>) -> Decimal {
+    /// Ages the token by its contract deploy time when known, otherwise by when we first saw it
+    fn analyze_market_timing(&self, token: &Token, now: DateTime<Utc>, flags: &mut Vec<String>) -> Decimal {
         let mut score = Decimal::ZERO;
-        let now = Utc::now();
-        let age = now.signed_duration_since(token.first_seen);
+        let (born, age_source) = match token.deployed_at {
+            Some(at) => (at, "deployed"),
+            None => (token.first_seen, "first seen"),
+        };
+        let age = now.signed_duration_since(born);
 
         // Very new tokens are riskier but have higher potential
         if age.num_hours() < 1 {
@@ -258,7 +545,7 @@ impl TokenAnalyzer {
             score += Decimal::from(3); // Older token, less moonshot potential
         }
 
-        info!("⏰ Market timing analysis: Age={}h, Score=+{}", age.num_hours(), score);
+        info!("⏰ Market timing analysis: Age={}h ({}), Score=+{}", age.num_hours(), age_source, score);
         score
     }
 
@@ -271,7 +558,7 @@ impl TokenAnalyzer {
             RiskLevel::Extreme
         } else if score < Decimal::from(50) {
             RiskLevel::High
-        } else if score < Decimal::from(70) {
+        } else if score < Decimal::from(70) || flags.iter().any(|f| f.contains("SECURITY_UNCHECKED")) {
             RiskLevel::Medium
         } else {
             RiskLevel::Low
@@ -282,43 +569,35 @@ impl TokenAnalyzer {
         flags.iter().any(|f| 
             f.contains("HONEYPOT") || 
             f.contains("UNVERIFIED_CONTRACT") ||
-            f.contains("LOW_LIQUIDITY")
+            f.contains("LOW_LIQUIDITY") ||
+            f.contains("FEW_BUYERS") ||
+            f.contains("WASH_TRADING") ||
+            f.contains("INCONSISTENT_DATA") ||
+            f.contains("STALE_METRICS") ||
+            f.contains("DUMPING")
         )
     }
 
     fn calculate_potential_multiplier(&self, score: Decimal, metrics: &TokenMetrics, flags: &[String]) -> Option<Decimal> {
-        if score < Decimal::from(60) {
-            return None; // Too risky
-        }
-
-        let mut base_multiplier = Decimal::from(2); // Minimum 2x expectation
-
-        // Higher score = higher potential
-        if score >= Decimal::from(90) {
-            base_multiplier = Decimal::from(100); // Moon potential
-        } else if score >= Decimal::from(85) {
-            base_multiplier = Decimal::from(50);
-        } else if score >= Decimal::from(80) {
-            base_multiplier = Decimal::from(20);
-        } else if score >= Decimal::from(75) {
-            base_multiplier = Decimal::from(10);
-        } else if score >= Decimal::from(70) {
-            base_multiplier = Decimal::from(5);
-        }
+        // Higher score = higher potential; below every tier is too risky to predict anything
+        let score = score.to_f64().unwrap_or(0.0);
+        let mut multiplier = self.trading.target_tiers.iter()
+            .find(|(min_score, _)| score >= *min_score)
+            .map(|(_, multiplier)| *multiplier)?;
 
         // Adjust based on liquidity (lower liquidity = higher potential volatility)
-        if let Some(liquidity) = metrics.liquidity_usd {
-            if liquidity < Decimal::from(50000) {
-                base_multiplier *= Decimal::try_from(1.5).unwrap(); // 50% bonus for low liquidity
+        if let Some(liquidity) = metrics.liquidity_usd.and_then(|l| l.to_f64()) {
+            if liquidity < self.trading.low_liquidity_target_usd {
+                multiplier *= self.trading.low_liquidity_target_factor;
             }
         }
 
         // New tokens have higher potential
         if flags.iter().any(|f| f.contains("VERY_NEW")) {
-            base_multiplier *= Decimal::from(2); // Double potential for very new tokens
+            multiplier *= self.trading.very_new_target_factor;
         }
 
-        Some(base_multiplier)
+        Decimal::try_from(multiplier).ok()
     }
 
     fn make_recommendation(&self, score: Decimal, risk_level: &RiskLevel, is_safe: bool) -> Recommendation {
@@ -331,12 +610,86 @@ impl TokenAnalyzer {
         }
     }
 
+    /// How much to invest in a token: `max_investment_usd` x risk factor x confidence,
+    /// where the risk factor is 1 for Low, 0.5 for Medium and 0 (no trade) for High/Extreme,
+    /// and confidence is the score on a 0-1 scale
+    pub fn position_size(&self, result: &AnalysisResult) -> Decimal {
+        let risk_factor = match result.risk_level {
+            RiskLevel::Low => Decimal::ONE,
+            RiskLevel::Medium => Decimal::new(5, 1),
+            RiskLevel::High | RiskLevel::Extreme => return Decimal::ZERO,
+        };
+        let confidence = (result.score / Decimal::from(100)).clamp(Decimal::ZERO, Decimal::ONE);
+        let max_investment = Decimal::try_from(self.trading.max_investment_usd).unwrap_or(Decimal::from(100));
+
+        (max_investment * risk_factor * confidence).round_dp(2)
+    }
+
+    /// Hold time limit for a trade opened on this result, when its risk level has its own
+    /// (None leaves the trade to the global `max_hold_hours`)
+    pub fn max_hold_hours(&self, result: &AnalysisResult) -> Option<i64> {
+        match result.risk_level {
+            RiskLevel::Medium => self.trading.medium_risk_max_hold_hours,
+            _ => None,
+        }
+    }
+
+    /// How much to put into a simulated trade on this result, if it earns one: a strong Buy
+    /// (score 80+), a non-zero position size and enough upside for the stop-loss risk
+    pub fn trade_investment(&self, token: &Token, result: &AnalysisResult) -> Option<Decimal> {
+        if !matches!(result.recommendation, Recommendation::Buy) || result.score < Decimal::from(80) {
+            return None;
+        }
+
+        let investment = self.position_size(result);
+        if investment <= Decimal::ZERO {
+            info!("👀 Not trading {} (watch only): {:?} risk gets no position", token.symbol, result.risk_level);
+            return None;
+        }
+
+        // The upside to the potential target must justify the stop-loss downside
+        let reward = result.potential_multiplier.unwrap_or(Decimal::ONE) - Decimal::ONE;
+        let risk = Decimal::try_from(self.trading.stop_loss).unwrap_or(Decimal::try_from(0.5).unwrap());
+        let min_ratio = Decimal::try_from(self.trading.min_risk_reward).unwrap_or(Decimal::from(3));
+
+        if risk > Decimal::ZERO && reward / risk < min_ratio {
+            info!("👀 Not trading {} (watch only): risk/reward {:.2}:1 is below the {}:1 minimum",
+                  token.symbol, reward / risk, min_ratio);
+            return None;
+        }
+
+        Some(investment)
+    }
+
+
+    /// Save a BUY signal for a safe result that clears the BUY and global confidence thresholds
     async fn generate_trading_signal(&self, state: &Arc<AppState>, token: &Token, result: &AnalysisResult) -> Result<()> {
+        let confidence = result.score / Decimal::from(100);
+        if !result.is_safe || !self.trading.allows_signal(&SignalType::Buy, confidence) {
+            debug!("🔕 Not signaling {}: score {} (safe: {}) is below the signal bar", token.symbol, result.score, result.is_safe);
+            return Ok(());
+        }
+
+        if state.db.is_ignored(&token.address).await? {
+            debug!("🙈 Not signaling ignored token {}", token.symbol);
+            return Ok(());
+        }
+
+        // A token re-discovered by every scan would otherwise be alerted over and over
+        if let Some(last) = state.db.get_last_signal_for_token(&token.address).await? {
+            let cooldown = chrono::Duration::minutes(self.trading.signal_cooldown_minutes);
+            let recent = Utc::now().signed_duration_since(last.created_at) < cooldown;
+            if matches!(last.signal_type, SignalType::Buy) && (!last.is_sent || recent) {
+                debug!("🔁 Skipping duplicate BUY signal for {} (last one at {})", token.symbol, last.created_at);
+                return Ok(());
+            }
+        }
+
         let signal = TradingSignal {
             id: None,
             token_address: token.address.clone(),
             signal_type: SignalType::Buy,
-            confidence: result.score / Decimal::from(100), // Convert to 0-1 scale
+            confidence,
             reason: format!(
                 "🚀 {} ({}) - Score: {}/100, Risk: {:?}\n📊 Flags: {}\n🎯 Target: {}x",
                 token.symbol,
@@ -351,13 +704,26 @@ impl TokenAnalyzer {
             is_sent: false,
         };
 
-        state.db.save_trading_signal(&signal).await?;
+        state.save_signal(&signal).await?;
         info!("💎 Generated BUY signal for {} with {}x potential", token.symbol, 
               result.potential_multiplier.unwrap_or(Decimal::from(2)));
+        state.record_event(EventKind::Signal, format!("BUY {} (score {})", token.symbol, result.score)).await;
 
         Ok(())
     }
 
+    fn create_blacklisted_result(&self, token_address: &str, reason: &str) -> AnalysisResult {
+        AnalysisResult {
+            token_address: token_address.to_string(),
+            score: Decimal::ZERO,
+            is_safe: false,
+            risk_level: RiskLevel::Extreme,
+            flags: vec![format!("⛔ BLACKLISTED: {}", reason)],
+            potential_multiplier: None,
+            recommendation: Recommendation::Avoid,
+        }
+    }
+
     fn create_insufficient_data_result(&self, token_address: &str) -> AnalysisResult {
         AnalysisResult {
             token_address: token_address.to_string(),
@@ -371,29 +737,241 @@ impl TokenAnalyzer {
     }
 }
 
+/// Flag pushed by enrichment steps when an external API failed, meaning the
+/// corresponding data is unknown rather than confirmed bad
+pub const ENRICHMENT_FAILED_FLAG: &str = "ENRICHMENT_FAILED";
+
+/// A 1h gain above this (percent) is a pump more likely to reverse than continue
+const MAX_HEALTHY_1H_GAIN_PCT: i64 = 50;
+
+/// A 5m fall of at least this much (percent) means the token is being dumped
+const DUMP_5M_DROP_PCT: i64 = 15;
+
+/// How long (hours) after the explorer had no creation record for a token it's asked again
+const CREATION_LOOKUP_RETRY_HOURS: i64 = 24;
+
+/// An analysis missing data because of failures (rather than bad data) is worth retrying
+fn is_incomplete(result: &AnalysisResult) -> bool {
+    result.flags.iter().any(|f| f.contains("INSUFFICIENT_DATA") || f.contains(ENRICHMENT_FAILED_FLAG))
+}
+
+/// What made an analysis incomplete, for the retry record
+fn incomplete_reason(result: &AnalysisResult) -> String {
+    result.flags.iter()
+        .filter(|f| f.contains("INSUFFICIENT_DATA") || f.contains(ENRICHMENT_FAILED_FLAG))
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Schedule another analysis of the token, or give up once `analysis_max_retries` is spent
+async fn schedule_retry(state: &AppState, token: &Token, reason: &str) -> Result<()> {
+    let trading = state.trading.read().await.clone();
+    let attempts = state.db.get_analysis_retry_attempts(&token.address).await? + 1;
+
+    if attempts > trading.analysis_max_retries {
+        warn!("🪦 Giving up on {} after {} retries: {}", token.symbol, attempts - 1, reason);
+        state.db.clear_analysis_retry(&token.address).await?;
+    } else {
+        // Linear backoff: the n-th retry waits n × the base delay
+        let delay = chrono::Duration::seconds(trading.analysis_retry_delay_secs as i64 * attempts as i64);
+        state.db.schedule_analysis_retry(&token.address, reason, Utc::now() + delay).await?;
+        info!("⏳ Analysis of {} incomplete ({}), retry {}/{} scheduled",
+              token.symbol, reason, attempts, trading.analysis_max_retries);
+    }
+
+    Ok(())
+}
+
+/// Re-run analyses that were incomplete because of missing or failed data
+pub async fn start_analysis_retry_loop(state: Arc<AppState>) -> Result<()> {
+    info!("⏳ Starting analysis retry loop...");
+
+    loop {
+        for address in state.db.get_due_analysis_retries(Utc::now()).await? {
+            // One token's failure mustn't hold up the rest of the pass
+            let retried = match state.db.get_token(&address).await {
+                Ok(Some(token)) => analyze_token(state.clone(), token).await.map(|_| ()),
+                Ok(None) => state.db.clear_analysis_retry(&address).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = retried {
+                warn!("⏳ Analysis retry of {} failed: {}", address, e);
+            }
+        }
+
+        if !*state.running.read().await {
+            break;
+        }
+
+        tokio::time::sleep(crate::utils::jittered(30, state.config.scan_intervals.jitter_pct)).await;
+    }
+
+    Ok(())
+}
+
 // Public function to analyze a token (called from scanners)
-pub async fn analyze_token(state: Arc<AppState>, token: Token) -> Result<()> {
-    let analyzer = TokenAnalyzer::new(&state);
+/// Analyze a token and act on the result. Returns `None` if the analysis itself failed
+/// (it is retried like an incomplete one).
+pub async fn analyze_token(state: Arc<AppState>, token: Token) -> Result<Option<AnalysisResult>> {
+    let analyzer = TokenAnalyzer::new(&state).await;
     
     match analyzer.analyze_token(&state, &token).await {
         Ok(result) => {
             info!("✅ Analysis completed for {}: {:?}", token.symbol, result.recommendation);
+
+            if is_incomplete(&result) {
+                schedule_retry(&state, &token, &incomplete_reason(&result)).await?;
+            } else {
+                state.db.clear_analysis_retry(&token.address).await?;
+            }
             
             // If it's a strong buy signal, also start a simulated trade
-            if matches!(result.recommendation, Recommendation::Buy) && result.score >= Decimal::from(80) {
-                start_simulated_trade(&state, &token, &result).await?;
+            if let Some(investment) = analyzer.trade_investment(&token, &result) {
+                let entry = TradeEntry {
+                    investment,
+                    target_multiplier: result.potential_multiplier,
+                    max_hold_hours: analyzer.max_hold_hours(&result),
+                    source: ANALYZER_TRADE,
+                };
+                start_simulated_trade(&state, &token, &entry).await?;
             }
+
+            Ok(Some(result))
         }
         Err(e) => {
             warn!("❌ Analysis failed for {}: {}", token.symbol, e);
+            state.record_event(EventKind::Error, format!("Analysis failed for {}: {}", token.symbol, e)).await;
+            // A failed analysis counts against the retry budget like an incomplete one
+            schedule_retry(&state, &token, &format!("analysis failed: {}", e)).await?;
+            Ok(None)
         }
     }
+}
 
-    Ok(())
+/// Re-enrich a token from every source, store the fresh snapshot and re-analyze it.
+/// Returns `None` when DEX Screener no longer lists the token.
+pub async fn refresh_token(state: &Arc<AppState>, token: &Token) -> Result<Option<AnalysisResult>> {
+    let scanner = DexScreenerScanner::new(&state.config);
+    let mut metrics = match scanner.fetch_metrics(&token.chain, &token.address).await? {
+        Some(metrics) => metrics,
+        None => return Ok(None),
+    };
+
+    run_honeypot_check(state, token, &mut metrics).await;
+    run_authority_check(state, token, &mut metrics).await;
+
+    state.db.save_token_metrics(&metrics).await?;
+    info!("🔄 Refreshed metrics for {} ({})", token.symbol, token.address);
+
+    analyze_token(state.clone(), token.clone()).await
+}
+
+/// Run the rate-limited honeypot simulation if it's enabled and applies to the token's chain,
+/// filling in the verdict and taxes. Returns false when the check was due but couldn't run.
+/// Chains honeypot.is doesn't support leave the verdict unknown.
+async fn run_honeypot_check(state: &AppState, token: &Token, metrics: &mut TokenMetrics) -> bool {
+    let checker = HoneypotChecker::new();
+    if !state.config.enable_honeypot_check
+        || !state.trading.read().await.security_checks_for(token.chain.as_str()).contains(&SecurityCheck::Honeypot)
+        || !checker.supports_chain(&token.chain) {
+        return true;
+    }
+
+    if !state.honeypot_limiter.lock().await.check_rate_limit(token.chain.as_str()).await {
+        warn!("⏳ Honeypot check rate limit reached on {}, skipping {}", token.chain, token.symbol);
+        return false;
+    }
+
+    match checker.check_honeypot(&token.chain, &token.address).await {
+        Ok(None) => true,
+        Ok(Some(report)) => {
+            metrics.is_honeypot = Some(report.is_honeypot);
+            metrics.buy_tax_pct = report.buy_tax_pct.and_then(|t| Decimal::try_from(t).ok());
+            metrics.sell_tax_pct = report.sell_tax_pct.and_then(|t| Decimal::try_from(t).ok());
+            true
+        }
+        Err(e) => {
+            warn!("Honeypot check failed for {}: {}", token.symbol, e);
+            false
+        }
+    }
+}
+
+/// Read the mint account if the authority check applies to the token's chain: a live mint
+/// authority makes it mintable, a freeze authority lets its owner lock holders out.
+/// Returns false when the check was due but couldn't run.
+async fn run_authority_check(state: &AppState, token: &Token, metrics: &mut TokenMetrics) -> bool {
+    if token.chain != Chain::Solana
+        || !state.trading.read().await.security_checks_for(token.chain.as_str()).contains(&SecurityCheck::SolanaAuthority) {
+        return true;
+    }
+
+    match SolanaRpcClient::new(&state.config.solana_rpc_url).get_mint_authorities(&token.address).await {
+        Ok(None) => true,
+        Ok(Some(authorities)) => {
+            metrics.is_mintable = Some(authorities.mint_authority.is_some());
+            metrics.owner_privileged = Some(authorities.freeze_authority.is_some());
+            true
+        }
+        Err(e) => {
+            warn!("Mint authority check failed for {}: {}", token.symbol, e);
+            false
+        }
+    }
+}
+
+/// The token with its contract deploy time and deployer filled in from the block explorer,
+/// stored so they're only looked up once. Left as is off EVM chains, without an Etherscan
+/// key, or when the lookup fails; a lookup that found nothing isn't repeated for
+/// `CREATION_LOOKUP_RETRY_HOURS`.
+async fn with_contract_creation(state: &AppState, token: &Token) -> Token {
+    let mut token = token.clone();
+    let recently_missed = token.creation_checked_at
+        .is_some_and(|at| Utc::now().signed_duration_since(at).num_hours() < CREATION_LOOKUP_RETRY_HOURS);
+    let api_key = match state.config.etherscan_api_key.as_deref() {
+        Some(key) if token.deployed_at.is_none() && !recently_missed && etherscan_chain_id(&token.chain).is_some() => key,
+        _ => return token,
+    };
+
+    match EtherscanClient::new(api_key).get_contract_creation(&token.chain, &token.address).await {
+        Ok(Some(creation)) => {
+            if let Err(e) = state.db.set_token_creation(&token.address, creation.deployed_at, creation.deployer.as_deref()).await {
+                warn!("Failed to store contract creation for {}: {}", token.symbol, e);
+            }
+            token.deployed_at = Some(creation.deployed_at);
+            token.deployer = creation.deployer;
+        }
+        Ok(None) => mark_creation_unknown(state, &mut token).await,
+        Err(e) => {
+            warn!("Contract creation lookup failed for {}: {}", token.symbol, e);
+            mark_creation_unknown(state, &mut token).await;
+        }
+    }
+    token
+}
+
+/// Record a creation lookup that came back empty-handed, so the next analyses skip it
+async fn mark_creation_unknown(state: &AppState, token: &mut Token) {
+    if let Err(e) = state.db.mark_creation_unknown(&token.address).await {
+        warn!("Failed to record the contract creation lookup for {}: {}", token.symbol, e);
+    }
+    token.creation_checked_at = Some(Utc::now());
+}
+
+/// What a new simulated trade is opened with, besides the token and its price
+pub struct TradeEntry<'a> {
+    pub investment: Decimal,
+    pub target_multiplier: Option<Decimal>,
+    pub max_hold_hours: Option<i64>,
+    /// What opened it: `ANALYZER_TRADE`, `WHALE_COPY_TRADE`
+    pub source: &'a str,
 }
 
-async fn start_simulated_trade(state: &Arc<AppState>, token: &Token, result: &AnalysisResult) -> Result<()> {
+/// Open a simulated trade at the token's latest price. Does nothing while there's no price.
+pub async fn start_simulated_trade(state: &AppState, token: &Token, entry: &TradeEntry<'_>) -> Result<()> {
     use crate::models::SimulatedTrade;
+    let investment = entry.investment;
 
     // Get current metrics to determine entry price
     if let Some(metrics) = state.db.get_latest_metrics(&token.address).await? {
@@ -405,17 +983,98 @@ async fn start_simulated_trade(state: &Arc<AppState>, token: &Token, result: &An
                 entry_time: Utc::now(),
                 exit_price: None,
                 exit_time: None,
-                investment_usd: Decimal::try_from(state.config.trading.max_investment_usd).unwrap_or(Decimal::from(100)),
+                investment_usd: investment,
                 profit_loss: None,
                 multiplier: None,
                 exit_reason: None,
                 is_active: true,
+                breakeven_armed: false,
+                remaining_fraction: Decimal::ONE,
+                realized_profit_loss: Decimal::ZERO,
+                targets_hit: 0,
+                target_multiplier: entry.target_multiplier,
+                source: entry.source.to_string(),
+                max_hold_hours: entry.max_hold_hours,
             };
 
+            if state.config.dry_run {
+                info!("🧪 Dry run: not opening ${} trade on {} at ${}", investment, token.symbol, price);
+                return Ok(());
+            }
+
             state.db.save_simulated_trade(&trade).await?;
-            info!("📈 Started simulated trade for {} at ${}", token.symbol, price);
+            info!("📈 Started simulated {} trade for {} at ${} (${} invested)", entry.source, token.symbol, price, investment);
+            state.record_event(EventKind::TradeOpened, format!("{} at ${}", token.symbol, price)).await;
         }
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{test_config, test_state, token};
+
+    fn analyzer() -> TokenAnalyzer {
+        let mut trading = test_config().trading;
+        trading.max_investment_usd = 100.0;
+        TokenAnalyzer::with_trading(trading)
+    }
+
+    /// A score-90 Buy at `risk_level` with plenty of upside
+    fn result(risk_level: RiskLevel) -> AnalysisResult {
+        AnalysisResult {
+            token_address: "TOKEN".to_string(),
+            score: Decimal::from(90),
+            is_safe: true,
+            risk_level,
+            flags: Vec::new(),
+            potential_multiplier: Some(Decimal::from(100)),
+            recommendation: Recommendation::Buy,
+        }
+    }
+
+    #[test]
+    fn position_size_scales_with_risk_and_score() {
+        let analyzer = analyzer();
+
+        assert_eq!(analyzer.position_size(&result(RiskLevel::Low)), Decimal::from(90));
+        assert_eq!(analyzer.position_size(&result(RiskLevel::Medium)), Decimal::from(45));
+        assert_eq!(analyzer.position_size(&result(RiskLevel::High)), Decimal::ZERO);
+        assert_eq!(analyzer.position_size(&result(RiskLevel::Extreme)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn high_and_extreme_risk_get_no_trade() {
+        let analyzer = analyzer();
+        let token = token("TOKEN", "TKN");
+
+        assert_eq!(analyzer.trade_investment(&token, &result(RiskLevel::Low)), Some(Decimal::from(90)));
+        assert_eq!(analyzer.trade_investment(&token, &result(RiskLevel::High)), None);
+        assert_eq!(analyzer.trade_investment(&token, &result(RiskLevel::Extreme)), None);
+    }
+
+    #[tokio::test]
+    async fn only_results_above_the_minimum_confidence_are_signaled() {
+        let mut config = test_config();
+        config.trading.min_signal_confidence = 0.7;
+        config.trading.signal_thresholds.buy = 0.0;
+        let state = test_state(config).await;
+        let analyzer = TokenAnalyzer::new(&state).await;
+
+        for (address, score) in [("BELOW", 69), ("ABOVE", 71)] {
+            let token = token(address, address);
+            state.db.save_token(&token).await.unwrap();
+            let result = AnalysisResult { score: Decimal::from(score), ..result(RiskLevel::Low) };
+            analyzer.generate_trading_signal(&state, &token, &result).await.unwrap();
+        }
+
+        let signaled: Vec<_> = state.db.get_unsent_signals().await.unwrap()
+            .into_iter()
+            .map(|signal| signal.token_address)
+            .collect();
+        assert_eq!(signaled, vec!["ABOVE".to_string()]);
+        assert!(state.db.get_last_signal_for_token("BELOW").await.unwrap().is_none());
+    }
+}
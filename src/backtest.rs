@@ -0,0 +1,221 @@
+// src/backtest.rs
+// "What would my settings have done?":
+// replays stored token_metrics through the analyzer and exit strategies, entirely in memory
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use log::info;
+use rust_decimal::Decimal;
+use std::collections::{HashMap, HashSet};
+
+use crate::analyzers::token_analyzer::TokenAnalyzer;
+use crate::config::Config;
+use crate::database::Database;
+use crate::models::{SimulatedTrade, Token, TokenMetrics, ANALYZER_TRADE};
+use crate::strategies::profit_taking::{ProfitExit, ProfitTakingStrategy};
+use crate::strategies::risk_management::RiskManagement;
+
+/// How much history the analyzer sees for each snapshot, as in a live analysis
+const ANALYSIS_WINDOW: Duration = Duration::hours(1);
+
+/// Trades a replay opened and closed
+#[derive(Debug, Clone, Default)]
+pub struct BacktestReport {
+    pub snapshots: usize,
+    pub trades: Vec<SimulatedTrade>,
+    /// Trades still open at the end, marked at their last price (included in `trades`)
+    pub still_open: usize,
+}
+
+impl BacktestReport {
+    /// Share of trades closed at a profit, 0-100
+    pub fn win_rate(&self) -> Decimal {
+        if self.trades.is_empty() {
+            return Decimal::ZERO;
+        }
+        let wins = self.trades.iter().filter(|t| t.profit_loss.unwrap_or_default() > Decimal::ZERO).count();
+        Decimal::from(wins * 100) / Decimal::from(self.trades.len())
+    }
+
+    pub fn average_multiplier(&self) -> Decimal {
+        if self.trades.is_empty() {
+            return Decimal::ZERO;
+        }
+        let total: Decimal = self.trades.iter().filter_map(|t| t.multiplier).sum();
+        total / Decimal::from(self.trades.len())
+    }
+
+    pub fn total_profit_loss(&self) -> Decimal {
+        self.trades.iter().filter_map(|t| t.profit_loss).sum()
+    }
+}
+
+/// A backtest bound: an RFC 3339 timestamp, or a date meaning midnight UTC
+pub fn parse_time(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid time {:?}: use RFC 3339 or YYYY-MM-DD", value))?;
+    Ok(date.and_time(chrono::NaiveTime::MIN).and_utc())
+}
+
+/// Replay every snapshot in `[from, to)` in order with the configured trading settings.
+/// A token is analyzed on each snapshot until a strong buy opens its one trade; later
+/// snapshots then drive the rug, risk, trailing stop and profit exits.
+/// Only reads from the database.
+pub async fn run_backtest(db: &Database, config: &Config, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<BacktestReport> {
+    let analyzer = TokenAnalyzer::with_trading(config.trading.clone());
+    let risk = RiskManagement::from_config(&config.trading);
+    let profit_taking = ProfitTakingStrategy::from_config(&config.trading);
+
+    let snapshots = db.get_metrics_between(from, to).await?;
+    info!("⏪ Backtesting {} snapshots from {} to {}", snapshots.len(), from, to);
+
+    let mut report = BacktestReport { snapshots: snapshots.len(), ..Default::default() };
+    let mut tokens: HashMap<String, Option<Token>> = HashMap::new();
+    let mut history: HashMap<String, Vec<TokenMetrics>> = HashMap::new();
+    let mut open: HashMap<String, SimulatedTrade> = HashMap::new();
+    let mut entered: HashSet<String> = HashSet::new();
+
+    for metrics in snapshots {
+        let address = metrics.token_address.clone();
+        let seen = history.entry(address.clone()).or_default();
+        seen.push(metrics.clone());
+        let now = metrics.timestamp;
+        let price = match metrics.price_usd {
+            Some(price) if price > Decimal::ZERO => price,
+            _ => continue,
+        };
+
+        if let Some(mut trade) = open.remove(&address) {
+            match exit_trade(&risk, &profit_taking, &mut trade, seen, price, now) {
+                Some(reason) => {
+                    close(&mut trade, price, now, &reason);
+                    report.trades.push(trade);
+                }
+                None => {
+                    open.insert(address, trade);
+                }
+            }
+            continue;
+        }
+        if entered.contains(&address) {
+            continue;
+        }
+
+        let token = match tokens.get(&address) {
+            Some(token) => token.clone(),
+            None => {
+                let token = db.get_token(&address).await?;
+                tokens.insert(address.clone(), token.clone());
+                token
+            }
+        };
+        let token = match token {
+            Some(token) => token,
+            None => continue,
+        };
+
+        let recent: Vec<TokenMetrics> = seen.iter()
+            .filter(|m| m.timestamp >= now - ANALYSIS_WINDOW)
+            .cloned()
+            .collect();
+        let result = analyzer.analyze_snapshot(&token, &metrics, &recent);
+        if let Some(investment) = analyzer.trade_investment(&token, &result) {
+            info!("📈 {} entered {} at ${} (${} invested)", now, token.symbol, price, investment);
+            entered.insert(address.clone());
+            open.insert(address.clone(), SimulatedTrade {
+                id: None,
+                token_address: address,
+                entry_price: price,
+                entry_time: now,
+                exit_price: None,
+                exit_time: None,
+                investment_usd: investment,
+                profit_loss: None,
+                multiplier: None,
+                exit_reason: None,
+                is_active: true,
+                breakeven_armed: false,
+                remaining_fraction: Decimal::ONE,
+                realized_profit_loss: Decimal::ZERO,
+                targets_hit: 0,
+                target_multiplier: result.potential_multiplier,
+                source: ANALYZER_TRADE.to_string(),
+                max_hold_hours: analyzer.max_hold_hours(&result),
+            });
+        }
+    }
+
+    // Whatever is still held is valued at its last known price
+    for (address, mut trade) in open {
+        let last = history.get(&address)
+            .and_then(|seen| seen.iter().rev().find_map(|m| m.price_usd.map(|p| (p, m.timestamp))));
+        let (price, time) = last.unwrap_or((trade.entry_price, to));
+        close(&mut trade, price, time, "Still open at end of backtest");
+        report.trades.push(trade);
+        report.still_open += 1;
+    }
+    report.trades.sort_by_key(|t| t.entry_time);
+
+    info!("⏪ Backtest finished: {} trades ({} still open) over {} snapshots",
+          report.trades.len(), report.still_open, report.snapshots);
+    info!("🏆 Win rate: {:.1}%", report.win_rate());
+    info!("📈 Average multiplier: {:.2}x", report.average_multiplier());
+    info!("💰 Total P&L: ${:.2}", report.total_profit_loss());
+
+    Ok(report)
+}
+
+/// Run the monitor's checks, in the monitor's order, against one new snapshot of a held token.
+/// Partial sales are applied to `trade`; returns the reason when the rest should be sold.
+fn exit_trade(risk: &RiskManagement, profit_taking: &ProfitTakingStrategy, trade: &mut SimulatedTrade,
+              seen: &[TokenMetrics], price: Decimal, now: DateTime<Utc>) -> Option<String> {
+    let since_entry: Vec<TokenMetrics> = seen.iter()
+        .filter(|m| m.timestamp >= trade.entry_time)
+        .cloned()
+        .collect();
+    if risk.detect_liquidity_rug(&since_entry).is_some() {
+        return Some("Liquidity removed".to_string());
+    }
+
+    if risk.arms_breakeven(trade, price) {
+        trade.breakeven_armed = true;
+    }
+    let recent: Vec<TokenMetrics> = match risk.volatility_window() {
+        Some(window) => seen.iter().filter(|m| m.timestamp >= now - window).cloned().collect(),
+        None => Vec::new(),
+    };
+    if let Some(reason) = risk.exit_reason(trade, price, &recent, now) {
+        return Some(reason);
+    }
+    if let Some(reason) = risk.trailing_stop_reason(trade, &since_entry, price) {
+        return Some(reason);
+    }
+
+    match profit_taking.next_exit(trade, price)? {
+        ProfitExit::Close(reason) => Some(reason),
+        ProfitExit::Partial { remaining, targets_hit, reason } => {
+            let sold = trade.remaining_fraction - remaining;
+            trade.realized_profit_loss += trade.partial_profit_loss(price, sold);
+            trade.remaining_fraction = remaining;
+            trade.targets_hit = targets_hit;
+            info!("🪜 {} sold {:.0}% of {}: {}", now, sold * Decimal::from(100), trade.token_address, reason);
+            None
+        }
+    }
+}
+
+fn close(trade: &mut SimulatedTrade, price: Decimal, time: DateTime<Utc>, reason: &str) {
+    let profit_loss = trade.exit_profit_loss(price);
+    let multiplier = price / trade.entry_price;
+    info!("🎯 {} closed {} at {:.2}x (${:.2}): {}", time, trade.token_address, multiplier, profit_loss, reason);
+
+    trade.exit_price = Some(price);
+    trade.exit_time = Some(time);
+    trade.profit_loss = Some(profit_loss);
+    trade.multiplier = Some(multiplier);
+    trade.exit_reason = Some(reason.to_string());
+    trade.is_active = false;
+}
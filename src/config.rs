@@ -4,36 +4,160 @@
 //How often should it check for new coins?
 //What makes a "good" coin vs a "scam" coin?
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use log::warn;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::env;
+use std::fs;
+use std::path::Path;
+use teloxide::types::{ChatId, Recipient};
+
+use crate::models::SignalType;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     // Database
-    pub database_url: String,
+    pub database_url: String, // sqlite:<path> or postgres://...
+    pub db_max_connections: u32,
     
     // Telegram
     pub telegram_token: String,
-    pub telegram_chat_id: i64,
+    /// Chats/channels signals are broadcast to, by numeric id or public "@channel" username;
+    /// the first one is the admin chat. A single chat is accepted too.
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub telegram_chat_ids: Vec<Recipient>,
+    /// Older config files name a single chat; folded into `telegram_chat_ids` on load
+    #[serde(default, skip_serializing, deserialize_with = "optional_chat")]
+    telegram_chat_id: Option<Recipient>,
+    
+    /// How long (seconds since creation) a failing channel keeps retrying a signal before giving up
+    pub signal_delivery_grace_secs: u64,
+    /// Send attempts per channel before a signal is given up on there
+    pub signal_max_send_attempts: u32,
+    
+    // Discord
+    /// Incoming webhook signals are also posted to (disabled when unset)
+    pub discord_webhook_url: Option<String>,
     
     // API Keys (some are optional)
     pub dex_screener_api_key: Option<String>,
     pub birdeye_api_key: Option<String>,
     pub twitter_bearer_token: Option<String>,
+    pub etherscan_api_key: Option<String>, // Whale tracking on EVM chains (Etherscan V2 multichain)
+    pub solscan_api_key: Option<String>,   // Whale tracking on Solana
+    /// Solana JSON-RPC endpoint mint accounts are read from (often carries a provider key)
+    pub solana_rpc_url: String,
     
     // Trading parameters
     pub trading: TradingConfig,
     
+    /// Simulated account size that /balance and ROI are measured against (USD)
+    pub starting_balance_usd: f64,
+    
     // Scanning intervals (in seconds)
     pub scan_intervals: ScanIntervals,
+    
+    /// Order scan candidates are processed in (and which survive the per-cycle cap)
+    pub discovery_order: DiscoveryOrder,
+    
+    /// Only track pairs created within `new_token_window_hours` (pure sniping mode)
+    pub new_tokens_only: bool,
+    pub new_token_window_hours: i64,
+    
+    /// Skip (don't save or signal) tokens younger than this many minutes, aged by contract
+    /// deploy time when the explorer knows it, otherwise by pair creation (0 disables)
+    pub min_token_age_minutes: i64,
+    
+    /// Persist the newest processed pair creation time per source and skip older pairs,
+    /// so restarts don't re-evaluate what was already seen
+    pub scan_cursor_enabled: bool,
+    
+    /// Base token symbols that are never tracked (stablecoins)
+    pub excluded_symbols: Vec<String>,
+    
+    // Webhook receiver for external token submissions
+    /// Address to listen on, e.g. "0.0.0.0:8080" (disabled when unset)
+    pub webhook_bind_addr: Option<String>,
+    /// Bearer token callers must present
+    pub webhook_token: Option<String>,
+    /// Max submissions per client IP per minute
+    pub webhook_rate_limit_per_minute: usize,
+    
+    // Trade archiving
+    /// Move closed trades out of the active trades table
+    pub archive_closed_trades: bool,
+    /// How long (days) after closing a trade is archived
+    pub archive_after_days: i64,
+    
+    // Metrics retention
+    /// Delete metrics snapshots older than this many days, except each token's latest (0 keeps everything)
+    pub metrics_retention_days: i64,
+    /// How often (hours) old metrics snapshots are pruned
+    pub metrics_prune_interval_hours: u64,
+    
+    // Health heartbeat
+    /// How often (minutes) a status heartbeat is posted to Telegram (0 disables it)
+    pub heartbeat_interval_minutes: u64,
+    /// Minutes without a successful DEX Screener scan before the heartbeat raises the alarm
+    pub heartbeat_stale_minutes: i64,
+    
+    /// How many recently filtered-out tokens /rejected keeps (0 only keeps the counts)
+    pub rejection_log_size: usize,
+    
+    /// Keep the per-reason rejection counts in the database so they add up across restarts
+    pub persist_rejection_counts: bool,
+    
+    /// Max /refresh requests per user per hour
+    pub refresh_rate_limit_per_hour: usize,
+    
+    /// Run the honeypot.is simulation during analysis (disable on tight free-tier quotas)
+    pub enable_honeypot_check: bool,
+    
+    /// Max honeypot checks per chain per minute
+    pub honeypot_rate_limit_per_minute: usize,
+    
+    /// Look a token up on GeckoTerminal when DEX Screener has no pair for it
+    pub geckoterminal_fallback: bool,
+    
+    /// How many recent events (signals, trades, errors) /log can show
+    pub activity_log_size: usize,
+    
+    /// Max newly discovered tokens written per database transaction
+    pub db_batch_size: usize,
+    
+    /// Max token analyses running at once; the rest wait their turn
+    pub max_concurrent_analyses: usize,
+    
+    // Service supervision
+    /// How many times a crashed service is restarted before giving up
+    pub supervisor_max_restarts: u32,
+    /// Base restart delay in seconds, doubled after each consecutive restart
+    pub supervisor_backoff_secs: u64,
+    
+    /// Run the full pipeline without side effects: Telegram messages, signals and
+    /// simulated trades are only logged
+    pub dry_run: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TradingConfig {
-    /// Minimum liquidity required (in USD)
+    /// Minimum liquidity required (in USD), for chains without their own entry below
     pub min_liquidity_usd: f64,
     
+    /// Per-chain liquidity floors (in USD) overriding `min_liquidity_usd`.
+    /// Defaults to solana=2000, bsc=5000, base=5000, arbitrum=5000, polygon=3000,
+    /// avalanche=3000; Ethereum and other chains use the global floor.
+    #[serde(default)]
+    pub min_liquidity_by_chain: HashMap<String, f64>,
+    
+    /// Minimum 24h trading volume for a pair to be tracked (in USD)
+    pub min_volume_24h_usd: f64,
+    
+    /// Chains (DEX Screener chain ids, lowercase) whose pairs are tracked
+    pub enabled_chains: Vec<String>,
+    
     /// Maximum percentage of supply held by top holders
     pub max_top_holder_percentage: f64,
     
@@ -43,11 +167,255 @@ pub struct TradingConfig {
     /// Profit targets (multipliers)
     pub profit_targets: Vec<f64>, // [2.0, 5.0, 10.0, 50.0, 100.0]
     
+    /// Share of the original position sold at each successive profit target; the target after
+    /// the last one sells the rest (e.g. [0.25, 0.25] = 25% at 2x, 25% at 5x, rest at 10x).
+    /// Empty sells everything at the first target reached.
+    #[serde(default)]
+    pub scale_out_fractions: Vec<f64>,
+    
+    /// Exit a trade at the target multiplier its buy signal predicted instead of
+    /// walking the `profit_targets` ladder (trades without one still use the ladder)
+    pub use_signal_targets: bool,
+    
+    /// Target multiplier a buy signal predicts from its analysis score, as (min score, multiplier)
+    /// tiers from the highest score down: the first tier the score reaches wins, and a score
+    /// below every tier predicts no target at all
+    pub target_tiers: Vec<(f64, f64)>,
+    
+    /// Pools with less liquidity than this (in USD) get `low_liquidity_target_factor` on their target
+    pub low_liquidity_target_usd: f64,
+    
+    /// Scales the predicted target of thin pools, which move further either way (1.5 = +50%)
+    pub low_liquidity_target_factor: f64,
+    
+    /// Scales the predicted target of tokens flagged VERY_NEW
+    pub very_new_target_factor: f64,
+    
     /// Stop loss percentage (0.5 = 50% loss)
     pub stop_loss: f64,
     
+    /// Close positions held longer than this many hours
+    pub max_hold_hours: i64,
+    
+    /// Hold time limit for trades opened on a medium-risk analysis, which deserve less
+    /// patience than low-risk ones (None: they get `max_hold_hours` too; "off" in the environment)
+    pub medium_risk_max_hold_hours: Option<i64>,
+    
     /// Maximum investment per token (in USD)
     pub max_investment_usd: f64,
+    
+    /// Minimum number of buy transactions in 24h before signaling
+    pub min_buys_24h: u32,
+    
+    /// 24h volume per buy above which activity looks like wash trading (in USD)
+    pub max_volume_per_buy_usd: f64,
+    
+    /// Minimum reward-to-risk ratio (potential upside vs stop loss) to open a trade
+    pub min_risk_reward: f64,
+    
+    /// Relative tolerance when cross-checking price against liquidity/market cap (0.25 = 25%)
+    pub price_consistency_tolerance: f64,
+    
+    /// Close open positions whose recent price swings exceed this percentage (None disables)
+    pub max_hold_volatility_pct: Option<f64>,
+    
+    /// Treat a held token as rugged when liquidity falls more than this percentage between
+    /// two snapshots (None disables; "off" in the environment)
+    pub rug_liquidity_drop_pct: Option<f64>,
+    
+    /// Warn when a held or watched token's 24h volume jumps to this multiple of its
+    /// average over the last hour (None disables; "off" in the environment)
+    pub volume_spike_multiple: Option<f64>,
+    
+    /// Metrics snapshots older than this (minutes) are flagged in analysis and refreshed before trade exits
+    pub max_metrics_age_minutes: i64,
+    
+    /// Once a position reaches this multiplier, its stop moves up to the entry price (None disables)
+    pub breakeven_arm_multiplier: Option<f64>,
+    
+    /// Close a position once it falls this far below its peak since entry (0.2 = 20%; None disables)
+    pub trailing_stop_pct: Option<f64>,
+    
+    /// Exit pressure (0-100, from fading momentum, volume and holders) at which the exit
+    /// analyzer sells a held position outright (None disables the exit analyzer)
+    pub exit_score_threshold: Option<f64>,
+    
+    /// Exit pressure at which the exit analyzer sells `exit_trim_fraction` of a position
+    /// that hasn't been scaled out of yet
+    pub trim_score_threshold: f64,
+    
+    /// Share of the original position an exit analyzer trim sells (0.5 = half)
+    pub exit_trim_fraction: f64,
+    
+    /// Window (in minutes) the holding volatility is measured over
+    pub volatility_window_minutes: i64,
+    
+    /// How holding volatility is measured
+    pub volatility_measure: VolatilityMeasure,
+    
+    /// Hourly price standard deviation (percent) above which analysis flags a token as HIGH_VOLATILITY
+    pub high_volatility_pct: f64,
+    
+    /// Which security checks apply on each chain; chains without any are flagged as unchecked
+    pub security_checks: HashMap<String, Vec<SecurityCheck>>,
+    
+    /// How many times an analysis with missing/failed data is retried before giving up
+    pub analysis_max_retries: u32,
+    
+    /// Base delay (in seconds) before retrying an incomplete analysis, multiplied by the attempt number
+    pub analysis_retry_delay_secs: u64,
+    
+    /// Minimum confidence (0-1) per signal type, applied when generating and delivering signals
+    pub signal_thresholds: SignalThresholds,
+    
+    /// Minimum confidence (0-1) any signal needs, on top of its type's threshold
+    pub min_signal_confidence: f64,
+    
+    /// Don't raise another buy signal for a token within this many minutes of the last one
+    pub signal_cooldown_minutes: i64,
+    
+    /// Score bonus for EVM tokens whose ownership has been renounced
+    pub ownership_renounced_bonus: f64,
+    
+    /// Score penalty when the owner keeps dangerous permissions (pause, blacklist, tax changes)
+    pub owner_privileged_penalty: f64,
+    
+    /// Base score penalty for reusing another token's symbol
+    pub symbol_impersonation_penalty: f64,
+    
+    /// Minimum whale success rate (0-1) for a buy of an unknown token to raise a WHALE ENTRY alert
+    pub whale_entry_min_success_rate: f64,
+    
+    /// Open a simulated trade whenever a tracked whale buys a token (a copy trade), sized as
+    /// `max_investment_usd` x the whale's success rate (50% while it has none)
+    pub copy_whale_trades: bool,
+    
+    /// Estimated gas cost (USD) of a single swap per chain, for round-trip cost estimates
+    pub gas_cost_usd: HashMap<String, f64>,
+    
+    /// Famous symbols (lowercase) and how many times the base penalty impersonating them costs.
+    /// Listed symbols are penalized even when no other token with the symbol is tracked.
+    pub protected_symbols: HashMap<String, f64>,
+}
+
+/// Different signal types have different cost asymmetries: a missed warning
+/// is worse than a missed buy, so warnings can fire at much lower confidence.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignalThresholds {
+    pub buy: f64,
+    pub sell: f64,
+    pub warning: f64,
+    pub whale_movement: f64,
+    pub social_buzz: f64,
+}
+
+impl SignalThresholds {
+    pub fn min_confidence(&self, signal_type: &SignalType) -> Decimal {
+        let threshold = match signal_type {
+            SignalType::Buy => self.buy,
+            SignalType::Sell => self.sell,
+            SignalType::Warning => self.warning,
+            SignalType::WhaleMovement => self.whale_movement,
+            SignalType::SocialBuzz => self.social_buzz,
+        };
+        Decimal::try_from(threshold).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Does a signal of this type and confidence clear its threshold?
+    pub fn allows(&self, signal_type: &SignalType, confidence: Decimal) -> bool {
+        confidence >= self.min_confidence(signal_type)
+    }
+}
+
+impl TradingConfig {
+    /// Security checks that apply to a chain (empty when none do)
+    pub fn security_checks_for(&self, chain: &str) -> &[SecurityCheck] {
+        self.security_checks
+            .get(&chain.to_lowercase())
+            .map(|checks| checks.as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Does a signal clear both its type's threshold and the global minimum?
+    pub fn allows_signal(&self, signal_type: &SignalType, confidence: Decimal) -> bool {
+        let min = Decimal::try_from(self.min_signal_confidence).unwrap_or(Decimal::ZERO);
+        confidence >= min && self.signal_thresholds.allows(signal_type, confidence)
+    }
+
+    /// Minimum liquidity (USD) for a token on a chain, falling back to `min_liquidity_usd`
+    pub fn min_liquidity_for(&self, chain: &str) -> f64 {
+        self.min_liquidity_by_chain.get(&chain.to_lowercase()).copied().unwrap_or(self.min_liquidity_usd)
+    }
+
+    /// Estimated USD gas for one swap on a chain (chains without an estimate assume $1)
+    pub fn gas_cost_for(&self, chain: &str) -> f64 {
+        self.gas_cost_usd.get(&chain.to_lowercase()).copied().unwrap_or(1.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SecurityCheck {
+    /// honeypot.is simulated buy/sell (EVM only)
+    Honeypot,
+    /// Mint and freeze authority read from the mint account over RPC (Solana only)
+    #[serde(rename = "authority")]
+    SolanaAuthority,
+}
+
+impl std::str::FromStr for SecurityCheck {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "honeypot" => Ok(SecurityCheck::Honeypot),
+            "authority" => Ok(SecurityCheck::SolanaAuthority),
+            other => Err(anyhow::anyhow!("Unknown security check: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VolatilityMeasure {
+    /// Standard deviation of price as a percentage of the mean
+    StdDev,
+    /// High-low range as a percentage of the low
+    Range,
+}
+
+impl std::str::FromStr for VolatilityMeasure {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "stddev" => Ok(VolatilityMeasure::StdDev),
+            "range" => Ok(VolatilityMeasure::Range),
+            other => Err(anyhow::anyhow!("Unknown volatility measure: {}", other)),
+        }
+    }
+}
+
+/// Which candidates get analyzed first when a scan finds more than the per-cycle cap
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiscoveryOrder {
+    /// Most recently created pairs first
+    Newest,
+    /// Deepest liquidity first
+    Liquidity,
+}
+
+impl std::str::FromStr for DiscoveryOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "newest" => Ok(DiscoveryOrder::Newest),
+            "liquidity" => Ok(DiscoveryOrder::Liquidity),
+            other => Err(anyhow::anyhow!("Unknown discovery order: {}", other)),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -56,26 +424,81 @@ pub struct ScanIntervals {
     pub pump_fun: u64,
     pub whale_tracking: u64,
     pub twitter_monitoring: u64,
+    pub trade_monitoring: u64,
+    /// Random +/- fraction applied to every loop sleep so scanners don't fire in lockstep (0.1 = ±10%)
+    pub jitter_pct: f64,
+    /// Each scanner waits a random 0..this many seconds before its first scan,
+    /// so services started together don't hit their APIs together (0 disables)
+    #[serde(default)]
+    pub start_stagger_secs: u64,
 }
 
 impl Config {
+    /// Load from the file named by `SNIPER_CONFIG` if set, otherwise from environment variables
     pub fn load() -> Result<Self> {
-        // Try to load from environment variables first
+        match env::var("SNIPER_CONFIG") {
+            Ok(path) => Self::load_from_file(Path::new(&path)),
+            Err(_) => Self::from_env()?.validated(),
+        }
+    }
+
+    /// Load a TOML or JSON file (picked by extension) shaped like `Config`.
+    /// Any field the file leaves out comes from the environment (or its default).
+    pub fn load_from_file(path: &Path) -> Result<Self> {
+        let config: Config = ::config::Config::builder()
+            .add_source(::config::Config::try_from(&Self::from_env()?)?)
+            .add_source(::config::File::from(path))
+            .build()
+            .and_then(|merged| merged.try_deserialize())
+            .with_context(|| format!("Failed to load config file {}", path.display()))?;
+
+        config.validated()
+    }
+
+    /// Everything the environment provides. Required fields it lacks are left empty
+    /// so a config file can still supply them; `validated` catches them otherwise.
+    fn from_env() -> Result<Self> {
         let config = Config {
-            database_url: env::var("DATABASE_URL")
-                .unwrap_or_else(|_| "sqlite:crypto_bot.db".to_string()),
+            database_url: secret_var("DATABASE_URL")?
+                .unwrap_or_else(|| "sqlite:crypto_bot.db".to_string()),
+            
+            db_max_connections: env::var("DB_MAX_CONNECTIONS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
             
-            telegram_token: env::var("TELEGRAM_TOKEN")
-                .expect("TELEGRAM_TOKEN environment variable is required"),
+            telegram_token: secret_var("TELEGRAM_TOKEN")?.unwrap_or_default(),
             
-            telegram_chat_id: env::var("TELEGRAM_CHAT_ID")
-                .expect("TELEGRAM_CHAT_ID environment variable is required")
+            telegram_chat_ids: match env::var("TELEGRAM_CHAT_IDS").or_else(|_| env::var("TELEGRAM_CHAT_ID")) {
+                Ok(v) => v.split(',')
+                    .map(str::trim)
+                    .filter(|id| !id.is_empty())
+                    .map(parse_chat)
+                    .collect::<Result<_>>()
+                    .context("TELEGRAM_CHAT_IDS must be comma-separated chat ids or @channel usernames")?,
+                Err(_) => Vec::new(),
+            },
+            telegram_chat_id: None,
+            
+            signal_delivery_grace_secs: env::var("SIGNAL_DELIVERY_GRACE_SECS")
+                .unwrap_or_else(|_| "300".to_string())
                 .parse()
-                .expect("TELEGRAM_CHAT_ID must be a valid integer"),
+                .unwrap_or(300),
+            
+            signal_max_send_attempts: env::var("SIGNAL_MAX_SEND_ATTEMPTS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            
+            discord_webhook_url: secret_var("DISCORD_WEBHOOK_URL")?,
             
-            dex_screener_api_key: env::var("DEX_SCREENER_API_KEY").ok(),
-            birdeye_api_key: env::var("BIRDEYE_API_KEY").ok(),
-            twitter_bearer_token: env::var("TWITTER_BEARER_TOKEN").ok(),
+            dex_screener_api_key: secret_var("DEX_SCREENER_API_KEY")?,
+            birdeye_api_key: secret_var("BIRDEYE_API_KEY")?,
+            twitter_bearer_token: secret_var("TWITTER_BEARER_TOKEN")?,
+            etherscan_api_key: secret_var("ETHERSCAN_API_KEY")?,
+            solscan_api_key: secret_var("SOLSCAN_API_KEY")?,
+            solana_rpc_url: secret_var("SOLANA_RPC_URL")?
+                .unwrap_or_else(|| "https://api.mainnet-beta.solana.com".to_string()),
             
             trading: TradingConfig {
                 min_liquidity_usd: env::var("MIN_LIQUIDITY_USD")
@@ -83,6 +506,25 @@ impl Config {
                     .parse()
                     .unwrap_or(10000.0),
                 
+                // Format: "chain=usd,chain=usd"
+                min_liquidity_by_chain: parse_chain_map(
+                    &env::var("MIN_LIQUIDITY_BY_CHAIN")
+                        .unwrap_or_else(|_| "solana=2000,bsc=5000,base=5000,arbitrum=5000,polygon=3000,avalanche=3000".to_string()),
+                    |usd| usd.parse().map_err(|e| anyhow::anyhow!("Invalid liquidity floor {}: {}", usd, e)),
+                )?,
+                
+                min_volume_24h_usd: env::var("MIN_VOLUME_24H_USD")
+                    .unwrap_or_else(|_| "10.0".to_string())
+                    .parse()
+                    .unwrap_or(10.0),
+                
+                enabled_chains: env::var("ENABLED_CHAINS")
+                    .unwrap_or_else(|_| "solana,ethereum,bsc,polygon,arbitrum,avalanche,pulsechain".to_string())
+                    .split(',')
+                    .map(|s| s.trim().to_lowercase())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+                
                 max_top_holder_percentage: env::var("MAX_TOP_HOLDER_PCT")
                     .unwrap_or_else(|_| "30.0".to_string())
                     .parse()
@@ -95,15 +537,229 @@ impl Config {
                 
                 profit_targets: vec![2.0, 5.0, 10.0, 50.0, 100.0, 500.0],
                 
+                scale_out_fractions: env::var("SCALE_OUT_FRACTIONS")
+                    .unwrap_or_default()
+                    .split(',')
+                    .filter_map(|s| s.trim().parse().ok())
+                    .collect(),
+                
+                use_signal_targets: env::var("USE_SIGNAL_TARGETS")
+                    .unwrap_or_else(|_| "false".to_string())
+                    .parse()
+                    .unwrap_or(false),
+                
+                // Format: "min_score=multiplier,..." from the highest score down
+                target_tiers: parse_target_tiers(
+                    &env::var("TARGET_TIERS")
+                        .unwrap_or_else(|_| "90=100,85=50,80=20,75=10,70=5,60=2".to_string()),
+                )?,
+                
+                low_liquidity_target_usd: env::var("LOW_LIQUIDITY_TARGET_USD")
+                    .unwrap_or_else(|_| "50000".to_string())
+                    .parse()
+                    .unwrap_or(50000.0),
+                
+                low_liquidity_target_factor: env::var("LOW_LIQUIDITY_TARGET_FACTOR")
+                    .unwrap_or_else(|_| "1.5".to_string())
+                    .parse()
+                    .unwrap_or(1.5),
+                
+                very_new_target_factor: env::var("VERY_NEW_TARGET_FACTOR")
+                    .unwrap_or_else(|_| "2.0".to_string())
+                    .parse()
+                    .unwrap_or(2.0),
+                
                 stop_loss: env::var("STOP_LOSS")
                     .unwrap_or_else(|_| "0.5".to_string())
                     .parse()
                     .unwrap_or(0.5),
                 
+                max_hold_hours: env::var("MAX_HOLD_HOURS")
+                    .unwrap_or_else(|_| "24".to_string())
+                    .parse()
+                    .unwrap_or(24),
+                
+                medium_risk_max_hold_hours: env::var("MEDIUM_RISK_MAX_HOLD_HOURS")
+                    .unwrap_or_else(|_| "6".to_string())
+                    .parse()
+                    .ok(),
+                
                 max_investment_usd: env::var("MAX_INVESTMENT_USD")
                     .unwrap_or_else(|_| "100.0".to_string())
                     .parse()
                     .unwrap_or(100.0),
+                
+                min_buys_24h: env::var("MIN_BUYS_24H")
+                    .unwrap_or_else(|_| "10".to_string())
+                    .parse()
+                    .unwrap_or(10),
+                
+                max_volume_per_buy_usd: env::var("MAX_VOLUME_PER_BUY_USD")
+                    .unwrap_or_else(|_| "10000.0".to_string())
+                    .parse()
+                    .unwrap_or(10000.0),
+                
+                min_risk_reward: env::var("MIN_RISK_REWARD")
+                    .unwrap_or_else(|_| "3.0".to_string())
+                    .parse()
+                    .unwrap_or(3.0),
+                
+                price_consistency_tolerance: env::var("PRICE_CONSISTENCY_TOLERANCE")
+                    .unwrap_or_else(|_| "0.25".to_string())
+                    .parse()
+                    .unwrap_or(0.25),
+                
+                max_hold_volatility_pct: env::var("MAX_HOLD_VOLATILITY_PCT")
+                    .ok()
+                    .and_then(|v| v.parse().ok()),
+                
+                rug_liquidity_drop_pct: env::var("RUG_LIQUIDITY_DROP_PCT")
+                    .unwrap_or_else(|_| "50".to_string())
+                    .parse()
+                    .ok(),
+                
+                volume_spike_multiple: env::var("VOLUME_SPIKE_MULTIPLE")
+                    .unwrap_or_else(|_| "3".to_string())
+                    .parse()
+                    .ok(),
+                
+                max_metrics_age_minutes: env::var("MAX_METRICS_AGE_MINUTES")
+                    .unwrap_or_else(|_| "15".to_string())
+                    .parse()
+                    .unwrap_or(15),
+                
+                breakeven_arm_multiplier: env::var("BREAKEVEN_ARM_MULTIPLIER")
+                    .ok()
+                    .and_then(|v| v.parse().ok()),
+                
+                trailing_stop_pct: env::var("TRAILING_STOP_PCT")
+                    .ok()
+                    .and_then(|v| v.parse().ok()),
+                
+                exit_score_threshold: env::var("EXIT_SCORE_THRESHOLD")
+                    .ok()
+                    .and_then(|v| v.parse().ok()),
+                
+                trim_score_threshold: env::var("TRIM_SCORE_THRESHOLD")
+                    .unwrap_or_else(|_| "50".to_string())
+                    .parse()
+                    .unwrap_or(50.0),
+                
+                exit_trim_fraction: env::var("EXIT_TRIM_FRACTION")
+                    .unwrap_or_else(|_| "0.5".to_string())
+                    .parse()
+                    .unwrap_or(0.5),
+                
+                volatility_window_minutes: env::var("VOLATILITY_WINDOW_MINUTES")
+                    .unwrap_or_else(|_| "30".to_string())
+                    .parse()
+                    .unwrap_or(30),
+                
+                volatility_measure: env::var("VOLATILITY_MEASURE")
+                    .unwrap_or_else(|_| "stddev".to_string())
+                    .parse()
+                    .unwrap_or(VolatilityMeasure::StdDev),
+                
+                high_volatility_pct: env::var("HIGH_VOLATILITY_PCT")
+                    .unwrap_or_else(|_| "20.0".to_string())
+                    .parse()
+                    .unwrap_or(20.0),
+                
+                // Format: "chain=check|check,chain=..." (an empty list means nothing applies)
+                security_checks: parse_chain_map(
+                    &env::var("SECURITY_CHECKS")
+                        .unwrap_or_else(|_| "ethereum=honeypot,bsc=honeypot,polygon=honeypot,arbitrum=honeypot,avalanche=honeypot,base=honeypot,solana=authority".to_string()),
+                    |checks| checks.split('|')
+                        .filter(|c| !c.trim().is_empty())
+                        .map(|c| c.parse())
+                        .collect(),
+                )?,
+                
+                analysis_max_retries: env::var("ANALYSIS_MAX_RETRIES")
+                    .unwrap_or_else(|_| "3".to_string())
+                    .parse()
+                    .unwrap_or(3),
+                
+                analysis_retry_delay_secs: env::var("ANALYSIS_RETRY_DELAY_SECS")
+                    .unwrap_or_else(|_| "120".to_string())
+                    .parse()
+                    .unwrap_or(120),
+                
+                signal_thresholds: SignalThresholds {
+                    buy: env::var("MIN_CONFIDENCE_BUY")
+                        .unwrap_or_else(|_| "0.75".to_string())
+                        .parse()
+                        .unwrap_or(0.75),
+                    
+                    sell: env::var("MIN_CONFIDENCE_SELL")
+                        .unwrap_or_else(|_| "0.0".to_string())
+                        .parse()
+                        .unwrap_or(0.0),
+                    
+                    warning: env::var("MIN_CONFIDENCE_WARNING")
+                        .unwrap_or_else(|_| "0.0".to_string())
+                        .parse()
+                        .unwrap_or(0.0),
+                    
+                    whale_movement: env::var("MIN_CONFIDENCE_WHALE")
+                        .unwrap_or_else(|_| "0.0".to_string())
+                        .parse()
+                        .unwrap_or(0.0),
+                    
+                    social_buzz: env::var("MIN_CONFIDENCE_SOCIAL")
+                        .unwrap_or_else(|_| "0.0".to_string())
+                        .parse()
+                        .unwrap_or(0.0),
+                },
+                
+                min_signal_confidence: env::var("MIN_SIGNAL_CONFIDENCE")
+                    .unwrap_or_else(|_| "0.0".to_string())
+                    .parse()
+                    .unwrap_or(0.0),
+                
+                signal_cooldown_minutes: env::var("SIGNAL_COOLDOWN_MINUTES")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse()
+                    .unwrap_or(60),
+                
+                ownership_renounced_bonus: env::var("OWNERSHIP_RENOUNCED_BONUS")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()
+                    .unwrap_or(5.0),
+                
+                owner_privileged_penalty: env::var("OWNER_PRIVILEGED_PENALTY")
+                    .unwrap_or_else(|_| "8".to_string())
+                    .parse()
+                    .unwrap_or(8.0),
+                
+                symbol_impersonation_penalty: env::var("SYMBOL_IMPERSONATION_PENALTY")
+                    .unwrap_or_else(|_| "5".to_string())
+                    .parse()
+                    .unwrap_or(5.0),
+                
+                whale_entry_min_success_rate: env::var("WHALE_ENTRY_MIN_SUCCESS_RATE")
+                    .unwrap_or_else(|_| "0.6".to_string())
+                    .parse()
+                    .unwrap_or(0.6),
+                
+                copy_whale_trades: env::var("COPY_WHALE_TRADES")
+                    .unwrap_or_else(|_| "false".to_string())
+                    .parse()
+                    .unwrap_or(false),
+                
+                // Format: "chain=usd,chain=usd"
+                gas_cost_usd: parse_chain_map(
+                    &env::var("GAS_COST_USD")
+                        .unwrap_or_else(|_| "ethereum=15,bsc=0.3,polygon=0.05,arbitrum=0.3,avalanche=0.5,base=0.1,solana=0.01".to_string()),
+                    |usd| usd.parse().map_err(|e| anyhow::anyhow!("Invalid gas cost {}: {}", usd, e)),
+                )?,
+                
+                // Format: "symbol=weight,symbol=weight"
+                protected_symbols: parse_chain_map(
+                    &env::var("PROTECTED_SYMBOLS")
+                        .unwrap_or_else(|_| "btc=4,eth=4,sol=4,usdc=4,usdt=4,bnb=3,pepe=3,doge=3,shib=3,wif=2,bonk=2".to_string()),
+                    |weight| weight.parse().map_err(|e| anyhow::anyhow!("Invalid symbol weight {}: {}", weight, e)),
+                )?,
             },
             
             scan_intervals: ScanIntervals {
@@ -126,9 +782,461 @@ impl Config {
                     .unwrap_or_else(|_| "120".to_string())
                     .parse()
                     .unwrap_or(120),
+                
+                trade_monitoring: env::var("TRADE_MONITORING_INTERVAL")
+                    .unwrap_or_else(|_| "60".to_string())
+                    .parse()
+                    .unwrap_or(60),
+                
+                jitter_pct: env::var("SCAN_JITTER_PCT")
+                    .unwrap_or_else(|_| "0.1".to_string())
+                    .parse()
+                    .unwrap_or(0.1),
+                
+                start_stagger_secs: env::var("SCAN_START_STAGGER_SECS")
+                    .unwrap_or_else(|_| "15".to_string())
+                    .parse()
+                    .unwrap_or(15),
             },
+            
+            discovery_order: env::var("DISCOVERY_ORDER")
+                .unwrap_or_else(|_| "newest".to_string())
+                .parse()
+                .unwrap_or(DiscoveryOrder::Newest),
+            
+            new_tokens_only: env::var("NEW_TOKENS_ONLY")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            
+            new_token_window_hours: env::var("NEW_TOKEN_WINDOW_HOURS")
+                .unwrap_or_else(|_| "48".to_string())
+                .parse()
+                .unwrap_or(48),
+            
+            min_token_age_minutes: env::var("MIN_TOKEN_AGE_MINUTES")
+                .unwrap_or_else(|_| "0".to_string())
+                .parse()
+                .unwrap_or(0),
+            
+            scan_cursor_enabled: env::var("SCAN_CURSOR_ENABLED")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            
+            excluded_symbols: env::var("EXCLUDED_SYMBOLS")
+                .unwrap_or_else(|_| "USDC,USDT,DAI,BUSD,TUSD,USDP,FDUSD,PYUSD,USDE,FRAX,LUSD".to_string())
+                .split(',')
+                .map(|s| s.trim().to_uppercase())
+                .filter(|s| !s.is_empty())
+                .collect(),
+            
+            webhook_bind_addr: env::var("WEBHOOK_BIND_ADDR").ok(),
+            webhook_token: secret_var("WEBHOOK_TOKEN")?,
+            webhook_rate_limit_per_minute: env::var("WEBHOOK_RATE_LIMIT_PER_MINUTE")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            
+            archive_closed_trades: env::var("ARCHIVE_CLOSED_TRADES")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            
+            archive_after_days: env::var("ARCHIVE_AFTER_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            
+            metrics_retention_days: env::var("METRICS_RETENTION_DAYS")
+                .unwrap_or_else(|_| "30".to_string())
+                .parse()
+                .unwrap_or(30),
+            
+            metrics_prune_interval_hours: env::var("METRICS_PRUNE_INTERVAL_HOURS")
+                .unwrap_or_else(|_| "6".to_string())
+                .parse()
+                .unwrap_or(6),
+            
+            heartbeat_interval_minutes: env::var("HEARTBEAT_INTERVAL_MINUTES")
+                .unwrap_or_else(|_| "60".to_string())
+                .parse()
+                .unwrap_or(60),
+            
+            heartbeat_stale_minutes: env::var("HEARTBEAT_STALE_MINUTES")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            
+            rejection_log_size: env::var("REJECTION_LOG_SIZE")
+                .unwrap_or_else(|_| "50".to_string())
+                .parse()
+                .unwrap_or(50),
+            
+            persist_rejection_counts: env::var("PERSIST_REJECTION_COUNTS")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            
+            refresh_rate_limit_per_hour: env::var("REFRESH_RATE_LIMIT_PER_HOUR")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            
+            enable_honeypot_check: env::var("ENABLE_HONEYPOT_CHECK")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            
+            geckoterminal_fallback: env::var("GECKOTERMINAL_FALLBACK")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            
+            honeypot_rate_limit_per_minute: env::var("HONEYPOT_RATE_LIMIT_PER_MINUTE")
+                .unwrap_or_else(|_| "10".to_string())
+                .parse()
+                .unwrap_or(10),
+            
+            activity_log_size: env::var("ACTIVITY_LOG_SIZE")
+                .unwrap_or_else(|_| "200".to_string())
+                .parse()
+                .unwrap_or(200),
+            
+            db_batch_size: env::var("DB_BATCH_SIZE")
+                .unwrap_or_else(|_| "50".to_string())
+                .parse()
+                .unwrap_or(50),
+            
+            max_concurrent_analyses: env::var("MAX_CONCURRENT_ANALYSES")
+                .unwrap_or_else(|_| "3".to_string())
+                .parse()
+                .unwrap_or(3),
+            
+            supervisor_max_restarts: env::var("SUPERVISOR_MAX_RESTARTS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            
+            supervisor_backoff_secs: env::var("SUPERVISOR_BACKOFF_SECS")
+                .unwrap_or_else(|_| "5".to_string())
+                .parse()
+                .unwrap_or(5),
+            
+            starting_balance_usd: env::var("STARTING_BALANCE_USD")
+                .unwrap_or_else(|_| "1000".to_string())
+                .parse()
+                .unwrap_or(1000.0),
+            
+            dry_run: env::var("DRY_RUN")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
         };
         
         Ok(config)
     }
-}
\ No newline at end of file
+
+    fn validated(mut self) -> Result<Self> {
+        if let Some(chat_id) = self.telegram_chat_id.take() {
+            self.telegram_chat_ids = vec![chat_id];
+        }
+        if self.telegram_token.is_empty() {
+            anyhow::bail!("telegram_token is required (set TELEGRAM_TOKEN, TELEGRAM_TOKEN_FILE or the config file)");
+        }
+        if self.telegram_chat_ids.is_empty() || self.telegram_chat_ids.contains(&Recipient::Id(ChatId(0))) {
+            anyhow::bail!("telegram_chat_ids is required (set TELEGRAM_CHAT_IDS, TELEGRAM_CHAT_ID or the config file)");
+        }
+        if !self.starting_balance_usd.is_finite() || self.starting_balance_usd <= 0.0 {
+            anyhow::bail!("starting_balance_usd must be positive (got {})", self.starting_balance_usd);
+        }
+        if let Some((chain, usd)) = self.trading.min_liquidity_by_chain.iter().find(|(_, usd)| !usd.is_finite() || **usd < 0.0) {
+            anyhow::bail!("min_liquidity_by_chain for {} must not be negative (got {})", chain, usd);
+        }
+        if self.trading.exit_trim_fraction <= 0.0 || self.trading.exit_trim_fraction >= 1.0 {
+            anyhow::bail!("exit_trim_fraction must be between 0 and 1 (got {})", self.trading.exit_trim_fraction);
+        }
+        // The ladder is walked by counting the targets a price has reached
+        if let Some(pair) = self.trading.profit_targets.windows(2).find(|pair| pair[1] <= pair[0]) {
+            anyhow::bail!("profit_targets must go from the lowest multiplier up ({} comes after {})", pair[1], pair[0]);
+        }
+        if let Some(pair) = self.trading.target_tiers.windows(2).find(|pair| pair[1].0 >= pair[0].0) {
+            anyhow::bail!("target_tiers must go from the highest min score down ({} comes after {})", pair[1].0, pair[0].0);
+        }
+        if let Some((_, multiplier)) = self.trading.target_tiers.iter().find(|(_, multiplier)| !multiplier.is_finite() || *multiplier <= 0.0) {
+            anyhow::bail!("target_tiers multipliers must be positive (got {})", multiplier);
+        }
+        for (name, factor) in [
+            ("low_liquidity_target_factor", self.trading.low_liquidity_target_factor),
+            ("very_new_target_factor", self.trading.very_new_target_factor),
+        ] {
+            if !factor.is_finite() || factor <= 0.0 {
+                anyhow::bail!("{} must be positive (got {})", name, factor);
+            }
+        }
+        if !(0.0..=1.0).contains(&self.trading.min_signal_confidence) {
+            anyhow::bail!("min_signal_confidence must be between 0 and 1 (got {})", self.trading.min_signal_confidence);
+        }
+        Ok(self)
+    }
+}
+
+/// Trading parameters that can be changed while the bot runs (with /set)
+pub const SETTABLE_TRADING_FIELDS: &[&str] = &[
+    "min_liquidity_usd",
+    "min_volume_24h_usd",
+    "max_top_holder_percentage",
+    "min_holders",
+    "min_buys_24h",
+    "stop_loss",
+    "max_hold_hours",
+    "max_investment_usd",
+    "min_risk_reward",
+    "min_signal_confidence",
+    "breakeven_arm_multiplier",
+    "trailing_stop_pct",
+    "use_signal_targets",
+];
+
+impl TradingConfig {
+    /// Change one of `SETTABLE_TRADING_FIELDS` from its text form.
+    /// Optional parameters are disabled with "off".
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        match key {
+            "min_liquidity_usd" => self.min_liquidity_usd = parse_amount(key, value)?,
+            "min_volume_24h_usd" => self.min_volume_24h_usd = parse_amount(key, value)?,
+            "max_top_holder_percentage" => self.max_top_holder_percentage = parse_amount(key, value)?,
+            "min_holders" => self.min_holders = parse_setting(key, value)?,
+            "min_buys_24h" => self.min_buys_24h = parse_setting(key, value)?,
+            "stop_loss" => self.stop_loss = parse_fraction(key, value)?,
+            "max_hold_hours" => self.max_hold_hours = parse_setting::<u32>(key, value)?.into(),
+            "max_investment_usd" => self.max_investment_usd = parse_amount(key, value)?,
+            "min_risk_reward" => self.min_risk_reward = parse_amount(key, value)?,
+            "min_signal_confidence" => self.min_signal_confidence = parse_fraction(key, value)?,
+            "breakeven_arm_multiplier" => self.breakeven_arm_multiplier = parse_optional(key, value, parse_amount)?,
+            "trailing_stop_pct" => self.trailing_stop_pct = parse_optional(key, value, parse_fraction)?,
+            "use_signal_targets" => self.use_signal_targets = parse_setting(key, value)?,
+            _ => anyhow::bail!("Unknown setting {} (settable: {})", key, SETTABLE_TRADING_FIELDS.join(", ")),
+        }
+        Ok(())
+    }
+}
+
+fn parse_setting<T: std::str::FromStr>(key: &str, value: &str) -> Result<T> {
+    value.parse().map_err(|_| anyhow::anyhow!("Invalid value for {}: {}", key, value))
+}
+
+/// A non-negative number
+fn parse_amount(key: &str, value: &str) -> Result<f64> {
+    let amount: f64 = parse_setting(key, value)?;
+    if !amount.is_finite() || amount < 0.0 {
+        anyhow::bail!("{} must be a non-negative number (got {})", key, value);
+    }
+    Ok(amount)
+}
+
+/// A fraction between 0 and 1 (0.2 = 20%)
+fn parse_fraction(key: &str, value: &str) -> Result<f64> {
+    let fraction = parse_amount(key, value)?;
+    if fraction > 1.0 {
+        anyhow::bail!("{} must be between 0 and 1 (got {})", key, value);
+    }
+    Ok(fraction)
+}
+
+fn parse_optional(key: &str, value: &str, parse: fn(&str, &str) -> Result<f64>) -> Result<Option<f64>> {
+    if value.eq_ignore_ascii_case("off") {
+        return Ok(None);
+    }
+    parse(key, value).map(Some)
+}
+
+/// Config fields whose values must never be shown
+const SECRET_FIELDS: &[&str] = &[
+    "telegram_token",
+    "dex_screener_api_key",
+    "birdeye_api_key",
+    "twitter_bearer_token",
+    "etherscan_api_key",
+    "solscan_api_key",
+    "solana_rpc_url",
+    "webhook_token",
+    "discord_webhook_url",
+];
+
+impl Config {
+    /// Human-readable dump of the effective configuration, grouped by section,
+    /// with secrets redacted
+    pub fn redacted_view(&self) -> String {
+        let value = match serde_json::to_value(self) {
+            Ok(serde_json::Value::Object(map)) => map,
+            _ => return "⚠️ Could not render configuration".to_string(),
+        };
+
+        let mut general = String::new();
+        let mut sections = String::new();
+
+        for (key, value) in &value {
+            match value {
+                serde_json::Value::Object(fields) => {
+                    sections.push_str(&format!("\n📁 {}\n", key));
+                    for (field, value) in fields {
+                        sections.push_str(&format!("• {}: {}\n", field, render_value(field, value)));
+                    }
+                }
+                _ => general.push_str(&format!("• {}: {}\n", key, render_value(key, value))),
+            }
+        }
+
+        format!("⚙️ Effective Configuration\n\n📁 general\n{}{}", general, sections)
+    }
+}
+
+impl Config {
+    /// Values of every secret field, for scrubbing free-form text
+    pub fn secret_values(&self) -> Vec<String> {
+        let value = serde_json::to_value(self).unwrap_or_default();
+        SECRET_FIELDS.iter()
+            .filter_map(|field| value.get(field).and_then(|v| v.as_str()).map(str::to_string))
+            .collect()
+    }
+}
+
+fn render_value(key: &str, value: &serde_json::Value) -> String {
+    let is_secret = SECRET_FIELDS.contains(&key)
+        // Connection strings can embed credentials
+        || (key == "database_url" && value.as_str().is_some_and(|url| url.contains('@')));
+
+    match value {
+        serde_json::Value::Null => "not set".to_string(),
+        _ if is_secret => "🔒 set (redacted)".to_string(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// A chat as written in the environment: a numeric id, or a channel's "@username"
+fn parse_chat(value: &str) -> Result<Recipient> {
+    if let Ok(id) = value.parse() {
+        return Ok(Recipient::Id(ChatId(id)));
+    }
+    match value.strip_prefix('@') {
+        Some(name) if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') => {
+            Ok(Recipient::ChannelUsername(value.to_string()))
+        }
+        _ => anyhow::bail!("Invalid Telegram chat {:?}: use a numeric id or @channelusername", value),
+    }
+}
+
+/// A chat in a config file: a number, or a string holding either form
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ChatValue {
+    Id(i64),
+    Text(String),
+}
+
+impl ChatValue {
+    fn into_recipient<E: serde::de::Error>(self) -> std::result::Result<Recipient, E> {
+        match self {
+            ChatValue::Id(id) => Ok(Recipient::Id(ChatId(id))),
+            ChatValue::Text(text) => parse_chat(&text).map_err(E::custom),
+        }
+    }
+}
+
+/// Accept either a single chat or a list of them
+fn one_or_many<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Vec<Recipient>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(ChatValue),
+        Many(Vec<ChatValue>),
+    }
+
+    match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(chat) => Ok(vec![chat.into_recipient()?]),
+        OneOrMany::Many(chats) => chats.into_iter().map(ChatValue::into_recipient).collect(),
+    }
+}
+
+fn optional_chat<'de, D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Option<Recipient>, D::Error> {
+    Option::<ChatValue>::deserialize(deserializer)?
+        .map(ChatValue::into_recipient)
+        .transpose()
+}
+
+/// Parse "min_score=multiplier,..." target tiers, keeping their order
+fn parse_target_tiers(raw: &str) -> Result<Vec<(f64, f64)>> {
+    raw.split(',')
+        .filter(|e| !e.trim().is_empty())
+        .map(|entry| {
+            let (score, multiplier) = entry.split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Invalid target tier: {} (expected min_score=multiplier)", entry))?;
+            Ok((parse_amount("TARGET_TIERS", score.trim())?, parse_amount("TARGET_TIERS", multiplier.trim())?))
+        })
+        .collect()
+}
+
+/// Parse a "chain=value,chain=value" map, lowercasing chain names
+fn parse_chain_map<T>(raw: &str, parse_value: impl Fn(&str) -> Result<T>) -> Result<HashMap<String, T>> {
+    let mut map = HashMap::new();
+
+    for entry in raw.split(',').filter(|e| !e.trim().is_empty()) {
+        let (chain, value) = entry.split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid chain mapping entry: {}", entry))?;
+        map.insert(chain.trim().to_lowercase(), parse_value(value.trim())?);
+    }
+
+    Ok(map)
+}
+
+/// Read a sensitive value from the environment.
+///
+/// `<NAME>_FILE` (Docker/K8s style mounted secret) takes precedence over the
+/// plain `<NAME>` variable; the file contents are trimmed. If both are set the
+/// file wins and a warning is logged. An unreadable secret file is an error
+/// rather than a silent fallback.
+fn secret_var(name: &str) -> Result<Option<String>> {
+    let file_var = format!("{}_FILE", name);
+
+    if let Ok(path) = env::var(&file_var) {
+        if env::var(name).is_ok() {
+            warn!("Both {} and {} are set, using {}", name, file_var, file_var);
+        }
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {} from {}", file_var, path))?;
+        return Ok(Some(contents.trim().to_string()));
+    }
+
+    Ok(env::var(name).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::test_config;
+
+    #[test]
+    fn signals_need_the_global_minimum_confidence() {
+        let mut trading = test_config().trading;
+        trading.min_signal_confidence = 0.7;
+        trading.signal_thresholds.buy = 0.0;
+
+        assert!(!trading.allows_signal(&SignalType::Buy, Decimal::new(69, 2)));
+        assert!(trading.allows_signal(&SignalType::Buy, Decimal::new(70, 2)));
+        assert!(trading.allows_signal(&SignalType::Buy, Decimal::new(71, 2)));
+    }
+
+    #[test]
+    fn profit_targets_must_be_ascending() {
+        let mut config = test_config();
+        config.trading.profit_targets = vec![5.0, 2.0];
+        assert!(config.validated().is_err());
+
+        let mut config = test_config();
+        config.trading.profit_targets = vec![2.0, 5.0];
+        assert!(config.validated().is_ok());
+    }
+}
@@ -1,17 +1,62 @@
 use anyhow::Result;
 use chrono::{DateTime, Utc};
-use sqlx::{SqlitePool, Row};
-use log::{info, error};
+use sqlx::{database::HasArguments, query::Query, ColumnIndex, Decode, Encode, PgPool, Row, SqlitePool, Type};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
+use log::info;
 use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
 
-use crate::models::{Token, TokenMetrics, TradingSignal, SimulatedTrade, WhaleWallet, WhaleTransaction};
+use crate::models::{Token, TokenMetrics, TradingSignal, SimulatedTrade, WhaleWallet, WhaleTransaction,
+                    AnalysisResult, RiskLevel, Recommendation};
+
+/// Columns shared by simulated_trades and trade_archive
+const TRADE_COLUMNS: &str = "id, token_address, entry_price, entry_time, exit_price, exit_time, \
+    investment_usd, profit_loss, multiplier, exit_reason, is_active, breakeven_armed, \
+    remaining_fraction, realized_profit_loss, targets_hit, target_multiplier, source, max_hold_hours";
+
+/// A token's newest metrics snapshot; served by idx_token_metrics_token_time
+const LATEST_METRICS_QUERY: &str = "SELECT * FROM token_metrics WHERE token_address = $1 ORDER BY timestamp DESC, id DESC LIMIT 1";
+
+/// Signals still waiting for delivery, oldest first; served by idx_trading_signals_sent_created
+const UNSENT_SIGNALS_QUERY: &str = "SELECT * FROM trading_signals WHERE is_sent = FALSE ORDER BY created_at ASC";
+
+/// How long an SQLite write waits for a competing writer before failing with "database is locked"
+const SQLITE_BUSY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Which backend the bot is talking to, picked from the database URL
+enum Pool {
+    Sqlite(SqlitePool),
+    Postgres(PgPool),
+}
+
+/// Run the same sqlx code against whichever backend is connected,
+/// with `$pool` bound to the concrete pool inside `$body`
+macro_rules! with_pool {
+    ($self:ident, $pool:ident => $body:expr) => {
+        match &$self.pool {
+            Pool::Sqlite($pool) => $body,
+            Pool::Postgres($pool) => $body,
+        }
+    };
+}
 
 pub struct Database {
-    pool: SqlitePool,
+    pool: Pool,
 }
 
 impl Database {
-    pub async fn new(database_url: &str) -> Result<Self> {
+    pub async fn new(database_url: &str, max_connections: u32) -> Result<Self> {
+        if database_url.starts_with("postgres://") || database_url.starts_with("postgresql://") {
+            info!("Connecting to Postgres database");
+            let pool = PgPoolOptions::new()
+                .max_connections(max_connections)
+                .connect(database_url)
+                .await?;
+            return Ok(Database { pool: Pool::Postgres(pool) });
+        }
+
         info!("Connecting to database: {}", database_url);
         
         // Extract the file path from the database URL
@@ -25,8 +70,15 @@ impl Database {
             }
         }
         
-        let pool = SqlitePool::connect(database_url).await?;
-        Ok(Database { pool })
+        // WAL lets readers carry on while a scanner writes; the busy timeout covers writer-vs-writer
+        let options = SqliteConnectOptions::from_str(database_url)?
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(SQLITE_BUSY_TIMEOUT);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(max_connections)
+            .connect_with(options)
+            .await?;
+        Ok(Database { pool: Pool::Sqlite(pool) })
     }
 
     /// Run database migrations to create tables
@@ -34,7 +86,7 @@ impl Database {
         info!("Running database migrations...");
         
         // Create tokens table
-        sqlx::query(r#"
+        self.execute_ddl(r#"
             CREATE TABLE IF NOT EXISTS tokens (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 address TEXT UNIQUE NOT NULL,
@@ -46,10 +98,10 @@ impl Database {
                 first_seen TEXT NOT NULL,
                 is_active BOOLEAN NOT NULL DEFAULT TRUE
             )
-        "#).execute(&self.pool).await?;
+        "#).await?;
 
         // Create token_metrics table
-        sqlx::query(r#"
+        self.execute_ddl(r#"
             CREATE TABLE IF NOT EXISTS token_metrics (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 token_address TEXT NOT NULL,
@@ -58,6 +110,7 @@ impl Database {
                 market_cap_usd TEXT,
                 liquidity_usd TEXT,
                 volume_24h_usd TEXT,
+                buy_count_24h INTEGER,
                 total_supply TEXT,
                 circulating_supply TEXT,
                 holder_count INTEGER,
@@ -66,12 +119,24 @@ impl Database {
                 is_mintable BOOLEAN,
                 has_proxy BOOLEAN,
                 contract_verified BOOLEAN,
+                ownership_renounced BOOLEAN,
+                owner_privileged BOOLEAN,
+                buy_tax_pct TEXT,
+                sell_tax_pct TEXT,
+                price_consistent BOOLEAN,
+                volume_5m_usd TEXT,
+                volume_1h_usd TEXT,
+                volume_6h_usd TEXT,
+                price_change_5m_pct TEXT,
+                price_change_1h_pct TEXT,
+                price_change_6h_pct TEXT,
+                price_change_24h_pct TEXT,
                 FOREIGN KEY (token_address) REFERENCES tokens (address)
             )
-        "#).execute(&self.pool).await?;
+        "#).await?;
 
         // Create trading_signals table
-        sqlx::query(r#"
+        self.execute_ddl(r#"
             CREATE TABLE IF NOT EXISTS trading_signals (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 token_address TEXT NOT NULL,
@@ -83,10 +148,100 @@ impl Database {
                 is_sent BOOLEAN NOT NULL DEFAULT FALSE,
                 FOREIGN KEY (token_address) REFERENCES tokens (address)
             )
-        "#).execute(&self.pool).await?;
+        "#).await?;
+
+        // Create signal_deliveries table (per-channel delivery state of each signal)
+        self.execute_ddl(r#"
+            CREATE TABLE IF NOT EXISTS signal_deliveries (
+                signal_id INTEGER NOT NULL,
+                channel TEXT NOT NULL,
+                status TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                updated_at TEXT NOT NULL,
+                PRIMARY KEY (signal_id, channel),
+                FOREIGN KEY (signal_id) REFERENCES trading_signals (id)
+            )
+        "#).await?;
+
+        // Create analysis_retries table (tokens whose analysis hit missing/failed data)
+        self.execute_ddl(r#"
+            CREATE TABLE IF NOT EXISTS analysis_retries (
+                token_address TEXT PRIMARY KEY,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                next_attempt_at TEXT NOT NULL,
+                last_reason TEXT,
+                FOREIGN KEY (token_address) REFERENCES tokens (address)
+            )
+        "#).await?;
+
+        // Create analysis_results table (every completed analysis, for score history)
+        self.execute_ddl(r#"
+            CREATE TABLE IF NOT EXISTS analysis_results (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                token_address TEXT NOT NULL,
+                score TEXT NOT NULL,
+                is_safe BOOLEAN NOT NULL,
+                risk_level TEXT NOT NULL,
+                recommendation TEXT NOT NULL,
+                potential_multiplier TEXT,
+                flags TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (token_address) REFERENCES tokens (address)
+            )
+        "#).await?;
+
+        // Create social_mentions table (how often a token was mentioned on Twitter/X per poll window)
+        self.execute_ddl(r#"
+            CREATE TABLE IF NOT EXISTS social_mentions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                token_address TEXT NOT NULL,
+                timestamp TEXT NOT NULL,
+                mention_count INTEGER NOT NULL,
+                window_secs INTEGER NOT NULL,
+                FOREIGN KEY (token_address) REFERENCES tokens (address)
+            )
+        "#).await?;
+
+        // Create watchlist table (tokens the user follows by hand)
+        self.execute_ddl(r#"
+            CREATE TABLE IF NOT EXISTS watchlist (
+                token_address TEXT PRIMARY KEY,
+                added_at TEXT NOT NULL,
+                note TEXT,
+                FOREIGN KEY (token_address) REFERENCES tokens (address)
+            )
+        "#).await?;
+
+        // Create blacklist table (token or deployer addresses that are never tracked).
+        // No foreign key: blacklisted tokens are usually never saved.
+        self.execute_ddl(r#"
+            CREATE TABLE IF NOT EXISTS blacklist (
+                address TEXT PRIMARY KEY,
+                reason TEXT,
+                added_at TEXT NOT NULL
+            )
+        "#).await?;
+
+        // Create scanner_state table (per-source discovery cursor that survives restarts)
+        self.execute_ddl(r#"
+            CREATE TABLE IF NOT EXISTS scanner_state (
+                source TEXT PRIMARY KEY,
+                last_pair_created_at INTEGER NOT NULL,
+                updated_at TEXT NOT NULL
+            )
+        "#).await?;
+
+        // Create rejection_counts table (tokens turned away per filter, across restarts)
+        self.execute_ddl(r#"
+            CREATE TABLE IF NOT EXISTS rejection_counts (
+                reason TEXT PRIMARY KEY,
+                count INTEGER NOT NULL
+            )
+        "#).await?;
 
         // Create simulated_trades table
-        sqlx::query(r#"
+        self.execute_ddl(r#"
             CREATE TABLE IF NOT EXISTS simulated_trades (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 token_address TEXT NOT NULL,
@@ -101,10 +256,38 @@ impl Database {
                 is_active BOOLEAN NOT NULL DEFAULT TRUE,
                 FOREIGN KEY (token_address) REFERENCES tokens (address)
             )
-        "#).execute(&self.pool).await?;
+        "#).await?;
+
+        // Create trade_archive table (closed trades moved out of the hot table)
+        self.execute_ddl(r#"
+            CREATE TABLE IF NOT EXISTS trade_archive (
+                id INTEGER PRIMARY KEY,
+                token_address TEXT NOT NULL,
+                entry_price TEXT NOT NULL,
+                entry_time TEXT NOT NULL,
+                exit_price TEXT,
+                exit_time TEXT,
+                investment_usd TEXT NOT NULL,
+                profit_loss TEXT,
+                multiplier TEXT,
+                exit_reason TEXT,
+                is_active BOOLEAN NOT NULL DEFAULT FALSE
+            )
+        "#).await?;
+
+        // Create trade_targets table (manual USD price targets per trade).
+        // No foreign key: trades move to trade_archive once closed.
+        self.execute_ddl(r#"
+            CREATE TABLE IF NOT EXISTS trade_targets (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                trade_id INTEGER NOT NULL,
+                target_price TEXT NOT NULL,
+                created_at TEXT NOT NULL
+            )
+        "#).await?;
 
         // Create whale_wallets table
-        sqlx::query(r#"
+        self.execute_ddl(r#"
             CREATE TABLE IF NOT EXISTS whale_wallets (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 address TEXT UNIQUE NOT NULL,
@@ -116,10 +299,10 @@ impl Database {
                 is_active BOOLEAN NOT NULL DEFAULT TRUE,
                 created_at TEXT NOT NULL
             )
-        "#).execute(&self.pool).await?;
+        "#).await?;
 
         // Create whale_transactions table
-        sqlx::query(r#"
+        self.execute_ddl(r#"
             CREATE TABLE IF NOT EXISTS whale_transactions (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 whale_address TEXT NOT NULL,
@@ -132,386 +315,1494 @@ impl Database {
                 FOREIGN KEY (whale_address) REFERENCES whale_wallets (address),
                 FOREIGN KEY (token_address) REFERENCES tokens (address)
             )
-        "#).execute(&self.pool).await?;
+        "#).await?;
+
+        // Indexes for the hot lookups (latest metrics, unsent signals, open trades)
+        self.execute_ddl("CREATE INDEX IF NOT EXISTS idx_token_metrics_token_time ON token_metrics (token_address, timestamp)").await?;
+        self.execute_ddl("CREATE INDEX IF NOT EXISTS idx_trading_signals_sent_created ON trading_signals (is_sent, created_at)").await?;
+        self.execute_ddl("CREATE INDEX IF NOT EXISTS idx_simulated_trades_active ON simulated_trades (is_active)").await?;
+
+        // Columns added after the initial schema
+        self.ensure_column("token_metrics", "buy_count_24h", "INTEGER").await?;
+        self.ensure_column("token_metrics", "price_consistent", "BOOLEAN").await?;
+        self.ensure_column("token_metrics", "ownership_renounced", "BOOLEAN").await?;
+        self.ensure_column("token_metrics", "owner_privileged", "BOOLEAN").await?;
+        self.ensure_column("token_metrics", "buy_tax_pct", "TEXT").await?;
+        self.ensure_column("token_metrics", "sell_tax_pct", "TEXT").await?;
+        for column in ["volume_5m_usd", "volume_1h_usd", "volume_6h_usd",
+                       "price_change_5m_pct", "price_change_1h_pct", "price_change_6h_pct", "price_change_24h_pct"] {
+            self.ensure_column("token_metrics", column, "TEXT").await?;
+        }
+        self.ensure_column("tokens", "ignored", "BOOLEAN NOT NULL DEFAULT FALSE").await?;
+        self.ensure_column("tokens", "deployed_at", "TEXT").await?;
+        self.ensure_column("tokens", "deployer", "TEXT").await?;
+        self.ensure_column("tokens", "creation_checked_at", "TEXT").await?;
+        self.ensure_column("signal_deliveries", "next_attempt_at", "TEXT").await?;
+        for table in ["simulated_trades", "trade_archive"] {
+            self.ensure_column(table, "breakeven_armed", "BOOLEAN NOT NULL DEFAULT FALSE").await?;
+            self.ensure_column(table, "remaining_fraction", "TEXT NOT NULL DEFAULT '1'").await?;
+            self.ensure_column(table, "realized_profit_loss", "TEXT NOT NULL DEFAULT '0'").await?;
+            self.ensure_column(table, "targets_hit", "INTEGER NOT NULL DEFAULT 0").await?;
+            self.ensure_column(table, "target_multiplier", "TEXT").await?;
+            self.ensure_column(table, "source", "TEXT NOT NULL DEFAULT 'analyzer'").await?;
+            self.ensure_column(table, "max_hold_hours", "INTEGER").await?;
+        }
+
+        // Every trade, live or archived, for history/stats queries.
+        // Recreated each run so it picks up new trade columns.
+        self.execute_ddl("DROP VIEW IF EXISTS all_trades").await?;
+        self.execute_ddl(&format!(
+            "CREATE VIEW all_trades AS SELECT {cols} FROM simulated_trades UNION ALL SELECT {cols} FROM trade_archive",
+            cols = TRADE_COLUMNS
+        )).await?;
 
         info!("✅ Database migrations completed");
         Ok(())
     }
 
+    /// Add a column to an existing table if it isn't there yet
+    async fn ensure_column(&self, table: &str, column: &str, definition: &str) -> Result<()> {
+        let columns: Vec<String> = match &self.pool {
+            Pool::Sqlite(pool) => sqlx::query_scalar("SELECT name FROM pragma_table_info($1)")
+                .bind(table)
+                .fetch_all(pool)
+                .await?,
+            Pool::Postgres(pool) => sqlx::query_scalar(
+                "SELECT column_name::TEXT FROM information_schema.columns WHERE table_name = $1 AND table_schema = current_schema()",
+            )
+                .bind(table)
+                .fetch_all(pool)
+                .await?,
+        };
+
+        if !columns.iter().any(|name| name == column) {
+            self.execute_ddl(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition)).await?;
+            info!("Added column {}.{}", table, column);
+        }
+
+        Ok(())
+    }
+
+    /// Run a schema statement, written in SQLite's dialect.
+    /// Postgres gets BIGSERIAL/BIGINT in place of SQLite's integer keys and columns.
+    async fn execute_ddl(&self, sql: &str) -> Result<()> {
+        match &self.pool {
+            Pool::Sqlite(pool) => {
+                sqlx::query(sql).execute(pool).await?;
+            }
+            Pool::Postgres(pool) => {
+                let sql = sql
+                    .replace("INTEGER PRIMARY KEY AUTOINCREMENT", "BIGSERIAL PRIMARY KEY")
+                    .replace("INTEGER", "BIGINT");
+                sqlx::query(&sql).execute(pool).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Cheap round-trip to verify the database is reachable
+    pub async fn ping(&self) -> Result<()> {
+        with_pool!(self, pool => {
+            sqlx::query("SELECT 1").execute(pool).await?;
+            Ok(())
+        })
+    }
+
     // TOKEN OPERATIONS
     pub async fn save_token(&self, token: &Token) -> Result<i64> {
-        let result = sqlx::query(r#"
-            INSERT OR REPLACE INTO tokens 
-            (address, symbol, name, chain, source, created_at, first_seen, is_active)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-        "#)
-        .bind(&token.address)
-        .bind(&token.symbol)
-        .bind(&token.name)
-        .bind(&token.chain)
-        .bind(&token.source)
-        .bind(token.created_at.to_rfc3339())
-        .bind(token.first_seen.to_rfc3339())
-        .bind(token.is_active)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_rowid())
+        with_pool!(self, pool => {
+            let rows = insert_token_query(token)
+                .fetch_all(pool)
+                .await?;
+
+            Ok(returned(rows)?.get("id"))
+        })
+    }
+
+    /// Save newly discovered tokens and their first metrics snapshot in one transaction,
+    /// so a batch is either fully visible or not written at all
+    pub async fn save_token_batch(&self, batch: &[(Token, TokenMetrics)]) -> Result<()> {
+        with_pool!(self, pool => {
+            let mut tx = pool.begin().await?;
+
+            for (token, metrics) in batch {
+                insert_token_query(token).execute(&mut *tx).await?;
+                insert_metrics_query(metrics).execute(&mut *tx).await?;
+            }
+
+            tx.commit().await?;
+            Ok(())
+        })
     }
 
     pub async fn get_token(&self, address: &str) -> Result<Option<Token>> {
-        let row = sqlx::query(r#"
-            SELECT * FROM tokens WHERE address = ?
-        "#)
-        .bind(address)
-        .fetch_optional(&self.pool)
-        .await?;
-
-        if let Some(row) = row {
-            Ok(Some(Token {
-                id: Some(row.get("id")),
-                address: row.get("address"),
-                symbol: row.get("symbol"),
-                name: row.get("name"),
-                chain: row.get("chain"),
-                source: row.get("source"),
-                created_at: row.get::<String, _>("created_at").parse()?,
-                first_seen: row.get::<String, _>("first_seen").parse()?,
-                is_active: row.get("is_active"),
-            }))
-        } else {
-            Ok(None)
-        }
+        with_pool!(self, pool => {
+            let row = sqlx::query(r#"
+                SELECT * FROM tokens WHERE address = $1
+            "#)
+            .bind(address)
+            .fetch_optional(pool)
+            .await?;
+
+            row.map(|row| token_from_row(&row)).transpose()
+        })
+    }
+
+    /// Like `get_token`, but EVM (0x) addresses match regardless of case:
+    /// explorers report them lowercase while DEX Screener checksums them
+    pub async fn find_token(&self, address: &str) -> Result<Option<Token>> {
+        with_pool!(self, pool => {
+            if let Some(token) = self.get_token(address).await? {
+                return Ok(Some(token));
+            }
+            if !address.starts_with("0x") {
+                return Ok(None);
+            }
+
+            let stored = sqlx::query_scalar::<_, String>(r#"
+                SELECT address FROM tokens WHERE LOWER(address) = LOWER($1) LIMIT 1
+            "#)
+            .bind(address)
+            .fetch_optional(pool)
+            .await?;
+
+            match stored {
+                Some(stored) => self.get_token(&stored).await,
+                None => Ok(None),
+            }
+        })
+    }
+
+    /// How many tokens were first seen at or after `since`
+    pub async fn count_tokens_since(&self, since: DateTime<Utc>) -> Result<i64> {
+        with_pool!(self, pool => {
+            let count = sqlx::query_scalar::<_, i64>(r#"
+                SELECT COUNT(*) FROM tokens WHERE first_seen >= $1
+            "#)
+            .bind(since.to_rfc3339())
+            .fetch_one(pool)
+            .await?;
+
+            Ok(count)
+        })
+    }
+
+    /// How many tracked tokens use this symbol (case-insensitive), across all addresses
+    pub async fn count_tokens_with_symbol(&self, symbol: &str) -> Result<i64> {
+        with_pool!(self, pool => {
+            let count = sqlx::query_scalar::<_, i64>(r#"
+                SELECT COUNT(*) FROM tokens WHERE LOWER(symbol) = LOWER($1)
+            "#)
+            .bind(symbol)
+            .fetch_one(pool)
+            .await?;
+
+            Ok(count)
+        })
+    }
+
+    /// Stop (or resume) signals for a token while still tracking it; false if the token is unknown
+    pub async fn set_ignored(&self, address: &str, ignored: bool) -> Result<bool> {
+        with_pool!(self, pool => {
+            let result = sqlx::query("UPDATE tokens SET ignored = $1 WHERE address = $2")
+                .bind(ignored)
+                .bind(address)
+                .execute(pool)
+                .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    pub async fn set_token_creation(&self, address: &str, deployed_at: DateTime<Utc>, deployer: Option<&str>) -> Result<()> {
+        with_pool!(self, pool => {
+            sqlx::query("UPDATE tokens SET deployed_at = $1, deployer = $2 WHERE address = $3")
+                .bind(deployed_at.to_rfc3339())
+                .bind(deployer)
+                .bind(address)
+                .execute(pool)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    /// Remember that the explorer was asked for a token's creation and had no answer
+    pub async fn mark_creation_unknown(&self, address: &str) -> Result<()> {
+        with_pool!(self, pool => {
+            sqlx::query("UPDATE tokens SET creation_checked_at = $1 WHERE address = $2")
+                .bind(Utc::now().to_rfc3339())
+                .bind(address)
+                .execute(pool)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    pub async fn is_ignored(&self, address: &str) -> Result<bool> {
+        with_pool!(self, pool => {
+            let ignored = sqlx::query_scalar::<_, bool>("SELECT ignored FROM tokens WHERE address = $1")
+                .bind(address)
+                .fetch_optional(pool)
+                .await?;
+
+            Ok(ignored.unwrap_or(false))
+        })
     }
 
     pub async fn get_recent_tokens(&self, limit: i64) -> Result<Vec<Token>> {
-        let rows = sqlx::query(r#"
-            SELECT * FROM tokens 
-            WHERE is_active = TRUE 
-            ORDER BY first_seen DESC 
-            LIMIT ?
-        "#)
-        .bind(limit)
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut tokens = Vec::new();
-        for row in rows {
-            tokens.push(Token {
-                id: Some(row.get("id")),
-                address: row.get("address"),
-                symbol: row.get("symbol"),
-                name: row.get("name"),
-                chain: row.get("chain"),
-                source: row.get("source"),
-                created_at: row.get::<String, _>("created_at").parse()?,
-                first_seen: row.get::<String, _>("first_seen").parse()?,
-                is_active: row.get("is_active"),
-            });
-        }
+        with_pool!(self, pool => {
+            let rows = sqlx::query(r#"
+                SELECT * FROM tokens 
+                WHERE is_active = TRUE 
+                ORDER BY first_seen DESC 
+                LIMIT $1
+            "#)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
 
-        Ok(tokens)
+            rows.iter().map(token_from_row).collect()
+        })
     }
 
     // TOKEN METRICS OPERATIONS
     pub async fn save_token_metrics(&self, metrics: &TokenMetrics) -> Result<i64> {
-        let result = sqlx::query(r#"
-            INSERT INTO token_metrics 
-            (token_address, timestamp, price_usd, market_cap_usd, liquidity_usd, 
-             volume_24h_usd, total_supply, circulating_supply, holder_count, 
-             top_10_holders_percentage, is_honeypot, is_mintable, has_proxy, contract_verified)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-        "#)
-        .bind(&metrics.token_address)
-        .bind(metrics.timestamp.to_rfc3339())
-        .bind(metrics.price_usd.map(|d| d.to_string()))
-        .bind(metrics.market_cap_usd.map(|d| d.to_string()))
-        .bind(metrics.liquidity_usd.map(|d| d.to_string()))
-        .bind(metrics.volume_24h_usd.map(|d| d.to_string()))
-        .bind(metrics.total_supply.map(|d| d.to_string()))
-        .bind(metrics.circulating_supply.map(|d| d.to_string()))
-        .bind(metrics.holder_count)
-        .bind(metrics.top_10_holders_percentage.map(|d| d.to_string()))
-        .bind(metrics.is_honeypot)
-        .bind(metrics.is_mintable)
-        .bind(metrics.has_proxy)
-        .bind(metrics.contract_verified)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_rowid())
+        with_pool!(self, pool => {
+            let rows = insert_metrics_query(metrics)
+                .fetch_all(pool)
+                .await?;
+
+            Ok(returned(rows)?.get("id"))
+        })
+    }
+
+    /// Write the security verdicts analysis filled in back onto the stored snapshot they were
+    /// checked for, rather than saving it again as a new one
+    pub async fn update_security_verdicts(&self, metrics: &TokenMetrics) -> Result<()> {
+        let id = metrics.id.ok_or_else(|| anyhow::anyhow!("Metrics for {} were never saved", metrics.token_address))?;
+
+        with_pool!(self, pool => {
+            sqlx::query(r#"
+                UPDATE token_metrics
+                SET is_honeypot = $1, buy_tax_pct = $2, sell_tax_pct = $3, is_mintable = $4, owner_privileged = $5
+                WHERE id = $6
+            "#)
+            .bind(metrics.is_honeypot)
+            .bind(metrics.buy_tax_pct.map(|d| d.to_string()))
+            .bind(metrics.sell_tax_pct.map(|d| d.to_string()))
+            .bind(metrics.is_mintable)
+            .bind(metrics.owner_privileged)
+            .bind(id)
+            .execute(pool)
+            .await?;
+
+            Ok(())
+        })
     }
 
     pub async fn get_latest_metrics(&self, token_address: &str) -> Result<Option<TokenMetrics>> {
-        let row = sqlx::query(r#"
-            SELECT * FROM token_metrics 
-            WHERE token_address = ? 
-            ORDER BY timestamp DESC 
-            LIMIT 1
-        "#)
-        .bind(token_address)
-        .fetch_optional(&self.pool)
-        .await?;
-
-        if let Some(row) = row {
-            Ok(Some(TokenMetrics {
-                id: Some(row.get("id")),
-                token_address: row.get("token_address"),
-                timestamp: row.get::<String, _>("timestamp").parse()?,
-                price_usd: row.get::<Option<String>, _>("price_usd").map(|s| s.parse()).transpose()?,
-                market_cap_usd: row.get::<Option<String>, _>("market_cap_usd").map(|s| s.parse()).transpose()?,
-                liquidity_usd: row.get::<Option<String>, _>("liquidity_usd").map(|s| s.parse()).transpose()?,
-                volume_24h_usd: row.get::<Option<String>, _>("volume_24h_usd").map(|s| s.parse()).transpose()?,
-                total_supply: row.get::<Option<String>, _>("total_supply").map(|s| s.parse()).transpose()?,
-                circulating_supply: row.get::<Option<String>, _>("circulating_supply").map(|s| s.parse()).transpose()?,
-                holder_count: row.get("holder_count"),
-                top_10_holders_percentage: row.get::<Option<String>, _>("top_10_holders_percentage").map(|s| s.parse()).transpose()?,
-                is_honeypot: row.get("is_honeypot"),
-                is_mintable: row.get("is_mintable"),
-                has_proxy: row.get("has_proxy"),
-                contract_verified: row.get("contract_verified"),
-            }))
-        } else {
-            Ok(None)
-        }
+        with_pool!(self, pool => {
+            let row = sqlx::query(LATEST_METRICS_QUERY)
+            .bind(token_address)
+            .fetch_optional(pool)
+            .await?;
+
+            row.map(|row| metrics_from_row(&row)).transpose()
+        })
+    }
+
+    /// All metric snapshots for a token since `since`, oldest first
+    pub async fn get_metrics_history(&self, token_address: &str, since: DateTime<Utc>) -> Result<Vec<TokenMetrics>> {
+        with_pool!(self, pool => {
+            let rows = sqlx::query(r#"
+                SELECT * FROM token_metrics 
+                WHERE token_address = $1 AND timestamp >= $2 
+                ORDER BY timestamp ASC
+            "#)
+            .bind(token_address)
+            .bind(since.to_rfc3339())
+            .fetch_all(pool)
+            .await?;
+
+            rows.iter().map(metrics_from_row).collect()
+        })
+    }
+
+    /// Delete snapshots taken before `older_than`, keeping each token's latest one
+    /// so `get_latest_metrics` still has an answer. Returns how many rows were deleted.
+    pub async fn prune_metrics(&self, older_than: DateTime<Utc>) -> Result<u64> {
+        with_pool!(self, pool => {
+            let deleted = sqlx::query(r#"
+                DELETE FROM token_metrics
+                WHERE timestamp < $1
+                  AND EXISTS (
+                      SELECT 1 FROM token_metrics newer
+                      WHERE newer.token_address = token_metrics.token_address
+                        AND (newer.timestamp > token_metrics.timestamp
+                             OR (newer.timestamp = token_metrics.timestamp AND newer.id > token_metrics.id))
+                  )
+            "#)
+            .bind(older_than.to_rfc3339())
+            .execute(pool)
+            .await?
+            .rows_affected();
+
+            Ok(deleted)
+        })
+    }
+
+    /// Every token's snapshots in `[from, to)`, oldest first, for replaying history
+    pub async fn get_metrics_between(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<Vec<TokenMetrics>> {
+        with_pool!(self, pool => {
+            let rows = sqlx::query(r#"
+                SELECT * FROM token_metrics
+                WHERE timestamp >= $1 AND timestamp < $2
+                ORDER BY timestamp ASC, id ASC
+            "#)
+            .bind(from.to_rfc3339())
+            .bind(to.to_rfc3339())
+            .fetch_all(pool)
+            .await?;
+
+            rows.iter().map(metrics_from_row).collect()
+        })
+    }
+
+    // ANALYSIS RESULT OPERATIONS
+    pub async fn save_analysis_result(&self, result: &AnalysisResult) -> Result<i64> {
+        with_pool!(self, pool => {
+            let ids = sqlx::query_scalar::<_, i64>(r#"
+                INSERT INTO analysis_results
+                (token_address, score, is_safe, risk_level, recommendation, potential_multiplier, flags, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING id
+            "#)
+            .bind(&result.token_address)
+            .bind(result.score.to_string())
+            .bind(result.is_safe)
+            .bind(format!("{:?}", result.risk_level).to_lowercase())
+            .bind(format!("{:?}", result.recommendation).to_lowercase())
+            .bind(result.potential_multiplier.map(|d| d.to_string()))
+            .bind(serde_json::to_string(&result.flags)?)
+            .bind(Utc::now().to_rfc3339())
+            .fetch_all(pool)
+            .await?;
+
+            returned(ids)
+        })
+    }
+
+    /// A token's most recent analyses with when they ran, newest first
+    pub async fn get_analysis_history(&self, token_address: &str, limit: i64) -> Result<Vec<(DateTime<Utc>, AnalysisResult)>> {
+        with_pool!(self, pool => {
+            let rows = sqlx::query(r#"
+                SELECT * FROM analysis_results
+                WHERE token_address = $1
+                ORDER BY created_at DESC, id DESC
+                LIMIT $2
+            "#)
+            .bind(token_address)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+
+            let mut history = Vec::new();
+            for row in rows {
+                history.push((row.get::<String, _>("created_at").parse()?, AnalysisResult {
+                    token_address: row.get("token_address"),
+                    score: row.get::<String, _>("score").parse()?,
+                    is_safe: row.get("is_safe"),
+                    risk_level: match row.get::<String, _>("risk_level").as_str() {
+                        "low" => RiskLevel::Low,
+                        "medium" => RiskLevel::Medium,
+                        "high" => RiskLevel::High,
+                        _ => RiskLevel::Extreme,
+                    },
+                    recommendation: match row.get::<String, _>("recommendation").as_str() {
+                        "buy" => Recommendation::Buy,
+                        "watch" => Recommendation::Watch,
+                        _ => Recommendation::Avoid,
+                    },
+                    potential_multiplier: row.get::<Option<String>, _>("potential_multiplier").map(|s| s.parse()).transpose()?,
+                    flags: serde_json::from_str(&row.get::<String, _>("flags"))?,
+                }));
+            }
+
+            Ok(history)
+        })
+    }
+
+    // SOCIAL MENTION OPERATIONS
+    pub async fn save_social_mentions(&self, token_address: &str, mention_count: i64, window_secs: i64) -> Result<i64> {
+        with_pool!(self, pool => {
+            let ids = sqlx::query_scalar::<_, i64>(r#"
+                INSERT INTO social_mentions (token_address, timestamp, mention_count, window_secs)
+                VALUES ($1, $2, $3, $4)
+                RETURNING id
+            "#)
+            .bind(token_address)
+            .bind(Utc::now().to_rfc3339())
+            .bind(mention_count)
+            .bind(window_secs)
+            .fetch_all(pool)
+            .await?;
+
+            returned(ids)
+        })
+    }
+
+    /// A token's latest mention counts, newest first
+    pub async fn get_recent_social_mentions(&self, token_address: &str, limit: i64) -> Result<Vec<i64>> {
+        with_pool!(self, pool => {
+            let counts = sqlx::query_scalar::<_, i64>(r#"
+                SELECT mention_count FROM social_mentions
+                WHERE token_address = $1
+                ORDER BY timestamp DESC, id DESC
+                LIMIT $2
+            "#)
+            .bind(token_address)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+
+            Ok(counts)
+        })
+    }
+
+    // WATCHLIST OPERATIONS
+    /// Add a tracked token to the watchlist; false if it was already on it
+    pub async fn add_to_watchlist(&self, token_address: &str, note: Option<&str>) -> Result<bool> {
+        with_pool!(self, pool => {
+            let result = sqlx::query(r#"
+                INSERT INTO watchlist (token_address, added_at, note)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (token_address) DO NOTHING
+            "#)
+            .bind(token_address)
+            .bind(Utc::now().to_rfc3339())
+            .bind(note)
+            .execute(pool)
+            .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    /// Remove a token from the watchlist; false if it wasn't on it
+    pub async fn remove_from_watchlist(&self, token_address: &str) -> Result<bool> {
+        with_pool!(self, pool => {
+            let result = sqlx::query("DELETE FROM watchlist WHERE token_address = $1")
+                .bind(token_address)
+                .execute(pool)
+                .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    /// Every watched token, oldest first
+    pub async fn get_watchlist(&self) -> Result<Vec<WatchlistEntry>> {
+        with_pool!(self, pool => {
+            let rows = sqlx::query("SELECT * FROM watchlist ORDER BY added_at ASC")
+                .fetch_all(pool)
+                .await?;
+
+            let mut entries = Vec::new();
+            for row in rows {
+                entries.push(WatchlistEntry {
+                    token_address: row.get("token_address"),
+                    added_at: row.get::<String, _>("added_at").parse()?,
+                    note: row.get("note"),
+                });
+            }
+
+            Ok(entries)
+        })
+    }
+
+    // BLACKLIST OPERATIONS
+    /// Blacklist an address; false if it already was
+    pub async fn add_blacklist(&self, address: &str, reason: Option<&str>) -> Result<bool> {
+        with_pool!(self, pool => {
+            let result = sqlx::query(r#"
+                INSERT INTO blacklist (address, reason, added_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (address) DO NOTHING
+            "#)
+            .bind(blacklist_key(address))
+            .bind(reason)
+            .bind(Utc::now().to_rfc3339())
+            .execute(pool)
+            .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    /// Take an address off the blacklist; false if it wasn't on it
+    pub async fn remove_blacklist(&self, address: &str) -> Result<bool> {
+        with_pool!(self, pool => {
+            let result = sqlx::query("DELETE FROM blacklist WHERE address = $1")
+                .bind(blacklist_key(address))
+                .execute(pool)
+                .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
+
+    pub async fn is_blacklisted(&self, address: &str) -> Result<bool> {
+        with_pool!(self, pool => {
+            let found = sqlx::query_scalar::<_, String>("SELECT address FROM blacklist WHERE address = $1")
+                .bind(blacklist_key(address))
+                .fetch_optional(pool)
+                .await?;
+
+            Ok(found.is_some())
+        })
+    }
+
+    // SCANNER STATE OPERATIONS
+    /// Creation time (unix ms) of the newest pair a source has already processed
+    pub async fn get_scanner_cursor(&self, source: &str) -> Result<Option<i64>> {
+        with_pool!(self, pool => {
+            let cursor = sqlx::query_scalar::<_, i64>(r#"
+                SELECT last_pair_created_at FROM scanner_state WHERE source = $1
+            "#)
+            .bind(source)
+            .fetch_optional(pool)
+            .await?;
+
+            Ok(cursor)
+        })
+    }
+
+    /// Move a source's cursor forward (never backwards)
+    pub async fn advance_scanner_cursor(&self, source: &str, last_pair_created_at: i64) -> Result<()> {
+        with_pool!(self, pool => {
+            sqlx::query(r#"
+                INSERT INTO scanner_state (source, last_pair_created_at, updated_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (source) DO UPDATE SET
+                    last_pair_created_at = CASE WHEN excluded.last_pair_created_at > scanner_state.last_pair_created_at
+                        THEN excluded.last_pair_created_at ELSE scanner_state.last_pair_created_at END,
+                    updated_at = excluded.updated_at
+            "#)
+            .bind(source)
+            .bind(last_pair_created_at)
+            .bind(Utc::now().to_rfc3339())
+            .execute(pool)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    // REJECTION COUNT OPERATIONS
+    /// Add a scan cycle's rejections to the stored per-reason totals
+    pub async fn add_rejection_counts(&self, counts: &[(&str, u64)]) -> Result<()> {
+        with_pool!(self, pool => {
+            let mut tx = pool.begin().await?;
+            for (reason, count) in counts {
+                sqlx::query(r#"
+                    INSERT INTO rejection_counts (reason, count)
+                    VALUES ($1, $2)
+                    ON CONFLICT (reason) DO UPDATE SET count = rejection_counts.count + excluded.count
+                "#)
+                .bind(*reason)
+                .bind(*count as i64)
+                .execute(&mut *tx)
+                .await?;
+            }
+            tx.commit().await?;
+
+            Ok(())
+        })
+    }
+
+    /// Stored rejection totals per reason
+    pub async fn get_rejection_counts(&self) -> Result<Vec<(String, u64)>> {
+        with_pool!(self, pool => {
+            let rows = sqlx::query("SELECT reason, count FROM rejection_counts")
+                .fetch_all(pool)
+                .await?;
+
+            Ok(rows.iter()
+                .map(|row| (row.get::<String, _>("reason"), row.get::<i64, _>("count") as u64))
+                .collect())
+        })
+    }
+
+    // ANALYSIS RETRY OPERATIONS
+    /// Queue a token for re-analysis, returning how many attempts have been scheduled so far
+    pub async fn schedule_analysis_retry(&self, token_address: &str, reason: &str, next_attempt_at: DateTime<Utc>) -> Result<u32> {
+        with_pool!(self, pool => {
+            let attempts = sqlx::query_scalar::<_, i64>(r#"
+                INSERT INTO analysis_retries (token_address, attempts, next_attempt_at, last_reason)
+                VALUES ($1, 1, $2, $3)
+                ON CONFLICT (token_address) DO UPDATE SET
+                    attempts = analysis_retries.attempts + 1,
+                    next_attempt_at = excluded.next_attempt_at,
+                    last_reason = excluded.last_reason
+                RETURNING attempts
+            "#)
+            .bind(token_address)
+            .bind(next_attempt_at.to_rfc3339())
+            .bind(reason)
+            .fetch_all(pool)
+            .await?;
+
+            Ok(returned(attempts)? as u32)
+        })
+    }
+
+    /// How many retries have been scheduled for a token (0 if none)
+    pub async fn get_analysis_retry_attempts(&self, token_address: &str) -> Result<u32> {
+        with_pool!(self, pool => {
+            let attempts = sqlx::query_scalar::<_, i64>(r#"
+                SELECT attempts FROM analysis_retries WHERE token_address = $1
+            "#)
+            .bind(token_address)
+            .fetch_optional(pool)
+            .await?;
+
+            Ok(attempts.unwrap_or(0) as u32)
+        })
+    }
+
+    /// Tokens whose retry is due
+    pub async fn get_due_analysis_retries(&self, now: DateTime<Utc>) -> Result<Vec<String>> {
+        with_pool!(self, pool => {
+            let addresses = sqlx::query_scalar::<_, String>(r#"
+                SELECT token_address FROM analysis_retries 
+                WHERE next_attempt_at <= $1 
+                ORDER BY next_attempt_at ASC
+            "#)
+            .bind(now.to_rfc3339())
+            .fetch_all(pool)
+            .await?;
+
+            Ok(addresses)
+        })
+    }
+
+    pub async fn clear_analysis_retry(&self, token_address: &str) -> Result<()> {
+        with_pool!(self, pool => {
+            sqlx::query("DELETE FROM analysis_retries WHERE token_address = $1")
+                .bind(token_address)
+                .execute(pool)
+                .await?;
+
+            Ok(())
+        })
     }
 
     // TRADING SIGNALS OPERATIONS
     pub async fn save_trading_signal(&self, signal: &TradingSignal) -> Result<i64> {
-        let result = sqlx::query(r#"
-            INSERT INTO trading_signals 
-            (token_address, signal_type, confidence, reason, target_multiplier, created_at, is_sent)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
-        "#)
-        .bind(&signal.token_address)
-        .bind(format!("{:?}", signal.signal_type).to_lowercase())
-        .bind(signal.confidence.to_string())
-        .bind(&signal.reason)
-        .bind(signal.target_multiplier.map(|d| d.to_string()))
-        .bind(signal.created_at.to_rfc3339())
-        .bind(signal.is_sent)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_rowid())
+        with_pool!(self, pool => {
+            let ids = sqlx::query_scalar::<_, i64>(r#"
+                INSERT INTO trading_signals 
+                (token_address, signal_type, confidence, reason, target_multiplier, created_at, is_sent)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                RETURNING id
+            "#)
+            .bind(&signal.token_address)
+            .bind(format!("{:?}", signal.signal_type).to_lowercase())
+            .bind(signal.confidence.to_string())
+            .bind(&signal.reason)
+            .bind(signal.target_multiplier.map(|d| d.to_string()))
+            .bind(signal.created_at.to_rfc3339())
+            .bind(signal.is_sent)
+            .fetch_all(pool)
+            .await?;
+
+            returned(ids)
+        })
     }
 
     pub async fn get_unsent_signals(&self) -> Result<Vec<TradingSignal>> {
-        let rows = sqlx::query(r#"
-            SELECT * FROM trading_signals 
-            WHERE is_sent = FALSE 
-            ORDER BY created_at ASC
-        "#)
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut signals = Vec::new();
-        for row in rows {
-            signals.push(TradingSignal {
-                id: Some(row.get("id")),
-                token_address: row.get("token_address"),
-                signal_type: match row.get::<String, _>("signal_type").as_str() {
-                    "buy" => crate::models::SignalType::Buy,
-                    "sell" => crate::models::SignalType::Sell,
-                    "warning" => crate::models::SignalType::Warning,
-                    "whalemovement" => crate::models::SignalType::WhaleMovement,
-                    _ => crate::models::SignalType::Buy,
-                },
-                confidence: row.get::<String, _>("confidence").parse()?,
-                reason: row.get("reason"),
-                target_multiplier: row.get::<Option<String>, _>("target_multiplier").map(|s| s.parse()).transpose()?,
-                created_at: row.get::<String, _>("created_at").parse()?,
-                is_sent: row.get("is_sent"),
-            });
-        }
+        with_pool!(self, pool => {
+            let rows = sqlx::query(UNSENT_SIGNALS_QUERY)
+            .fetch_all(pool)
+            .await?;
+
+            rows.iter().map(signal_from_row).collect()
+        })
+    }
 
-        Ok(signals)
+    /// The most recent signal of any type for a token, sent or not
+    pub async fn get_last_signal_for_token(&self, token_address: &str) -> Result<Option<TradingSignal>> {
+        with_pool!(self, pool => {
+            let row = sqlx::query(r#"
+                SELECT * FROM trading_signals
+                WHERE token_address = $1
+                ORDER BY created_at DESC, id DESC
+                LIMIT 1
+            "#)
+            .bind(token_address)
+            .fetch_optional(pool)
+            .await?;
+
+            row.map(|row| signal_from_row(&row)).transpose()
+        })
     }
 
     pub async fn mark_signal_sent(&self, signal_id: i64) -> Result<()> {
-        sqlx::query(r#"
-            UPDATE trading_signals 
-            SET is_sent = TRUE 
-            WHERE id = ?
-        "#)
-        .bind(signal_id)
-        .execute(&self.pool)
-        .await?;
+        with_pool!(self, pool => {
+            sqlx::query(r#"
+                UPDATE trading_signals 
+                SET is_sent = TRUE 
+                WHERE id = $1
+            "#)
+            .bind(signal_id)
+            .execute(pool)
+            .await?;
 
-        Ok(())
+            Ok(())
+        })
+    }
+
+    /// Record one delivery attempt of a signal on a channel.
+    /// `status` is "delivered", "pending" (retried from `next_attempt_at`) or "abandoned".
+    pub async fn record_signal_delivery(&self, signal_id: i64, channel: &str, status: &str, error: Option<&str>,
+                                        next_attempt_at: Option<DateTime<Utc>>) -> Result<()> {
+        with_pool!(self, pool => {
+            sqlx::query(r#"
+                INSERT INTO signal_deliveries (signal_id, channel, status, attempts, last_error, updated_at, next_attempt_at)
+                VALUES ($1, $2, $3, 1, $4, $5, $6)
+                ON CONFLICT (signal_id, channel) DO UPDATE SET
+                    status = excluded.status,
+                    attempts = signal_deliveries.attempts + 1,
+                    last_error = excluded.last_error,
+                    updated_at = excluded.updated_at,
+                    next_attempt_at = excluded.next_attempt_at
+            "#)
+            .bind(signal_id)
+            .bind(channel)
+            .bind(status)
+            .bind(error)
+            .bind(Utc::now().to_rfc3339())
+            .bind(next_attempt_at.map(|t| t.to_rfc3339()))
+            .execute(pool)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    /// Delivery state of a signal on every channel that has been tried
+    pub async fn get_signal_deliveries(&self, signal_id: i64) -> Result<Vec<SignalDelivery>> {
+        with_pool!(self, pool => {
+            let rows = sqlx::query(r#"
+                SELECT channel, status, attempts, next_attempt_at FROM signal_deliveries
+                WHERE signal_id = $1
+            "#)
+            .bind(signal_id)
+            .fetch_all(pool)
+            .await?;
+
+            let mut deliveries = Vec::new();
+            for row in rows {
+                deliveries.push(SignalDelivery {
+                    channel: row.get("channel"),
+                    status: row.get("status"),
+                    attempts: row.get::<i64, _>("attempts") as u32,
+                    next_attempt_at: row.get::<Option<String>, _>("next_attempt_at").map(|s| s.parse()).transpose()?,
+                });
+            }
+
+            Ok(deliveries)
+        })
     }
 
     // SIMULATED TRADES OPERATIONS
     pub async fn save_simulated_trade(&self, trade: &SimulatedTrade) -> Result<i64> {
-        let result = sqlx::query(r#"
-            INSERT INTO simulated_trades 
-            (token_address, entry_price, entry_time, exit_price, exit_time, 
-             investment_usd, profit_loss, multiplier, exit_reason, is_active)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-        "#)
-        .bind(&trade.token_address)
-        .bind(trade.entry_price.to_string())
-        .bind(trade.entry_time.to_rfc3339())
-        .bind(trade.exit_price.map(|d| d.to_string()))
-        .bind(trade.exit_time.map(|dt| dt.to_rfc3339()))
-        .bind(trade.investment_usd.to_string())
-        .bind(trade.profit_loss.map(|d| d.to_string()))
-        .bind(trade.multiplier.map(|d| d.to_string()))
-        .bind(&trade.exit_reason)
-        .bind(trade.is_active)
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_rowid())
+        with_pool!(self, pool => {
+            let ids = sqlx::query_scalar::<_, i64>(r#"
+                INSERT INTO simulated_trades 
+                (token_address, entry_price, entry_time, exit_price, exit_time, 
+                 investment_usd, profit_loss, multiplier, exit_reason, is_active, breakeven_armed,
+                 remaining_fraction, realized_profit_loss, targets_hit, target_multiplier, source, max_hold_hours)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17)
+                RETURNING id
+            "#)
+            .bind(&trade.token_address)
+            .bind(trade.entry_price.to_string())
+            .bind(trade.entry_time.to_rfc3339())
+            .bind(trade.exit_price.map(|d| d.to_string()))
+            .bind(trade.exit_time.map(|dt| dt.to_rfc3339()))
+            .bind(trade.investment_usd.to_string())
+            .bind(trade.profit_loss.map(|d| d.to_string()))
+            .bind(trade.multiplier.map(|d| d.to_string()))
+            .bind(&trade.exit_reason)
+            .bind(trade.is_active)
+            .bind(trade.breakeven_armed)
+            .bind(trade.remaining_fraction.to_string())
+            .bind(trade.realized_profit_loss.to_string())
+            .bind(i64::from(trade.targets_hit))
+            .bind(trade.target_multiplier.map(|d| d.to_string()))
+            .bind(&trade.source)
+            .bind(trade.max_hold_hours)
+            .fetch_all(pool)
+            .await?;
+
+            returned(ids)
+        })
     }
 
     pub async fn get_active_trades(&self) -> Result<Vec<SimulatedTrade>> {
-        let rows = sqlx::query(r#"
-            SELECT * FROM simulated_trades 
-            WHERE is_active = TRUE 
-            ORDER BY entry_time DESC
-        "#)
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut trades = Vec::new();
-        for row in rows {
-            trades.push(SimulatedTrade {
-                id: Some(row.get("id")),
-                token_address: row.get("token_address"),
-                entry_price: row.get::<String, _>("entry_price").parse()?,
-                entry_time: row.get::<String, _>("entry_time").parse()?,
-                exit_price: row.get::<Option<String>, _>("exit_price").map(|s| s.parse()).transpose()?,
-                exit_time: row.get::<Option<String>, _>("exit_time").map(|s| s.parse()).transpose()?,
-                investment_usd: row.get::<String, _>("investment_usd").parse()?,
-                profit_loss: row.get::<Option<String>, _>("profit_loss").map(|s| s.parse()).transpose()?,
-                multiplier: row.get::<Option<String>, _>("multiplier").map(|s| s.parse()).transpose()?,
-                exit_reason: row.get("exit_reason"),
-                is_active: row.get("is_active"),
-            });
-        }
+        with_pool!(self, pool => {
+            let rows = sqlx::query(r#"
+                SELECT * FROM simulated_trades 
+                WHERE is_active = TRUE 
+                ORDER BY entry_time DESC
+            "#)
+            .fetch_all(pool)
+            .await?;
 
-        Ok(trades)
+            rows.iter().map(trade_from_row).collect()
+        })
+    }
+
+    /// Raise a trade's stop to its entry price
+    pub async fn arm_breakeven(&self, trade_id: i64) -> Result<()> {
+        with_pool!(self, pool => {
+            sqlx::query("UPDATE simulated_trades SET breakeven_armed = TRUE WHERE id = $1")
+                .bind(trade_id)
+                .execute(pool)
+                .await?;
+
+            Ok(())
+        })
+    }
+
+    /// Add a USD price level at which the trade should take profit
+    pub async fn add_trade_target(&self, trade_id: i64, target_price: rust_decimal::Decimal) -> Result<()> {
+        with_pool!(self, pool => {
+            sqlx::query(r#"
+                INSERT INTO trade_targets (trade_id, target_price, created_at)
+                VALUES ($1, $2, $3)
+            "#)
+            .bind(trade_id)
+            .bind(target_price.to_string())
+            .bind(Utc::now().to_rfc3339())
+            .execute(pool)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    /// Price targets set on a trade, lowest first
+    pub async fn get_trade_targets(&self, trade_id: i64) -> Result<Vec<rust_decimal::Decimal>> {
+        with_pool!(self, pool => {
+            let prices = sqlx::query_scalar::<_, String>(r#"
+                SELECT target_price FROM trade_targets
+                WHERE trade_id = $1
+                ORDER BY CAST(target_price AS DOUBLE PRECISION) ASC
+            "#)
+            .bind(trade_id)
+            .fetch_all(pool)
+            .await?;
+
+            Ok(prices.iter().map(|p| p.parse()).collect::<Result<_, _>>()?)
+        })
+    }
+
+    /// Closed trades ranked by `order`, paired with the token symbol
+    pub async fn get_top_trades(&self, limit: i64, order: TradeOrder) -> Result<Vec<(String, SimulatedTrade)>> {
+        let (column, direction) = match order {
+            TradeOrder::BestMultiplier => ("multiplier", "DESC"),
+            TradeOrder::WorstProfitLoss => ("profit_loss", "ASC"),
+        };
+
+        with_pool!(self, pool => {
+            let rows = sqlx::query(&format!(r#"
+                SELECT t.*, COALESCE(k.symbol, t.token_address) AS symbol
+                FROM all_trades t
+                LEFT JOIN tokens k ON k.address = t.token_address
+                WHERE t.is_active = FALSE AND t.{column} IS NOT NULL
+                ORDER BY CAST(t.{column} AS DOUBLE PRECISION) {direction}
+                LIMIT $1
+            "#))
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+
+            rows.iter()
+                .map(|row| Ok((row.get("symbol"), trade_from_row(row)?)))
+                .collect()
+        })
+    }
+
+    /// Every closed trade, live or archived, oldest exit first
+    pub async fn get_all_closed_trades(&self) -> Result<Vec<SimulatedTrade>> {
+        with_pool!(self, pool => {
+            let rows = sqlx::query(r#"
+                SELECT * FROM all_trades
+                WHERE is_active = FALSE
+                ORDER BY exit_time ASC, id ASC
+            "#)
+            .fetch_all(pool)
+            .await?;
+
+            rows.iter().map(trade_from_row).collect()
+        })
+    }
+
+    /// Realized P&L of every closed trade (live or archived) per token symbol, most profitable first
+    pub async fn get_closed_trades_grouped(&self) -> Result<Vec<(String, TokenPnl)>> {
+        with_pool!(self, pool => {
+            let rows = sqlx::query(r#"
+                SELECT COALESCE(k.symbol, t.token_address) AS symbol,
+                       SUM(CAST(t.investment_usd AS DOUBLE PRECISION)) AS invested,
+                       SUM(CAST(COALESCE(t.profit_loss, '0') AS DOUBLE PRECISION)) AS realized,
+                       SUM(CASE WHEN CAST(t.profit_loss AS DOUBLE PRECISION) > 0 THEN 1 ELSE 0 END) AS wins,
+                       SUM(CASE WHEN CAST(t.profit_loss AS DOUBLE PRECISION) > 0 THEN 0 ELSE 1 END) AS losses
+                FROM all_trades t
+                LEFT JOIN tokens k ON k.address = t.token_address
+                WHERE t.is_active = FALSE
+                GROUP BY COALESCE(k.symbol, t.token_address)
+                ORDER BY realized DESC
+            "#)
+            .fetch_all(pool)
+            .await?;
+
+            Ok(rows.iter()
+                .map(|row| (row.get("symbol"), TokenPnl {
+                    invested_usd: row.get("invested"),
+                    realized_profit_usd: row.get("realized"),
+                    wins: row.get("wins"),
+                    losses: row.get("losses"),
+                }))
+                .collect())
+        })
     }
 
     pub async fn close_trade(&self, trade_id: i64, exit_price: rust_decimal::Decimal, 
                             profit_loss: rust_decimal::Decimal, multiplier: rust_decimal::Decimal, 
                             exit_reason: &str) -> Result<()> {
-        let now = Utc::now();
+        with_pool!(self, pool => {
+            let now = Utc::now();
         
-        sqlx::query(r#"
-            UPDATE simulated_trades 
-            SET exit_price = ?, exit_time = ?, profit_loss = ?, 
-                multiplier = ?, exit_reason = ?, is_active = FALSE
-            WHERE id = ?
-        "#)
-        .bind(exit_price.to_string())
-        .bind(now.to_rfc3339())
-        .bind(profit_loss.to_string())
-        .bind(multiplier.to_string())
-        .bind(exit_reason)
-        .bind(trade_id)
-        .execute(&self.pool)
-        .await?;
+            sqlx::query(r#"
+                UPDATE simulated_trades 
+                SET exit_price = $1, exit_time = $2, profit_loss = $3, 
+                    multiplier = $4, exit_reason = $5, is_active = FALSE
+                WHERE id = $6
+            "#)
+            .bind(exit_price.to_string())
+            .bind(now.to_rfc3339())
+            .bind(profit_loss.to_string())
+            .bind(multiplier.to_string())
+            .bind(exit_reason)
+            .bind(trade_id)
+            .execute(pool)
+            .await?;
 
-        Ok(())
+            Ok(())
+        })
+    }
+
+    /// Sell part of a position: book its P&L and shrink what's left, keeping the trade open
+    pub async fn partial_close_trade(&self, trade_id: i64, remaining_fraction: rust_decimal::Decimal,
+                                     realized_profit_loss: rust_decimal::Decimal, targets_hit: u32) -> Result<()> {
+        with_pool!(self, pool => {
+            sqlx::query(r#"
+                UPDATE simulated_trades 
+                SET remaining_fraction = $1, realized_profit_loss = $2, targets_hit = $3
+                WHERE id = $4 AND is_active = TRUE
+            "#)
+            .bind(remaining_fraction.to_string())
+            .bind(realized_profit_loss.to_string())
+            .bind(i64::from(targets_hit))
+            .bind(trade_id)
+            .execute(pool)
+            .await?;
+
+            Ok(())
+        })
+    }
+
+    /// Wipe the paper-trading record for a fresh start.
+    /// Discovered tokens and metrics are always kept; signals only go when asked.
+    pub async fn reset_simulation(&self, include_signals: bool) -> Result<u64> {
+        with_pool!(self, pool => {
+            let mut tx = pool.begin().await?;
+
+            let deleted = sqlx::query("DELETE FROM simulated_trades")
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+
+            let archived = sqlx::query("DELETE FROM trade_archive")
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+
+            sqlx::query("DELETE FROM trade_targets")
+                .execute(&mut *tx)
+                .await?;
+
+            if include_signals {
+                // Deliveries reference their signal, so they have to go first
+                sqlx::query("DELETE FROM signal_deliveries")
+                    .execute(&mut *tx)
+                    .await?;
+
+                sqlx::query("DELETE FROM trading_signals")
+                    .execute(&mut *tx)
+                    .await?;
+            }
+
+            tx.commit().await?;
+            info!("🧹 Simulation reset: {} trades deleted (signals purged: {})", deleted + archived, include_signals);
+
+            Ok(deleted + archived)
+        })
+    }
+
+    /// Move trades closed before `closed_before` into trade_archive
+    pub async fn archive_closed_trades(&self, closed_before: DateTime<Utc>) -> Result<u64> {
+        with_pool!(self, pool => {
+            let mut tx = pool.begin().await?;
+
+            sqlx::query(&format!(
+                "INSERT INTO trade_archive ({cols}) SELECT {cols} FROM simulated_trades WHERE is_active = FALSE AND exit_time < $1",
+                cols = TRADE_COLUMNS
+            ))
+            .bind(closed_before.to_rfc3339())
+            .execute(&mut *tx)
+            .await?;
+
+            let archived = sqlx::query("DELETE FROM simulated_trades WHERE is_active = FALSE AND exit_time < $1")
+                .bind(closed_before.to_rfc3339())
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+
+            tx.commit().await?;
+            Ok(archived)
+        })
     }
 
     // WHALE OPERATIONS
     pub async fn save_whale_wallet(&self, whale: &WhaleWallet) -> Result<i64> {
-        let result = sqlx::query(r#"
-            INSERT OR REPLACE INTO whale_wallets 
-            (address, chain, label, balance_usd, success_rate, avg_multiplier, is_active, created_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
-        "#)
-        .bind(&whale.address)
-        .bind(&whale.chain)
-        .bind(&whale.label)
-        .bind(whale.balance_usd.map(|d| d.to_string()))
-        .bind(whale.success_rate.map(|d| d.to_string()))
-        .bind(whale.avg_multiplier.map(|d| d.to_string()))
-        .bind(whale.is_active)
-        .bind(whale.created_at.to_rfc3339())
-        .execute(&self.pool)
-        .await?;
-
-        Ok(result.last_insert_rowid())
+        with_pool!(self, pool => {
+            let ids = sqlx::query_scalar::<_, i64>(r#"
+                INSERT INTO whale_wallets 
+                (address, chain, label, balance_usd, success_rate, avg_multiplier, is_active, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (address) DO UPDATE SET
+                    chain = excluded.chain, label = excluded.label, balance_usd = excluded.balance_usd,
+                    success_rate = excluded.success_rate, avg_multiplier = excluded.avg_multiplier,
+                    is_active = excluded.is_active, created_at = excluded.created_at
+                RETURNING id
+            "#)
+            .bind(&whale.address)
+            .bind(whale.chain.to_string())
+            .bind(&whale.label)
+            .bind(whale.balance_usd.map(|d| d.to_string()))
+            .bind(whale.success_rate.map(|d| d.to_string()))
+            .bind(whale.avg_multiplier.map(|d| d.to_string()))
+            .bind(whale.is_active)
+            .bind(whale.created_at.to_rfc3339())
+            .fetch_all(pool)
+            .await?;
+
+            returned(ids)
+        })
     }
 
     pub async fn get_active_whales(&self) -> Result<Vec<WhaleWallet>> {
-        let rows = sqlx::query(r#"
-            SELECT * FROM whale_wallets 
-            WHERE is_active = TRUE 
-            ORDER BY success_rate DESC
-        "#)
-        .fetch_all(&self.pool)
-        .await?;
-
-        let mut whales = Vec::new();
-        for row in rows {
-            whales.push(WhaleWallet {
-                id: Some(row.get("id")),
-                address: row.get("address"),
-                chain: row.get("chain"),
-                label: row.get("label"),
-                balance_usd: row.get::<Option<String>, _>("balance_usd").map(|s| s.parse()).transpose()?,
-                success_rate: row.get::<Option<String>, _>("success_rate").map(|s| s.parse()).transpose()?,
-                avg_multiplier: row.get::<Option<String>, _>("avg_multiplier").map(|s| s.parse()).transpose()?,
-                is_active: row.get("is_active"),
-                created_at: row.get::<String, _>("created_at").parse()?,
-            });
-        }
+        with_pool!(self, pool => {
+            let rows = sqlx::query(r#"
+                SELECT * FROM whale_wallets 
+                WHERE is_active = TRUE 
+                ORDER BY success_rate DESC
+            "#)
+            .fetch_all(pool)
+            .await?;
+
+            let mut whales = Vec::new();
+            for row in rows {
+                whales.push(WhaleWallet {
+                    id: Some(row.get("id")),
+                    address: row.get("address"),
+                    chain: row.get::<String, _>("chain").parse()?,
+                    label: row.get("label"),
+                    balance_usd: row.get::<Option<String>, _>("balance_usd").map(|s| s.parse()).transpose()?,
+                    success_rate: row.get::<Option<String>, _>("success_rate").map(|s| s.parse()).transpose()?,
+                    avg_multiplier: row.get::<Option<String>, _>("avg_multiplier").map(|s| s.parse()).transpose()?,
+                    is_active: row.get("is_active"),
+                    created_at: row.get::<String, _>("created_at").parse()?,
+                });
+            }
+
+            Ok(whales)
+        })
+    }
+
+    /// Record a whale transaction; false if one with the same hash was already recorded
+    pub async fn save_whale_transaction(&self, transaction: &WhaleTransaction) -> Result<bool> {
+        with_pool!(self, pool => {
+            let result = sqlx::query(r#"
+                INSERT INTO whale_transactions
+                (whale_address, token_address, transaction_hash, action, amount_tokens, amount_usd, timestamp)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                ON CONFLICT (transaction_hash) DO NOTHING
+            "#)
+            .bind(&transaction.whale_address)
+            .bind(&transaction.token_address)
+            .bind(&transaction.transaction_hash)
+            .bind(format!("{:?}", transaction.action).to_lowercase())
+            .bind(transaction.amount_tokens.to_string())
+            .bind(transaction.amount_usd.map(|d| d.to_string()))
+            .bind(transaction.timestamp.to_rfc3339())
+            .execute(pool)
+            .await?;
+
+            Ok(result.rows_affected() > 0)
+        })
+    }
 
-        Ok(whales)
+    /// A whale's latest transactions, newest first
+    pub async fn get_recent_whale_transactions(&self, whale_address: &str, limit: i64) -> Result<Vec<WhaleTransaction>> {
+        with_pool!(self, pool => {
+            let rows = sqlx::query(r#"
+                SELECT * FROM whale_transactions
+                WHERE whale_address = $1
+                ORDER BY timestamp DESC
+                LIMIT $2
+            "#)
+            .bind(whale_address)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+
+            let mut transactions = Vec::new();
+            for row in rows {
+                transactions.push(WhaleTransaction {
+                    id: Some(row.get("id")),
+                    whale_address: row.get("whale_address"),
+                    token_address: row.get("token_address"),
+                    transaction_hash: row.get("transaction_hash"),
+                    action: match row.get::<String, _>("action").as_str() {
+                        "buy" => crate::models::TransactionAction::Buy,
+                        "sell" => crate::models::TransactionAction::Sell,
+                        _ => crate::models::TransactionAction::Transfer,
+                    },
+                    amount_tokens: row.get::<String, _>("amount_tokens").parse()?,
+                    amount_usd: row.get::<Option<String>, _>("amount_usd").map(|s| s.parse()).transpose()?,
+                    timestamp: row.get::<String, _>("timestamp").parse()?,
+                });
+            }
+
+            Ok(transactions)
+        })
     }
 
     // STATISTICS
     pub async fn get_trading_stats(&self) -> Result<TradingStats> {
-        let total_trades = sqlx::query_scalar::<_, i64>(r#"
-            SELECT COUNT(*) FROM simulated_trades WHERE is_active = FALSE
-        "#)
-        .fetch_one(&self.pool)
-        .await?;
-
-        let profitable_trades = sqlx::query_scalar::<_, i64>(r#"
-            SELECT COUNT(*) FROM simulated_trades 
-            WHERE is_active = FALSE AND profit_loss > '0'
-        "#)
-        .fetch_one(&self.pool)
-        .await?;
-
-        let total_profit = sqlx::query_scalar::<_, Option<String>>(r#"
-            SELECT SUM(CAST(profit_loss AS REAL)) FROM simulated_trades 
-            WHERE is_active = FALSE
-        "#)
-        .fetch_one(&self.pool)
-        .await?;
-
-        let avg_multiplier = sqlx::query_scalar::<_, Option<String>>(r#"
-            SELECT AVG(CAST(multiplier AS REAL)) FROM simulated_trades 
-            WHERE is_active = FALSE AND multiplier IS NOT NULL
-        "#)
-        .fetch_one(&self.pool)
-        .await?;
-
-        Ok(TradingStats {
-            total_trades,
-            profitable_trades,
-            win_rate: if total_trades > 0 { 
-                (profitable_trades as f64 / total_trades as f64) * 100.0 
-            } else { 
-                0.0 
-            },
-            total_profit_usd: total_profit
-                .and_then(|s| s.parse::<f64>().ok())
-                .unwrap_or(0.0),
-            avg_multiplier: avg_multiplier
-                .and_then(|s| s.parse::<f64>().ok())
-                .unwrap_or(1.0),
+        with_pool!(self, pool => {
+            let total_trades = sqlx::query_scalar::<_, i64>(r#"
+                SELECT COUNT(*) FROM all_trades WHERE is_active = FALSE
+            "#)
+            .fetch_one(pool)
+            .await?;
+
+            let profitable_trades = sqlx::query_scalar::<_, i64>(r#"
+                SELECT COUNT(*) FROM all_trades 
+                WHERE is_active = FALSE AND profit_loss > '0'
+            "#)
+            .fetch_one(pool)
+            .await?;
+
+            let total_profit = sqlx::query_scalar::<_, Option<f64>>(r#"
+                SELECT SUM(CAST(profit_loss AS DOUBLE PRECISION)) FROM all_trades 
+                WHERE is_active = FALSE
+            "#)
+            .fetch_one(pool)
+            .await?;
+
+            let avg_multiplier = sqlx::query_scalar::<_, Option<f64>>(r#"
+                SELECT AVG(CAST(multiplier AS DOUBLE PRECISION)) FROM all_trades 
+                WHERE is_active = FALSE AND multiplier IS NOT NULL
+            "#)
+            .fetch_one(pool)
+            .await?;
+
+            Ok(TradingStats {
+                total_trades,
+                profitable_trades,
+                win_rate: if total_trades > 0 { 
+                    (profitable_trades as f64 / total_trades as f64) * 100.0 
+                } else { 
+                    0.0 
+                },
+                total_profit_usd: total_profit.unwrap_or(0.0),
+                avg_multiplier: avg_multiplier.unwrap_or(1.0),
+            })
         })
     }
 }
 
+fn token_from_row<R: Row>(row: &R) -> Result<Token>
+where
+    for<'c> &'c str: ColumnIndex<R>,
+    for<'r> String: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> i64: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> bool: Decode<'r, R::Database> + Type<R::Database>,
+{
+    Ok(Token {
+        id: Some(row.get("id")),
+        address: row.get("address"),
+        symbol: row.get("symbol"),
+        name: row.get("name"),
+        chain: row.get::<String, _>("chain").parse()?,
+        source: row.get("source"),
+        created_at: row.get::<String, _>("created_at").parse()?,
+        first_seen: row.get::<String, _>("first_seen").parse()?,
+        is_active: row.get("is_active"),
+        deployed_at: row.get::<Option<String>, _>("deployed_at").map(|s| s.parse()).transpose()?,
+        deployer: row.get("deployer"),
+        creation_checked_at: row.get::<Option<String>, _>("creation_checked_at").map(|s| s.parse()).transpose()?,
+    })
+}
+
+fn signal_from_row<R: Row>(row: &R) -> Result<TradingSignal>
+where
+    for<'c> &'c str: ColumnIndex<R>,
+    for<'r> String: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> i64: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> bool: Decode<'r, R::Database> + Type<R::Database>,
+{
+    Ok(TradingSignal {
+        id: Some(row.get("id")),
+        token_address: row.get("token_address"),
+        signal_type: match row.get::<String, _>("signal_type").as_str() {
+            "buy" => crate::models::SignalType::Buy,
+            "sell" => crate::models::SignalType::Sell,
+            "warning" => crate::models::SignalType::Warning,
+            "whalemovement" => crate::models::SignalType::WhaleMovement,
+            "socialbuzz" => crate::models::SignalType::SocialBuzz,
+            _ => crate::models::SignalType::Buy,
+        },
+        confidence: row.get::<String, _>("confidence").parse()?,
+        reason: row.get("reason"),
+        target_multiplier: row.get::<Option<String>, _>("target_multiplier").map(|s| s.parse()).transpose()?,
+        created_at: row.get::<String, _>("created_at").parse()?,
+        is_sent: row.get("is_sent"),
+    })
+}
+
+fn trade_from_row<R: Row>(row: &R) -> Result<SimulatedTrade>
+where
+    for<'c> &'c str: ColumnIndex<R>,
+    for<'r> String: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> i64: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> bool: Decode<'r, R::Database> + Type<R::Database>,
+{
+    Ok(SimulatedTrade {
+        id: Some(row.get("id")),
+        token_address: row.get("token_address"),
+        entry_price: row.get::<String, _>("entry_price").parse()?,
+        entry_time: row.get::<String, _>("entry_time").parse()?,
+        exit_price: row.get::<Option<String>, _>("exit_price").map(|s| s.parse()).transpose()?,
+        exit_time: row.get::<Option<String>, _>("exit_time").map(|s| s.parse()).transpose()?,
+        investment_usd: row.get::<String, _>("investment_usd").parse()?,
+        profit_loss: row.get::<Option<String>, _>("profit_loss").map(|s| s.parse()).transpose()?,
+        multiplier: row.get::<Option<String>, _>("multiplier").map(|s| s.parse()).transpose()?,
+        exit_reason: row.get("exit_reason"),
+        is_active: row.get("is_active"),
+        breakeven_armed: row.get("breakeven_armed"),
+        remaining_fraction: row.get::<String, _>("remaining_fraction").parse()?,
+        realized_profit_loss: row.get::<String, _>("realized_profit_loss").parse()?,
+        targets_hit: row.get::<i64, _>("targets_hit") as u32,
+        target_multiplier: row.get::<Option<String>, _>("target_multiplier").map(|s| s.parse()).transpose()?,
+        source: row.get("source"),
+        max_hold_hours: row.get("max_hold_hours"),
+    })
+}
+
+/// The row an `INSERT ... RETURNING` produced. Such statements are run with `fetch_all`:
+/// SQLite only commits the write once the statement has run to completion, which `fetch_one`
+/// doesn't do, leaving the new row invisible to the pool's other connections.
+fn returned<T>(rows: Vec<T>) -> Result<T> {
+    rows.into_iter().next().ok_or_else(|| anyhow::anyhow!("INSERT ... RETURNING produced no row"))
+}
+
+/// EVM (0x) addresses are stored lowercase so any checksum casing matches
+fn blacklist_key(address: &str) -> String {
+    if address.starts_with("0x") {
+        address.to_lowercase()
+    } else {
+        address.to_string()
+    }
+}
+
+/// Shared by the single-row and batched write paths
+fn insert_token_query<DB: sqlx::Database>(token: &Token) -> Query<'_, DB, <DB as HasArguments<'_>>::Arguments>
+where
+    for<'q> String: Encode<'q, DB> + Type<DB>,
+    for<'q> Option<String>: Encode<'q, DB> + Type<DB>,
+    for<'q> bool: Encode<'q, DB> + Type<DB>,
+{
+    // A re-scan doesn't know the deploy time or deployer, so keep the ones already looked up
+    sqlx::query(r#"
+        INSERT INTO tokens 
+        (address, symbol, name, chain, source, created_at, first_seen, is_active, deployed_at, deployer)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (address) DO UPDATE SET
+            symbol = excluded.symbol, name = excluded.name, chain = excluded.chain,
+            source = excluded.source, created_at = excluded.created_at,
+            first_seen = excluded.first_seen, is_active = excluded.is_active,
+            deployed_at = COALESCE(excluded.deployed_at, tokens.deployed_at),
+            deployer = COALESCE(excluded.deployer, tokens.deployer)
+        RETURNING id
+    "#)
+    .bind(&token.address)
+    .bind(&token.symbol)
+    .bind(&token.name)
+    .bind(token.chain.to_string())
+    .bind(&token.source)
+    .bind(token.created_at.to_rfc3339())
+    .bind(token.first_seen.to_rfc3339())
+    .bind(token.is_active)
+    .bind(token.deployed_at.map(|t| t.to_rfc3339()))
+    .bind(&token.deployer)
+}
+
+fn insert_metrics_query<DB: sqlx::Database>(metrics: &TokenMetrics) -> Query<'_, DB, <DB as HasArguments<'_>>::Arguments>
+where
+    for<'q> String: Encode<'q, DB> + Type<DB>,
+    for<'q> Option<String>: Encode<'q, DB> + Type<DB>,
+    for<'q> Option<i64>: Encode<'q, DB> + Type<DB>,
+    for<'q> Option<bool>: Encode<'q, DB> + Type<DB>,
+{
+    sqlx::query(r#"
+        INSERT INTO token_metrics 
+        (token_address, timestamp, price_usd, market_cap_usd, liquidity_usd, 
+         volume_24h_usd, buy_count_24h, total_supply, circulating_supply, holder_count, 
+         top_10_holders_percentage, is_honeypot, is_mintable, has_proxy, contract_verified,
+         ownership_renounced, owner_privileged, buy_tax_pct, sell_tax_pct, price_consistent,
+         volume_5m_usd, volume_1h_usd, volume_6h_usd,
+         price_change_5m_pct, price_change_1h_pct, price_change_6h_pct, price_change_24h_pct)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20,
+                $21, $22, $23, $24, $25, $26, $27)
+        RETURNING id
+    "#)
+    .bind(&metrics.token_address)
+    .bind(metrics.timestamp.to_rfc3339())
+    .bind(metrics.price_usd.map(|d| d.to_string()))
+    .bind(metrics.market_cap_usd.map(|d| d.to_string()))
+    .bind(metrics.liquidity_usd.map(|d| d.to_string()))
+    .bind(metrics.volume_24h_usd.map(|d| d.to_string()))
+    .bind(metrics.buy_count_24h.map(i64::from))
+    .bind(metrics.total_supply.map(|d| d.to_string()))
+    .bind(metrics.circulating_supply.map(|d| d.to_string()))
+    .bind(metrics.holder_count.map(i64::from))
+    .bind(metrics.top_10_holders_percentage.map(|d| d.to_string()))
+    .bind(metrics.is_honeypot)
+    .bind(metrics.is_mintable)
+    .bind(metrics.has_proxy)
+    .bind(metrics.contract_verified)
+    .bind(metrics.ownership_renounced)
+    .bind(metrics.owner_privileged)
+    .bind(metrics.buy_tax_pct.map(|d| d.to_string()))
+    .bind(metrics.sell_tax_pct.map(|d| d.to_string()))
+    .bind(metrics.price_consistent)
+    .bind(metrics.volume_5m_usd.map(|d| d.to_string()))
+    .bind(metrics.volume_1h_usd.map(|d| d.to_string()))
+    .bind(metrics.volume_6h_usd.map(|d| d.to_string()))
+    .bind(metrics.price_change_5m_pct.map(|d| d.to_string()))
+    .bind(metrics.price_change_1h_pct.map(|d| d.to_string()))
+    .bind(metrics.price_change_6h_pct.map(|d| d.to_string()))
+    .bind(metrics.price_change_24h_pct.map(|d| d.to_string()))
+}
+
+fn metrics_from_row<R: Row>(row: &R) -> Result<TokenMetrics>
+where
+    for<'c> &'c str: ColumnIndex<R>,
+    for<'r> String: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> i64: Decode<'r, R::Database> + Type<R::Database>,
+    for<'r> bool: Decode<'r, R::Database> + Type<R::Database>,
+{
+    Ok(TokenMetrics {
+        id: Some(row.get("id")),
+        token_address: row.get("token_address"),
+        timestamp: row.get::<String, _>("timestamp").parse()?,
+        price_usd: row.get::<Option<String>, _>("price_usd").map(|s| s.parse()).transpose()?,
+        market_cap_usd: row.get::<Option<String>, _>("market_cap_usd").map(|s| s.parse()).transpose()?,
+        liquidity_usd: row.get::<Option<String>, _>("liquidity_usd").map(|s| s.parse()).transpose()?,
+        volume_24h_usd: row.get::<Option<String>, _>("volume_24h_usd").map(|s| s.parse()).transpose()?,
+        buy_count_24h: row.get::<Option<i64>, _>("buy_count_24h").map(|n| n as u32),
+        total_supply: row.get::<Option<String>, _>("total_supply").map(|s| s.parse()).transpose()?,
+        circulating_supply: row.get::<Option<String>, _>("circulating_supply").map(|s| s.parse()).transpose()?,
+        holder_count: row.get::<Option<i64>, _>("holder_count").map(|n| n as u32),
+        top_10_holders_percentage: row.get::<Option<String>, _>("top_10_holders_percentage").map(|s| s.parse()).transpose()?,
+        is_honeypot: row.get("is_honeypot"),
+        is_mintable: row.get("is_mintable"),
+        has_proxy: row.get("has_proxy"),
+        contract_verified: row.get("contract_verified"),
+        ownership_renounced: row.get("ownership_renounced"),
+        owner_privileged: row.get("owner_privileged"),
+        buy_tax_pct: row.get::<Option<String>, _>("buy_tax_pct").map(|s| s.parse()).transpose()?,
+        sell_tax_pct: row.get::<Option<String>, _>("sell_tax_pct").map(|s| s.parse()).transpose()?,
+        price_consistent: row.get("price_consistent"),
+        volume_5m_usd: row.get::<Option<String>, _>("volume_5m_usd").map(|s| s.parse()).transpose()?,
+        volume_1h_usd: row.get::<Option<String>, _>("volume_1h_usd").map(|s| s.parse()).transpose()?,
+        volume_6h_usd: row.get::<Option<String>, _>("volume_6h_usd").map(|s| s.parse()).transpose()?,
+        price_change_5m_pct: row.get::<Option<String>, _>("price_change_5m_pct").map(|s| s.parse()).transpose()?,
+        price_change_1h_pct: row.get::<Option<String>, _>("price_change_1h_pct").map(|s| s.parse()).transpose()?,
+        price_change_6h_pct: row.get::<Option<String>, _>("price_change_6h_pct").map(|s| s.parse()).transpose()?,
+        price_change_24h_pct: row.get::<Option<String>, _>("price_change_24h_pct").map(|s| s.parse()).transpose()?,
+    })
+}
+
+/// A token the user follows by hand
+#[derive(Debug, Clone)]
+pub struct WatchlistEntry {
+    pub token_address: String,
+    pub added_at: DateTime<Utc>,
+    pub note: Option<String>,
+}
+
+/// How `get_top_trades` ranks closed trades
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TradeOrder {
+    BestMultiplier,
+    WorstProfitLoss,
+}
+
+/// How far a signal got on one delivery channel
+#[derive(Debug, Clone)]
+pub struct SignalDelivery {
+    pub channel: String,
+    pub status: String, // "delivered", "pending" or "abandoned"
+    pub attempts: u32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+}
+
+impl SignalDelivery {
+    /// Delivered or given up on
+    pub fn is_finished(&self) -> bool {
+        self.status == "delivered" || self.status == "abandoned"
+    }
+}
+
+/// Closed-trade totals for one token
+#[derive(Debug, Clone)]
+pub struct TokenPnl {
+    pub invested_usd: f64,
+    pub realized_profit_usd: f64,
+    pub wins: i64,
+    pub losses: i64,
+}
+
 #[derive(Debug, Clone)]
 pub struct TradingStats {
     pub total_trades: i64,
@@ -520,3 +1811,195 @@ pub struct TradingStats {
     pub total_profit_usd: f64,
     pub avg_multiplier: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SignalType;
+    use crate::testing::{memory_db, metrics, token};
+    use rust_decimal::Decimal;
+
+    fn signal(token_address: &str) -> TradingSignal {
+        TradingSignal {
+            id: None,
+            token_address: token_address.to_string(),
+            signal_type: SignalType::Buy,
+            confidence: rust_decimal::Decimal::new(9, 1),
+            reason: "test".to_string(),
+            target_multiplier: None,
+            created_at: Utc::now(),
+            is_sent: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn reset_purges_signals_that_have_deliveries() {
+        let db = memory_db().await;
+        db.save_token(&token("TOKEN", "TKN")).await.unwrap();
+        let signal_id = db.save_trading_signal(&signal("TOKEN")).await.unwrap();
+        db.record_signal_delivery(signal_id, "telegram:1", "delivered", None, None).await.unwrap();
+
+        db.reset_simulation(true).await.unwrap();
+
+        assert!(db.get_unsent_signals().await.unwrap().is_empty());
+        assert!(db.get_signal_deliveries(signal_id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn token_batch_is_written_and_read_back() {
+        let db = memory_db().await;
+        let mut first = metrics("AAA");
+        first.price_usd = Some(rust_decimal::Decimal::new(125, 3));
+        first.holder_count = Some(42);
+
+        db.save_token_batch(&[(token("AAA", "A"), first), (token("BBB", "B"), metrics("BBB"))]).await.unwrap();
+
+        assert_eq!(db.get_token("AAA").await.unwrap().unwrap().symbol, "A");
+        assert_eq!(db.get_token("BBB").await.unwrap().unwrap().symbol, "B");
+        let saved = db.get_latest_metrics("AAA").await.unwrap().unwrap();
+        assert_eq!(saved.price_usd, Some(rust_decimal::Decimal::new(125, 3)));
+        assert_eq!(saved.holder_count, Some(42));
+        assert!(db.get_latest_metrics("BBB").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn failed_token_batch_writes_nothing() {
+        let db = memory_db().await;
+        // The second snapshot points at a token that doesn't exist, violating its foreign key
+        let batch = [
+            (token("AAA", "A"), metrics("AAA")),
+            (token("BBB", "B"), metrics("MISSING")),
+            (token("CCC", "C"), metrics("CCC")),
+        ];
+
+        assert!(db.save_token_batch(&batch).await.is_err());
+
+        for address in ["AAA", "BBB", "CCC"] {
+            assert!(db.get_token(address).await.unwrap().is_none(), "{} was written", address);
+            assert!(db.get_latest_metrics(address).await.unwrap().is_none(), "{} metrics were written", address);
+        }
+    }
+
+    /// SQLite's plan for `query`, one detail line per step
+    async fn query_plan(db: &Database, query: &str) -> String {
+        let pool = match &db.pool {
+            Pool::Sqlite(pool) => pool,
+            Pool::Postgres(_) => unreachable!("tests run on SQLite"),
+        };
+        let rows = sqlx::query(&format!("EXPLAIN QUERY PLAN {}", query))
+            .bind("TOKEN")
+            .fetch_all(pool)
+            .await
+            .unwrap();
+        rows.iter().map(|row| row.get::<String, _>("detail")).collect::<Vec<_>>().join("\n")
+    }
+
+    #[tokio::test]
+    async fn hot_lookups_use_their_indexes() {
+        let db = memory_db().await;
+
+        let plan = query_plan(&db, LATEST_METRICS_QUERY).await;
+        assert!(plan.contains("idx_token_metrics_token_time"), "latest metrics plan: {}", plan);
+
+        let plan = query_plan(&db, UNSENT_SIGNALS_QUERY).await;
+        assert!(plan.contains("idx_trading_signals_sent_created"), "unsent signals plan: {}", plan);
+    }
+
+    #[tokio::test]
+    async fn recent_tokens_come_back_newest_first_and_complete() {
+        let db = memory_db().await;
+        let mut old = token("OLD", "OLDY");
+        old.first_seen = Utc::now() - chrono::Duration::hours(2);
+        let mut new = token("0xNEW", "NEWT");
+        new.chain = crate::models::Chain::Base;
+        new.deployed_at = Some(Utc::now() - chrono::Duration::hours(1));
+        new.deployer = Some("0xdeployer".to_string());
+        db.save_token(&old).await.unwrap();
+        db.save_token(&new).await.unwrap();
+
+        let recent = db.get_recent_tokens(10).await.unwrap();
+
+        let addresses: Vec<&str> = recent.iter().map(|t| t.address.as_str()).collect();
+        assert_eq!(addresses, ["0xNEW", "OLD"]);
+        let (got, want) = (&recent[0], &new);
+        assert!(got.id.is_some());
+        assert_eq!((&got.symbol, &got.name, &got.chain, &got.source), (&want.symbol, &want.name, &want.chain, &want.source));
+        assert_eq!(got.first_seen.timestamp(), want.first_seen.timestamp());
+        assert_eq!(got.created_at.timestamp(), want.created_at.timestamp());
+        assert_eq!(got.deployed_at.map(|t| t.timestamp()), want.deployed_at.map(|t| t.timestamp()));
+        assert_eq!(got.deployer, want.deployer);
+        assert!(got.is_active);
+        assert_eq!(recent[1].first_seen.timestamp(), old.first_seen.timestamp());
+    }
+
+    #[tokio::test]
+    async fn concurrent_writers_wait_instead_of_failing() {
+        let path = std::env::temp_dir().join(format!("sniperbot-concurrency-{}.db", std::process::id()));
+        let url = format!("sqlite:{}?mode=rwc", path.display());
+        let db = std::sync::Arc::new(Database::new(&url, 8).await.unwrap());
+        db.migrate().await.unwrap();
+        db.save_token(&token("TOKEN", "TKN")).await.unwrap();
+
+        let writers: Vec<_> = (0..8).map(|_| {
+            let db = db.clone();
+            tokio::spawn(async move {
+                for _ in 0..25 {
+                    db.save_token_metrics(&metrics("TOKEN")).await?;
+                }
+                anyhow::Ok(())
+            })
+        }).collect();
+        let mut failures = Vec::new();
+        for writer in writers {
+            if let Err(e) = writer.await.unwrap() {
+                failures.push(e.to_string());
+            }
+        }
+        let since = Utc::now() - chrono::Duration::hours(1);
+        let saved = db.get_metrics_history("TOKEN", since).await.unwrap().len();
+
+        for suffix in ["", "-wal", "-shm"] {
+            let _ = std::fs::remove_file(format!("{}{}", path.display(), suffix));
+        }
+        assert!(failures.is_empty(), "writers failed: {:?}", failures);
+        assert_eq!(saved, 8 * 25);
+    }
+
+    #[tokio::test]
+    async fn security_verdicts_update_the_snapshot_in_place() {
+        let db = memory_db().await;
+        db.save_token(&token("TOKEN", "TKN")).await.unwrap();
+        db.save_token_metrics(&metrics("TOKEN")).await.unwrap();
+
+        let mut latest = db.get_latest_metrics("TOKEN").await.unwrap().unwrap();
+        latest.is_honeypot = Some(false);
+        latest.buy_tax_pct = Some(Decimal::new(25, 1));
+        latest.sell_tax_pct = Some(Decimal::from(5));
+        latest.is_mintable = Some(true);
+        db.update_security_verdicts(&latest).await.unwrap();
+
+        let since = Utc::now() - chrono::Duration::hours(1);
+        let history = db.get_metrics_history("TOKEN", since).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].id, latest.id);
+        assert_eq!(history[0].timestamp, latest.timestamp);
+        assert_eq!(history[0].is_honeypot, Some(false));
+        assert_eq!(history[0].buy_tax_pct, Some(Decimal::new(25, 1)));
+        assert_eq!(history[0].sell_tax_pct, Some(Decimal::from(5)));
+        assert_eq!(history[0].is_mintable, Some(true));
+    }
+
+    #[tokio::test]
+    async fn unknown_creation_is_remembered_across_rescans() {
+        let db = memory_db().await;
+        db.save_token(&token("TOKEN", "TKN")).await.unwrap();
+        db.mark_creation_unknown("TOKEN").await.unwrap();
+
+        // A re-scan saves the token again without knowing about the lookup
+        db.save_token(&token("TOKEN", "TKN")).await.unwrap();
+
+        let saved = db.get_token("TOKEN").await.unwrap().unwrap();
+        assert!(saved.creation_checked_at.is_some());
+        assert!(saved.deployed_at.is_none());
+    }
+}
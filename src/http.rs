@@ -0,0 +1,76 @@
+// src/http.rs
+// The scanners' only door to the network:
+// they fetch through `HttpFetch`, so a canned response can stand in for the real API
+
+use anyhow::Result;
+use reqwest::Client;
+use std::fmt;
+use std::time::Duration;
+
+/// Something that can GET a URL and hand back the JSON body as text
+pub trait HttpFetch {
+    /// The body of a successful response; any other status is an `HttpError`
+    async fn get_json(&self, url: &str) -> Result<String>;
+}
+
+/// The real thing, backed by reqwest
+pub struct HttpClient {
+    client: Client,
+}
+
+impl HttpClient {
+    pub fn new(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+impl HttpFetch for HttpClient {
+    async fn get_json(&self, url: &str) -> Result<String> {
+        let response = self.client
+            .get(url)
+            .header("Accept", "application/json")
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(HttpError::from_response(&response).into());
+        }
+
+        Ok(response.text().await?)
+    }
+}
+
+/// A non-success HTTP response
+#[derive(Debug, Clone)]
+pub struct HttpError {
+    pub status: reqwest::StatusCode,
+    /// How long a 429 asked us to wait, if it said
+    pub retry_after: Option<Duration>,
+}
+
+impl HttpError {
+    /// The status of a failed response, and how long it asked us to back off if it's a 429
+    pub fn from_response(response: &reqwest::Response) -> Self {
+        let status = response.status();
+        let retry_after = (status == reqwest::StatusCode::TOO_MANY_REQUESTS)
+            .then(|| response.headers().get(reqwest::header::RETRY_AFTER))
+            .flatten()
+            .and_then(|value| value.to_str().ok())
+            .and_then(crate::utils::parse_retry_after);
+
+        Self { status, retry_after }
+    }
+
+    /// Worth retrying later: rate limits and server-side trouble
+    pub fn is_transient(&self) -> bool {
+        self.status == reqwest::StatusCode::TOO_MANY_REQUESTS || self.status.is_server_error()
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "HTTP {}", self.status)
+    }
+}
+
+impl std::error::Error for HttpError {}
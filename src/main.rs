@@ -1,21 +1,41 @@
 // src/main.rs
 use anyhow::Result;
-use log::{info, error};
+use chrono::{DateTime, Utc};
+use log::{info, warn, error};
+use std::future::Future;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock, Semaphore};
+use tokio::task::JoinHandle;
 
+mod activity;
+mod backtest;
 mod config;
 mod models;
+mod notifiers;
 mod scanners;
 mod analyzers;
 mod database;
+mod http;
 mod telegram;
 mod strategies;
 mod utils;
+mod selftest;
+mod webhook;
+#[cfg(test)]
+mod testing;
 
+use activity::{ActivityLog, EventKind};
+use analyzers::exit_analyzer::ExitAnalyzer;
 use config::Config;
 use database::Database;
+use strategies::profit_taking::ProfitTakingStrategy;
+use strategies::risk_management::RiskManagement;
 use telegram::TelegramBot;
+use utils::RateLimiter;
+
+/// How long services get to notice a shutdown and finish their current iteration
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
 
 #[tokio::main]
 async fn main() -> Result<()> {
@@ -27,72 +47,412 @@ async fn main() -> Result<()> {
     info!("🚀 Starting Crypto Research Bot");
 
     // Load configuration
-    let config = Config::load()?;
+    let mut config = Config::load()?;
     info!("✅ Configuration loaded");
+    if config.dry_run {
+        info!("🧪 Dry run: Telegram, signals and simulated trades are only logged");
+    }
 
     // Initialize database
-    let db = Database::new(&config.database_url).await?;
+    let db = Database::new(&config.database_url, config.db_max_connections).await?;
     db.migrate().await?;
     info!("✅ Database initialized");
 
+    // `sniperbot backtest <from> <to>` replays stored metrics instead of running the bot
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("backtest") {
+        let (from, to) = match (args.get(2), args.get(3)) {
+            (Some(from), Some(to)) => (backtest::parse_time(from)?, backtest::parse_time(to)?),
+            _ => return Err(anyhow::anyhow!("Usage: sniperbot backtest <from> <to> (RFC 3339 or YYYY-MM-DD)")),
+        };
+        backtest::run_backtest(&db, &config, from, to).await?;
+        return Ok(());
+    }
+
     // Initialize Telegram bot
-    let telegram = TelegramBot::new(&config.telegram_token).await?;
+    let telegram = TelegramBot::new(&config.telegram_token, config.dry_run).await?;
+    config.telegram_chat_ids = telegram.resolve_chats(&config.telegram_chat_ids).await?;
     info!("✅ Telegram bot initialized");
 
     // Create shared state
+    let refresh_limiter = RateLimiter::new(config.refresh_rate_limit_per_hour, Duration::from_secs(3600));
+    let honeypot_limiter = RateLimiter::new(config.honeypot_rate_limit_per_minute, Duration::from_secs(60));
+    let activity = ActivityLog::new(config.activity_log_size, config.secret_values());
+    let trading = RwLock::new(config.trading.clone());
+    let analysis_slots = Semaphore::new(config.max_concurrent_analyses.max(1));
     let app_state = Arc::new(AppState {
         config,
+        trading,
         db,
         telegram,
         running: RwLock::new(true),
+        pending_reset: RwLock::new(None),
+        rejections: RwLock::new(Default::default()),
+        refresh_limiter: Mutex::new(refresh_limiter),
+        honeypot_limiter: Mutex::new(honeypot_limiter),
+        analysis_slots,
+        last_successful_scan: RwLock::new(None),
+        activity: RwLock::new(activity),
     });
 
+    // Rejection counts pick up where the last run left off
+    if app_state.config.persist_rejection_counts {
+        match app_state.db.get_rejection_counts().await {
+            Ok(counts) => app_state.rejections.write().await.restore(counts),
+            Err(e) => warn!("Failed to load rejection counts: {}", e),
+        }
+    }
+
+    // Close whatever hit its target or stop while we were down (a dry run leaves trades alone)
+    if !app_state.config.dry_run {
+        if let Err(e) = reconcile_open_trades(&app_state).await {
+            error!("Failed to reconcile open trades: {}", e);
+        }
+    }
+
     // Start all the scanning services
     let mut handles = vec![];
 
     // Start DEX Screener scanner
-    handles.push(tokio::spawn(start_dex_screener_scanner(app_state.clone())));
+    handles.push(tokio::spawn(supervise("DEX Screener", app_state.clone(), start_dex_screener_scanner)));
+
+    // Re-run analyses that were incomplete because of missing or failed data
+    handles.push(tokio::spawn(supervise(
+        "Analysis retries",
+        app_state.clone(),
+        analyzers::token_analyzer::start_analysis_retry_loop,
+    )));
+
+    // Accept token submissions from external tools
+    if app_state.config.webhook_bind_addr.is_some() {
+        if app_state.config.webhook_token.is_some() {
+            handles.push(tokio::spawn(supervise("Webhook", app_state.clone(), webhook::start_webhook_server)));
+        } else {
+            warn!("⚠️  WEBHOOK_BIND_ADDR is set but WEBHOOK_TOKEN is not, refusing to start an unauthenticated webhook");
+        }
+    }
 
-    // Start Pump.fun scanner (when we implement it)
-    // handles.push(tokio::spawn(start_pumpfun_scanner(app_state.clone())));
+    // Close simulated trades on stop loss, time limit and profit targets
+    // (a dry run leaves the trades already in the database alone)
+    if !app_state.config.dry_run {
+        handles.push(tokio::spawn(supervise("Trade monitor", app_state.clone(), start_trade_monitor)));
+    }
+
+    // Keep the active trades table lean
+    if app_state.config.archive_closed_trades {
+        handles.push(tokio::spawn(supervise("Trade archiver", app_state.clone(), start_trade_archiver)));
+    }
+
+    // Keep token_metrics from growing forever
+    if app_state.config.metrics_retention_days > 0 {
+        handles.push(tokio::spawn(supervise("Maintenance", app_state.clone(), start_maintenance)));
+    }
+
+    // Start Pump.fun scanner
+    handles.push(tokio::spawn(supervise("Pump.fun", app_state.clone(), start_pumpfun_scanner)));
 
     // Start whale tracking
-    // handles.push(tokio::spawn(start_whale_tracker(app_state.clone())));
+    handles.push(tokio::spawn(supervise("Whale tracker", app_state.clone(), start_whale_tracker)));
+
+    // Watch Twitter/X for sudden buzz around tracked tokens
+    if app_state.config.twitter_bearer_token.is_some() {
+        handles.push(tokio::spawn(supervise("Twitter monitor", app_state.clone(), start_twitter_monitor)));
+    }
+
+    // Post a heartbeat so a silently dead scanner gets noticed
+    if app_state.config.heartbeat_interval_minutes > 0 {
+        handles.push(tokio::spawn(supervise("Heartbeat", app_state.clone(), telegram::start_heartbeat)));
+    }
 
-    // Start Telegram bot - FIXED: Clone state before passing to avoid move
+    // Start Telegram bot
     let telegram_state = app_state.clone();
-    handles.push(tokio::spawn(async move {
-        start_telegram_bot(telegram_state).await
-    }));
+    handles.push(tokio::spawn(supervise("Telegram bot", telegram_state, start_telegram_bot)));
 
     info!("🔥 All services started! Bot is now running...");
 
-    // Wait for all services to complete
-    for handle in handles {
-        if let Err(e) = handle.await {
-            error!("Service error: {}", e);
+    // Wait for all services to complete, or for Ctrl-C / SIGTERM
+    tokio::select! {
+        _ = join_services(&mut handles) => {}
+        _ = shutdown_signal() => {
+            info!("🛑 Shutdown requested, stopping services...");
+            *app_state.running.write().await = false;
+
+            if tokio::time::timeout(SHUTDOWN_TIMEOUT, join_services(&mut handles)).await.is_err() {
+                warn!("⚠️  {} services still busy after {}s, aborting them", handles.len(), SHUTDOWN_TIMEOUT.as_secs());
+                for handle in &handles {
+                    handle.abort();
+                }
+            }
         }
     }
 
+    info!("👋 Bot stopped");
     Ok(())
 }
 
+/// Await every service, removing each from `handles` once it has finished
+async fn join_services(handles: &mut Vec<JoinHandle<Result<()>>>) {
+    while let Some(handle) = handles.first_mut() {
+        match handle.await {
+            Ok(Err(e)) => error!("Service error: {}", e),
+            Err(e) => error!("Service error: {}", e),
+            Ok(Ok(())) => {}
+        }
+        handles.remove(0);
+    }
+}
+
+/// Resolves on Ctrl-C, or SIGTERM on Unix
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(e) = tokio::signal::ctrl_c().await {
+            error!("Failed to listen for Ctrl-C: {}", e);
+            std::future::pending::<()>().await;
+        }
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(e) => {
+                error!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
 /// Shared application state
 pub struct AppState {
     pub config: Config,
+    pub trading: RwLock<config::TradingConfig>, // Live trading parameters, changed with /set
     pub db: Database,
     pub telegram: TelegramBot,
     pub running: RwLock<bool>,
+    pub pending_reset: RwLock<Option<telegram::PendingReset>>,
+    pub rejections: RwLock<scanners::dex_screener::RejectionLog>,
+    pub refresh_limiter: Mutex<RateLimiter>,
+    pub honeypot_limiter: Mutex<RateLimiter>, // Keyed by chain
+    pub analysis_slots: Semaphore, // Bounds concurrent token analyses
+    pub last_successful_scan: RwLock<Option<DateTime<Utc>>>, // Watched by the heartbeat
+    pub activity: RwLock<ActivityLog>,
+}
+
+impl AppState {
+    /// Note a significant event for /log
+    pub async fn record_event(&self, kind: EventKind, message: impl Into<String>) {
+        self.activity.write().await.record(kind, message);
+    }
+
+    /// Queue a signal for delivery; a dry run only logs it
+    pub async fn save_signal(&self, signal: &models::TradingSignal) -> Result<()> {
+        if self.config.dry_run {
+            info!("🧪 Dry run: not saving {:?} signal for {}: {}", signal.signal_type, signal.token_address, signal.reason);
+            return Ok(());
+        }
+
+        self.db.save_trading_signal(signal).await?;
+        Ok(())
+    }
+}
+
+/// Run a long-lived service, restarting it with exponential backoff when it
+/// errors or panics. A clean `Ok(())` exit (e.g. on shutdown) is not restarted.
+async fn supervise<F, Fut>(name: &'static str, state: Arc<AppState>, service: F) -> Result<()>
+where
+    F: Fn(Arc<AppState>) -> Fut,
+    Fut: Future<Output = Result<()>> + Send + 'static,
+{
+    let max_restarts = state.config.supervisor_max_restarts;
+    let mut restarts = 0;
+
+    loop {
+        match tokio::spawn(service(state.clone())).await {
+            Ok(Ok(())) => return Ok(()),
+            Ok(Err(e)) => {
+                error!("💥 {} failed: {}", name, e);
+                state.record_event(EventKind::Error, format!("{} failed: {}", name, e)).await;
+            }
+            Err(e) => {
+                error!("💥 {} panicked: {}", name, e);
+                state.record_event(EventKind::Error, format!("{} panicked: {}", name, e)).await;
+            }
+        }
+
+        if !*state.running.read().await {
+            return Ok(());
+        }
+
+        if restarts >= max_restarts {
+            error!("🛑 {} crashed {} times, giving up", name, restarts + 1);
+            return Err(anyhow::anyhow!("{} exceeded {} restarts", name, max_restarts));
+        }
+
+        restarts += 1;
+        let backoff = Duration::from_secs(state.config.supervisor_backoff_secs << (restarts - 1).min(6));
+        warn!("🔁 Restarting {} in {}s (restart {}/{})", name, backoff.as_secs(), restarts, max_restarts);
+        tokio::time::sleep(backoff).await;
+    }
 }
 
 async fn start_dex_screener_scanner(state: Arc<AppState>) -> Result<()> {
     use scanners::dex_screener::DexScreenerScanner;
     
     let scanner = DexScreenerScanner::new(&state.config);
-    scanner.start_scanning(state).await
+    scanners::run_scanner(&scanner, state).await
+}
+
+async fn start_pumpfun_scanner(state: Arc<AppState>) -> Result<()> {
+    use scanners::pump_fun::PumpFunScanner;
+
+    let scanner = PumpFunScanner::new(&state.config);
+    scanners::run_scanner(&scanner, state).await
+}
+
+async fn start_whale_tracker(state: Arc<AppState>) -> Result<()> {
+    use scanners::whale_tracker::WhaleTracker;
+
+    let tracker = WhaleTracker::new(&state.config);
+    tracker.start_tracking(state).await
+}
+
+async fn start_twitter_monitor(state: Arc<AppState>) -> Result<()> {
+    use scanners::twitter_monitor::TwitterMonitor;
+
+    let bearer_token = state.config.twitter_bearer_token.clone().unwrap_or_default();
+    let monitor = TwitterMonitor::new(&state.config, &bearer_token);
+    monitor.start_monitoring(state).await
+}
+
+async fn start_trade_monitor(state: Arc<AppState>) -> Result<()> {
+    info!("📊 Starting trade monitor...");
+
+    loop {
+        let risk = RiskManagement::from_config(&*state.trading.read().await);
+        if let Err(e) = risk.check_volume_spikes(&state).await {
+            error!("Failed to check for volume spikes: {}", e);
+            state.record_event(EventKind::Error, format!("Volume spike check failed: {}", e)).await;
+        }
+        check_exits(&state).await;
+
+        if !*state.running.read().await {
+            info!("🛑 Trade monitor stopping...");
+            break;
+        }
+
+        tokio::time::sleep(utils::jittered(state.config.scan_intervals.trade_monitoring, state.config.scan_intervals.jitter_pct)).await;
+    }
+
+    Ok(())
+}
+
+/// Run every exit condition over the active trades once, on current prices
+async fn check_exits(state: &Arc<AppState>) {
+    let trading = state.trading.read().await.clone();
+    let risk = RiskManagement::from_config(&trading);
+    let profit_taking = ProfitTakingStrategy::from_config(&trading);
+
+    // Risk exits first, so a position that hit its stop isn't also counted as taking profit
+    if let Err(e) = risk.check_liquidity_rugs(state).await {
+        error!("Failed to check for liquidity rugs: {}", e);
+        state.record_event(EventKind::Error, format!("Rug check failed: {}", e)).await;
+    }
+    if let Err(e) = risk.check_risk_limits(state).await {
+        error!("Failed to check risk limits: {}", e);
+        state.record_event(EventKind::Error, format!("Risk check failed: {}", e)).await;
+    }
+    if let Err(e) = risk.check_trailing_stops(state).await {
+        error!("Failed to check trailing stops: {}", e);
+        state.record_event(EventKind::Error, format!("Trailing stop check failed: {}", e)).await;
+    }
+    if let Some(exit_analyzer) = ExitAnalyzer::from_config(&trading) {
+        if let Err(e) = exit_analyzer.check_exit_signals(state).await {
+            error!("Failed to check exit signals: {}", e);
+            state.record_event(EventKind::Error, format!("Exit analysis failed: {}", e)).await;
+        }
+    }
+    if let Err(e) = profit_taking.check_profit_targets(state).await {
+        error!("Failed to check profit targets: {}", e);
+        state.record_event(EventKind::Error, format!("Profit target check failed: {}", e)).await;
+    }
+}
+
+/// Trades left open while the bot was down may be past their exit already;
+/// settle them on fresh prices before anything else starts
+async fn reconcile_open_trades(state: &Arc<AppState>) -> Result<()> {
+    let open = state.db.get_active_trades().await?.len();
+    if open == 0 {
+        return Ok(());
+    }
+    info!("🔄 Reconciling {} open trades after startup...", open);
+
+    check_exits(state).await;
+
+    let closed = open.saturating_sub(state.db.get_active_trades().await?.len());
+    info!("✅ Reconciled open trades: {} closed, {} still open", closed, open - closed);
+    if closed > 0 {
+        state.record_event(EventKind::TradeClosed, format!("{} trades closed on startup reconciliation", closed)).await;
+    }
+
+    Ok(())
+}
+
+async fn start_trade_archiver(state: Arc<AppState>) -> Result<()> {
+    info!("🗄️ Starting trade archiver...");
+
+    loop {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(state.config.archive_after_days);
+        match state.db.archive_closed_trades(cutoff).await {
+            Ok(0) => {}
+            Ok(archived) => info!("🗄️ Archived {} closed trades", archived),
+            Err(e) => error!("Failed to archive closed trades: {}", e),
+        }
+
+        if !*state.running.read().await {
+            break;
+        }
+
+        tokio::time::sleep(utils::jittered(3600, state.config.scan_intervals.jitter_pct)).await;
+    }
+
+    Ok(())
+}
+
+/// Periodic database upkeep: prunes metrics snapshots past the retention window
+async fn start_maintenance(state: Arc<AppState>) -> Result<()> {
+    info!("🧹 Starting maintenance...");
+
+    loop {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(state.config.metrics_retention_days);
+        match state.db.prune_metrics(cutoff).await {
+            Ok(0) => {}
+            Ok(deleted) => info!("🧹 Pruned {} metrics snapshots older than {} days", deleted, state.config.metrics_retention_days),
+            Err(e) => {
+                error!("Failed to prune metrics: {}", e);
+                state.record_event(EventKind::Error, format!("Metrics pruning failed: {}", e)).await;
+            }
+        }
+
+        if !*state.running.read().await {
+            break;
+        }
+
+        tokio::time::sleep(utils::jittered(state.config.metrics_prune_interval_hours.max(1) * 3600, state.config.scan_intervals.jitter_pct)).await;
+    }
+
+    Ok(())
 }
 
-// FIXED: Accept Arc<AppState> by value and clone it for the telegram start method
 async fn start_telegram_bot(state: Arc<AppState>) -> Result<()> {
     state.telegram.start(state.clone()).await
 }
\ No newline at end of file
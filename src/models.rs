@@ -11,6 +11,8 @@ use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::fmt;
+use std::str::FromStr;
 
 /// Represents a cryptocurrency token we're tracking
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -19,11 +21,110 @@ pub struct Token {
     pub address: String,           // Contract address
     pub symbol: String,           // e.g., "PEPE"
     pub name: String,             // e.g., "Pepe Coin"
-    pub chain: String,            // e.g., "solana", "ethereum"
+    #[sqlx(try_from = "String")]
+    pub chain: Chain,             // e.g., Solana, Ethereum
     pub source: String,           // Where we found it: "dex_screener", "pump_fun", etc.
     pub created_at: DateTime<Utc>,
     pub first_seen: DateTime<Utc>,
     pub is_active: bool,
+    pub deployed_at: Option<DateTime<Utc>>, // Contract creation time from the block explorer (EVM only)
+    pub deployer: Option<String>,           // Wallet that created the contract (EVM only)
+    pub creation_checked_at: Option<DateTime<Utc>>, // Last explorer lookup that didn't find the above
+}
+
+/// A blockchain, named by its lowercase DEX Screener chain id ("solana", "bsc", ...).
+/// That id is also how it's stored, so chains we have no variant for survive as `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Chain {
+    Solana,
+    Ethereum,
+    Bsc,
+    Polygon,
+    Arbitrum,
+    Avalanche,
+    Base,
+    Pulsechain,
+    Other(String),
+}
+
+impl Chain {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Chain::Solana => "solana",
+            Chain::Ethereum => "ethereum",
+            Chain::Bsc => "bsc",
+            Chain::Polygon => "polygon",
+            Chain::Arbitrum => "arbitrum",
+            Chain::Avalanche => "avalanche",
+            Chain::Base => "base",
+            Chain::Pulsechain => "pulsechain",
+            Chain::Other(id) => id,
+        }
+    }
+
+    /// EVM chain id (None off EVM and for chains we don't know)
+    pub fn chain_id(&self) -> Option<u32> {
+        match self {
+            Chain::Ethereum => Some(1),
+            Chain::Bsc => Some(56),
+            Chain::Polygon => Some(137),
+            Chain::Arbitrum => Some(42161),
+            Chain::Avalanche => Some(43114),
+            Chain::Base => Some(8453),
+            Chain::Pulsechain => Some(369),
+            Chain::Solana | Chain::Other(_) => None,
+        }
+    }
+
+    /// Checks an address has this chain's format. None for chains we don't know.
+    pub fn address_validator(&self) -> Option<fn(&str) -> bool> {
+        match self {
+            Chain::Solana => Some(crate::utils::is_valid_solana_address),
+            Chain::Other(_) => None,
+            _ => Some(crate::utils::is_valid_ethereum_address),
+        }
+    }
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Chain {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let id = s.trim().to_lowercase();
+        Ok(match id.as_str() {
+            "solana" => Chain::Solana,
+            "ethereum" => Chain::Ethereum,
+            "bsc" => Chain::Bsc,
+            "polygon" => Chain::Polygon,
+            "arbitrum" => Chain::Arbitrum,
+            "avalanche" => Chain::Avalanche,
+            "base" => Chain::Base,
+            "pulsechain" => Chain::Pulsechain,
+            "" => anyhow::bail!("Empty chain id"),
+            _ => Chain::Other(id),
+        })
+    }
+}
+
+impl TryFrom<String> for Chain {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> anyhow::Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<Chain> for String {
+    fn from(chain: Chain) -> Self {
+        chain.to_string()
+    }
 }
 
 /// Market data for a token at a specific point in time
@@ -39,6 +140,18 @@ pub struct TokenMetrics {
     pub liquidity_usd: Option<Decimal>,
     pub volume_24h_usd: Option<Decimal>,
     
+    // Shorter windows (price changes in percent)
+    pub volume_5m_usd: Option<Decimal>,
+    pub volume_1h_usd: Option<Decimal>,
+    pub volume_6h_usd: Option<Decimal>,
+    pub price_change_5m_pct: Option<Decimal>,
+    pub price_change_1h_pct: Option<Decimal>,
+    pub price_change_6h_pct: Option<Decimal>,
+    pub price_change_24h_pct: Option<Decimal>,
+    
+    // Activity data
+    pub buy_count_24h: Option<u32>,
+    
     // Supply data
     pub total_supply: Option<Decimal>,
     pub circulating_supply: Option<Decimal>,
@@ -52,6 +165,13 @@ pub struct TokenMetrics {
     pub is_mintable: Option<bool>,
     pub has_proxy: Option<bool>,
     pub contract_verified: Option<bool>,
+    pub ownership_renounced: Option<bool>, // Owner set to the zero address
+    pub owner_privileged: Option<bool>,    // Owner can pause, blacklist or change tax
+    pub buy_tax_pct: Option<Decimal>,      // Token tax on buys (percent)
+    pub sell_tax_pct: Option<Decimal>,     // Token tax on sells (percent)
+    
+    // Data quality
+    pub price_consistent: Option<bool>, // Does price reconcile with liquidity/market cap?
 }
 
 /// A trading signal generated by our analysis
@@ -74,6 +194,7 @@ pub enum SignalType {
     Sell,
     Warning,    // Potential rug pull detected
     WhaleMovement,
+    SocialBuzz, // Sudden spike in Twitter/X mentions
 }
 
 /// Simulated trade for backtesting
@@ -90,6 +211,32 @@ pub struct SimulatedTrade {
     pub multiplier: Option<Decimal>,
     pub exit_reason: Option<String>, // "target_reached", "stop_loss", "time_limit"
     pub is_active: bool,
+    pub breakeven_armed: bool,       // Stop has been raised to the entry price
+    pub remaining_fraction: Decimal, // Share of the position still held (1 until scaled out)
+    pub realized_profit_loss: Decimal, // P&L already booked by partial exits
+    pub targets_hit: u32,            // Profit-target ladder levels already taken
+    pub target_multiplier: Option<Decimal>, // Exit multiplier predicted by the buy signal
+    pub source: String,              // What opened it: ANALYZER_TRADE or WHALE_COPY_TRADE
+    pub max_hold_hours: Option<i64>, // Own hold time limit set at entry (None: the global one)
+}
+
+/// Source of trades opened on the token analyzer's buy decision
+pub const ANALYZER_TRADE: &str = "analyzer";
+
+/// Source of trades mirroring a tracked whale's buy
+pub const WHALE_COPY_TRADE: &str = "whale_copy";
+
+impl SimulatedTrade {
+    /// Total P&L if the rest of the position were sold at `exit_price`
+    pub fn exit_profit_loss(&self, exit_price: Decimal) -> Decimal {
+        self.realized_profit_loss
+            + (exit_price - self.entry_price) * self.investment_usd * self.remaining_fraction / self.entry_price
+    }
+
+    /// P&L from selling `fraction` of the original position at `exit_price`
+    pub fn partial_profit_loss(&self, exit_price: Decimal, fraction: Decimal) -> Decimal {
+        (exit_price - self.entry_price) * self.investment_usd * fraction / self.entry_price
+    }
 }
 
 /// Whale wallet we're tracking
@@ -97,7 +244,8 @@ pub struct SimulatedTrade {
 pub struct WhaleWallet {
     pub id: Option<i64>,
     pub address: String,
-    pub chain: String,
+    #[sqlx(try_from = "String")]
+    pub chain: Chain,
     pub label: Option<String>,      // e.g., "Smart Money Wallet #1"
     pub balance_usd: Option<Decimal>,
     pub success_rate: Option<Decimal>, // Historical success rate
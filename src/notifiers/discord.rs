@@ -0,0 +1,80 @@
+// src/notifiers/discord.rs
+// Signals posted to a Discord channel through an incoming webhook, one embed each
+use anyhow::Result;
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+
+use crate::http::HttpError;
+use crate::models::{SignalType, Token, TokenMetrics, TradingSignal};
+use crate::notifiers::format_signal;
+
+/// Discord rejects embed descriptions longer than this
+const MAX_DESCRIPTION_CHARS: usize = 4096;
+
+pub struct DiscordNotifier {
+    client: Client,
+    webhook_url: String,
+}
+
+impl DiscordNotifier {
+    pub fn new(webhook_url: &str) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(15))
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self { client, webhook_url: webhook_url.to_string() }
+    }
+
+    /// Post a signal as an embed; a rejected post is an `HttpError`
+    pub async fn send_signal(&self, token: &Token, signal: &TradingSignal, metrics: &Option<TokenMetrics>) -> Result<()> {
+        let message = format_signal(token, signal, metrics);
+        let description: String = escape_markdown(&message.body)
+            .chars()
+            .take(MAX_DESCRIPTION_CHARS)
+            .collect();
+
+        let payload = json!({
+            "username": "Crypto Sniper Bot",
+            "embeds": [{
+                "title": message.title,
+                "description": description,
+                "color": embed_color(&signal.signal_type),
+                "timestamp": signal.created_at.to_rfc3339(),
+            }],
+        });
+
+        let response = self.client
+            .post(&self.webhook_url)
+            .json(&payload)
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            return Err(HttpError::from_response(&response).into());
+        }
+
+        Ok(())
+    }
+}
+
+/// The stripe down the side of the embed
+fn embed_color(signal_type: &SignalType) -> u32 {
+    match signal_type {
+        SignalType::Buy => 0x2ECC71,
+        SignalType::Sell => 0xE67E22,
+        SignalType::Warning => 0xE74C3C,
+        SignalType::WhaleMovement => 0x3498DB,
+        SignalType::SocialBuzz => 0x9B59B6,
+    }
+}
+
+/// Keep token names and reasons from being read as Discord markdown
+fn escape_markdown(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\\' | '*' | '_' | '~' | '`' | '|' | '>' | '#' | '[' | ']' | '(' | ')' => format!("\\{}", c),
+            _ => c.to_string(),
+        })
+        .collect()
+}
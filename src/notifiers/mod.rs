@@ -0,0 +1,151 @@
+// src/notifiers/mod.rs
+// What a signal says, independent of where it's sent:
+// Telegram renders it as MarkdownV2 text, Discord as an embed
+pub mod discord;
+
+use rust_decimal::Decimal;
+
+use crate::models::{SignalType, Token, TokenMetrics, TradingSignal};
+
+/// A signal ready to send: a headline and the plain-text body below it
+#[derive(Debug, Clone)]
+pub struct SignalMessage {
+    pub title: String,
+    pub body: String,
+}
+
+impl SignalMessage {
+    /// Headline and body as a single plain-text message
+    pub fn text(&self) -> String {
+        format!("{}\n\n{}", self.title, self.body)
+    }
+}
+
+/// Format any signal for its type
+pub fn format_signal(token: &Token, signal: &TradingSignal, metrics: &Option<TokenMetrics>) -> SignalMessage {
+    match signal.signal_type {
+        SignalType::Buy => format_buy_signal(token, signal, metrics),
+        SignalType::Sell => format_sell_signal(token, signal),
+        SignalType::Warning => format_warning_signal(token, signal),
+        SignalType::WhaleMovement => format_whale_signal(token, signal),
+        SignalType::SocialBuzz => format_buzz_signal(token, signal),
+    }
+}
+
+pub fn format_buy_signal(token: &Token, signal: &TradingSignal, metrics: &Option<TokenMetrics>) -> SignalMessage {
+    let mut body = format!(
+        "💎 {} ({})\n\
+         🔗 {}\n\
+         ⛓️ Chain: {}\n\
+         📍 Source: {}\n\n\
+         📊 Analysis:\n\
+         🎯 Confidence: {:.1}%\n",
+        token.name,
+        token.symbol,
+        token.address,
+        token.chain.as_str().to_uppercase(),
+        token.source.to_uppercase(),
+        signal.confidence * Decimal::from(100)
+    );
+
+    if let Some(target) = signal.target_multiplier {
+        body.push_str(&format!("🚀 Target: {}x\n", target));
+    }
+
+    if let Some(metrics) = metrics {
+        body.push_str("\n💰 Market Data:\n");
+
+        if let Some(price) = metrics.price_usd {
+            body.push_str(&format!("💵 Price: ${}\n", price));
+        }
+
+        if let Some(liquidity) = metrics.liquidity_usd {
+            body.push_str(&format!("💧 Liquidity: ${:.0}\n", liquidity));
+        }
+
+        if let Some(volume) = metrics.volume_24h_usd {
+            body.push_str(&format!("📈 24h Volume: ${:.0}\n", volume));
+        }
+
+        if let Some(holders) = metrics.holder_count {
+            body.push_str(&format!("👥 Holders: {}\n", holders));
+        }
+    }
+
+    body.push_str(&format!("\n🧠 Reason:\n{}\n", signal.reason));
+    body.push_str(&format!("\n⏰ Detected: {}", signal.created_at.format("%H:%M:%S UTC")));
+
+    SignalMessage { title: "🚀 BUY SIGNAL DETECTED!".to_string(), body }
+}
+
+pub fn format_sell_signal(token: &Token, signal: &TradingSignal) -> SignalMessage {
+    let body = format!(
+        "📉 {} ({})\n\
+         🔗 {}\n\n\
+         ⚠️ Reason:\n{}\n\n\
+         📊 Confidence: {:.1}%\n\
+         ⏰ {}",
+        token.name,
+        token.symbol,
+        token.address,
+        signal.reason,
+        signal.confidence * Decimal::from(100),
+        signal.created_at.format("%H:%M:%S UTC")
+    );
+
+    SignalMessage { title: "💸 SELL SIGNAL".to_string(), body }
+}
+
+pub fn format_warning_signal(token: &Token, signal: &TradingSignal) -> SignalMessage {
+    let body = format!(
+        "🚨 {} ({})\n\
+         🔗 {}\n\n\
+         ❗ Alert:\n{}\n\n\
+         📊 Confidence: {:.1}%\n\
+         ⏰ {}",
+        token.name,
+        token.symbol,
+        token.address,
+        signal.reason,
+        signal.confidence * Decimal::from(100),
+        signal.created_at.format("%H:%M:%S UTC")
+    );
+
+    SignalMessage { title: "⚠️ WARNING ALERT".to_string(), body }
+}
+
+pub fn format_whale_signal(token: &Token, signal: &TradingSignal) -> SignalMessage {
+    let body = format!(
+        "💎 {} ({})\n\
+         🔗 {}\n\n\
+         🔍 Movement:\n{}\n\n\
+         📊 Confidence: {:.1}%\n\
+         ⏰ {}",
+        token.name,
+        token.symbol,
+        token.address,
+        signal.reason,
+        signal.confidence * Decimal::from(100),
+        signal.created_at.format("%H:%M:%S UTC")
+    );
+
+    SignalMessage { title: "🐋 WHALE MOVEMENT DETECTED".to_string(), body }
+}
+
+pub fn format_buzz_signal(token: &Token, signal: &TradingSignal) -> SignalMessage {
+    let body = format!(
+        "💎 {} ({})\n\
+         🔗 {}\n\n\
+         📣 Buzz:\n{}\n\n\
+         📊 Confidence: {:.1}%\n\
+         ⏰ {}",
+        token.name,
+        token.symbol,
+        token.address,
+        signal.reason,
+        signal.confidence * Decimal::from(100),
+        signal.created_at.format("%H:%M:%S UTC")
+    );
+
+    SignalMessage { title: "🐦 SOCIAL BUZZ DETECTED".to_string(), body }
+}
@@ -6,156 +6,443 @@
 // Calls the bouncer (triggers analysis for suspicious activity)
 
 // src/scanners/dex_screener.rs
-use anyhow::Result;
-use chrono::Utc;
-use log::{info, warn, error};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use log::{debug, info, warn, error};
+use reqwest::header::{self, HeaderMap, HeaderValue};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::sleep;
 
-use crate::config::Config;
-use crate::models::{Token, TokenMetrics};
+use crate::analyzers::birdeye::{BirdeyeClient, HolderData};
+use crate::analyzers::explorer::{etherscan_chain_id, ContractCreation, EtherscanClient};
+use crate::config::{Config, DiscoveryOrder, TradingConfig};
+use crate::http::{HttpClient, HttpError, HttpFetch};
+use crate::models::{Chain, Token, TokenMetrics};
+use crate::scanners::geckoterminal::{GeckoPool, GeckoTerminalClient};
+use crate::scanners::Scanner;
+use crate::utils::{f64_to_decimal, is_valid_address};
 use crate::AppState;
 
-pub struct DexScreenerScanner {
-    client: Client,
+/// Source name new tokens and the scan cursor are recorded under
+const SCAN_SOURCE: &str = "dex_screener";
+
+/// Longest we wait between retries, whatever the backoff or Retry-After says
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Most addresses DEX Screener's token lookup takes in one request
+const PAIR_LOOKUP_BATCH: usize = 30;
+
+/// Most new tokens looked at per scan; the rest wait for a later one
+const MAX_CANDIDATES_PER_SCAN: usize = 10;
+
+/// Where a discovery strategy gets its candidate pairs from
+enum Endpoint {
+    /// Answers with pairs directly (search, trending)
+    Pairs(&'static str),
+    /// Answers with a list of tokens (profiles, boosts) whose pairs still have to be looked up
+    TokenList(&'static str),
+}
+
+impl Endpoint {
+    fn url(&self) -> &'static str {
+        match self {
+            Endpoint::Pairs(url) | Endpoint::TokenList(url) => url,
+        }
+    }
+}
+
+pub struct DexScreenerScanner<H = HttpClient> {
+    http: H,
     config: Config,
+    birdeye: Option<BirdeyeClient>, // Holder/supply data for Solana tokens, when a key is configured
+    gecko: Option<GeckoTerminalClient>, // Pools for tokens DEX Screener has no pair for
 }
 
 impl DexScreenerScanner {
     pub fn new(config: &Config) -> Self {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::REFERER, HeaderValue::from_static("https://dexscreener.com/"));
         let client = Client::builder()
             .timeout(Duration::from_secs(30))
-            .user_agent("CryptoBot/1.0")
+            .user_agent("Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
+            .default_headers(headers)
             .build()
             .expect("Failed to create HTTP client");
 
-        Self {
-            client,
-            config: config.clone(),
-        }
+        Self::with_http(config, HttpClient::new(client))
     }
+}
 
-    pub async fn start_scanning(&self, state: Arc<AppState>) -> Result<()> {
-        info!("🔍 Starting DEX Screener scanner...");
-        
-        loop {
-            match self.scan_new_tokens(&state).await {
-                Ok(count) => {
-                    if count > 0 {
-                        info!("✅ DEX Screener: Found {} new tokens", count);
-                    } else {
-                        info!("🔍 DEX Screener: No new tokens found this scan");
-                    }
-                }
-                Err(e) => {
-                    error!("❌ DEX Screener scan error: {}", e);
-                }
-            }
+impl<H: HttpFetch> Scanner for DexScreenerScanner<H> {
+    fn name(&self) -> &str {
+        "DEX Screener"
+    }
 
-            // Check if we should keep running
-            if !*state.running.read().await {
-                info!("🛑 DEX Screener scanner stopping...");
-                break;
-            }
+    /// Discover new tokens, then keep the prices of held tokens fresh
+    async fn scan_once(&self, state: &Arc<AppState>) -> Result<usize> {
+        let found = self.scan_new_tokens(state).await;
 
-            sleep(Duration::from_secs(self.config.scan_intervals.dex_screener)).await;
+        match self.refresh_open_positions(state).await {
+            Ok(0) => {}
+            Ok(count) => debug!("💹 Refreshed prices for {} open positions", count),
+            Err(e) => error!("❌ Failed to refresh open positions: {}", e),
         }
 
-        Ok(())
+        found
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.scan_intervals.dex_screener)
+    }
+}
+
+impl<H: HttpFetch> DexScreenerScanner<H> {
+    /// A scanner that fetches DEX Screener through `http`, e.g. canned responses
+    pub fn with_http(config: &Config, http: H) -> Self {
+        Self {
+            http,
+            config: config.clone(),
+            birdeye: config.birdeye_api_key.as_deref().map(BirdeyeClient::new),
+            gecko: config.geckoterminal_fallback.then(GeckoTerminalClient::new),
+        }
     }
 
     async fn scan_new_tokens(&self, state: &Arc<AppState>) -> Result<usize> {
         // Get trending tokens from DEX Screener
-        let trending_tokens = self.fetch_trending_tokens().await?;
+        let trending_tokens = self.fetch_trending_tokens(state).await?;
         let mut new_tokens_count = 0;
+        let mut candidates = 0;
+        let mut batch: Vec<(Token, TokenMetrics)> = Vec::new();
+        let mut batch_pairs: Vec<Option<i64>> = Vec::new();
 
         info!("📊 Processing {} tokens from DEX Screener", trending_tokens.len());
 
+        // Pair creation times of what's dealt with for good (saved, known or permanently rejected),
+        // and of the oldest pair that still has to be looked at again
+        let mut processed: Vec<i64> = Vec::new();
+        let mut oldest_waiting = None;
+        let mut wait = |created: Option<i64>| {
+            oldest_waiting = oldest_waiting.into_iter().chain(created).min();
+        };
+
         for dex_token in trending_tokens {
-            // Check if we already have this token
-            if state.db.get_token(&dex_token.base_token.address).await?.is_some() {
+            let address = &dex_token.base_token.address;
+
+            // A malformed address would be saved, analyzed and alerted on as if it were real
+            if !is_valid_address(&dex_token.chain_id, address) {
+                warn!("🪪 Skipping {} with a malformed {} address: {:?}", dex_token.base_token.symbol, dex_token.chain_id, address);
+                state.rejections.write().await.record(RejectedToken {
+                    symbol: dex_token.base_token.symbol.clone(),
+                    address: address.clone(),
+                    chain: dex_token.chain_id.clone(),
+                    reason: RejectReason::InvalidAddress,
+                    rejected_at: Utc::now(),
+                }, self.config.rejection_log_size);
+                processed.extend(dex_token.pair_created_at);
+                continue;
+            }
+            // Another pair of a token saved this cycle stands or falls with that one
+            if batch.iter().any(|(t, _)| &t.address == address) {
+                continue;
+            }
+            if state.db.get_token(address).await?.is_some() {
+                processed.extend(dex_token.pair_created_at);
                 continue; // Skip if we already know about this token
             }
+            if state.db.is_blacklisted(address).await? {
+                debug!("⛔ Skipping blacklisted token {} ({})", dex_token.base_token.symbol, address);
+                processed.extend(dex_token.pair_created_at);
+                continue;
+            }
 
-            // Convert DEX Screener data to our Token model
-            let token = Token {
-                id: None,
-                address: dex_token.base_token.address.clone(),
-                symbol: dex_token.base_token.symbol.clone(),
-                name: dex_token.base_token.name.clone(),
-                chain: dex_token.chain_id.clone(),
-                source: "dex_screener".to_string(),
-                created_at: Utc::now(),
-                first_seen: Utc::now(),
-                is_active: true,
+            // Limit to the most interesting new tokens to avoid overwhelming the system
+            if candidates == MAX_CANDIDATES_PER_SCAN {
+                wait(dex_token.pair_created_at);
+                continue;
+            }
+            candidates += 1;
+
+            // The riskiest launches aren't saved at all
+            let creation = if self.config.min_token_age_minutes > 0 {
+                self.contract_creation(&dex_token).await
+            } else {
+                None
             };
+            if let Some(minutes) = self.too_new(&dex_token, creation.as_ref()) {
+                debug!("🍼 Skipping {} ({}): only {} minutes old", dex_token.base_token.symbol, address, minutes);
+                state.rejections.write().await.record(RejectedToken {
+                    symbol: dex_token.base_token.symbol.clone(),
+                    address: address.clone(),
+                    chain: dex_token.chain_id.clone(),
+                    reason: RejectReason::TooNew(minutes),
+                    rejected_at: Utc::now(),
+                }, self.config.rejection_log_size);
+                wait(dex_token.pair_created_at);
+                continue;
+            }
 
-            // Save the token
-            match state.db.save_token(&token).await {
-                Ok(_) => {
-                    info!("💾 Saved new token: {} ({}) on {}", token.symbol, token.name, token.chain);
-                    new_tokens_count += 1;
+            let metrics = match self.convert_to_metrics(&dex_token).await {
+                Ok(metrics) => metrics,
+                Err(e) => {
+                    warn!("🔢 Skipping token with unusable market data: {:#}", e);
+                    wait(dex_token.pair_created_at);
+                    continue;
+                }
+            };
+            let mut token = self.to_token(&dex_token, SCAN_SOURCE);
+            // Already looked up, so analysis doesn't ask the explorer again
+            if let Some(creation) = creation {
+                token.deployed_at = Some(creation.deployed_at);
+                token.deployer = creation.deployer;
+            }
+            batch.push((token, metrics));
+            batch_pairs.push(dex_token.pair_created_at);
+        }
 
-                    // Create and save metrics
-                    let metrics = self.convert_to_metrics(&dex_token).await;
-                    if let Err(e) = state.db.save_token_metrics(&metrics).await {
-                        warn!("Failed to save metrics for {}: {}", token.symbol, e);
-                    }
+        // Write the cycle's discoveries a batch per transaction instead of row by row
+        let batch_size = self.config.db_batch_size.max(1);
+        for (chunk, pairs) in batch.chunks(batch_size).zip(batch_pairs.chunks(batch_size)) {
+            if let Err(e) = state.db.save_token_batch(chunk).await {
+                warn!("Failed to save batch of {} tokens: {}", chunk.len(), e);
+                pairs.iter().for_each(|created| wait(*created));
+                continue;
+            }
+            processed.extend(pairs.iter().flatten());
 
-                    // Analyze the token
-                    tokio::spawn({
-                        let state = state.clone();
-                        let token = token.clone();
-                        async move {
-                            if let Err(e) = analyze_and_signal(state, token).await {
-                                error!("Analysis failed: {}", e);
-                            }
-                        }
-                    });
-                }
+            for (token, _) in chunk {
+                info!("💾 Saved new token: {} ({}) on {}", token.symbol, token.name, token.chain);
+                spawn_analysis(state, token.clone());
+                new_tokens_count += 1;
+            }
+        }
+
+        if let (true, Some(position)) = (self.config.scan_cursor_enabled, cursor_position(&processed, oldest_waiting)) {
+            state.db.advance_scanner_cursor(SCAN_SOURCE, position).await?;
+        }
+        let rejected = state.rejections.write().await.take_unsaved();
+        if self.config.persist_rejection_counts && !rejected.is_empty() {
+            if let Err(e) = state.db.add_rejection_counts(&rejected).await {
+                warn!("Failed to save rejection counts: {}", e);
+            }
+        }
+        *state.last_successful_scan.write().await = Some(Utc::now());
+
+        Ok(new_tokens_count)
+    }
+
+    /// When and by whom the pair's token contract was deployed, if the explorer can tell
+    async fn contract_creation(&self, token: &DexScreenerToken) -> Option<ContractCreation> {
+        let api_key = match self.config.etherscan_api_key.as_deref() {
+            Some(key) if etherscan_chain_id(&token.chain_id).is_some() => key,
+            _ => return None,
+        };
+
+        match EtherscanClient::new(api_key).get_contract_creation(&token.chain_id, &token.base_token.address).await {
+            Ok(creation) => creation,
+            Err(e) => {
+                warn!("Contract creation lookup failed for {}: {}", token.base_token.symbol, e);
+                None
+            }
+        }
+    }
+
+    /// The token's age in minutes when it's younger than `min_token_age_minutes`: by deploy
+    /// time when known, otherwise by pair creation. Tokens of unknown age are let through.
+    fn too_new(&self, token: &DexScreenerToken, creation: Option<&ContractCreation>) -> Option<i64> {
+        if self.config.min_token_age_minutes <= 0 {
+            return None;
+        }
+
+        let born = creation.map(|c| c.deployed_at)
+            .or_else(|| token.pair_created_at.and_then(DateTime::from_timestamp_millis))?;
+        let minutes = Utc::now().signed_duration_since(born).num_minutes();
+        (minutes < self.config.min_token_age_minutes).then_some(minutes)
+    }
+
+    /// Append a fresh metrics snapshot for every token with an active trade,
+    /// so exits are decided on current prices. Returns how many were refreshed.
+    async fn refresh_open_positions(&self, state: &Arc<AppState>) -> Result<usize> {
+        let mut addresses: Vec<String> = state.db.get_active_trades().await?
+            .into_iter()
+            .map(|trade| trade.token_address)
+            .collect();
+        addresses.sort();
+        addresses.dedup();
+
+        let mut refreshed = 0;
+        for address in addresses {
+            let token = match state.db.get_token(&address).await? {
+                Some(token) => token,
+                None => continue,
+            };
+
+            match self.refresh_metrics(state, &token).await {
+                Ok(Some(_)) => refreshed += 1,
+                Ok(None) => warn!("No DEX Screener pair left for held token {}", token.symbol),
                 Err(e) => {
-                    warn!("Failed to save token {}: {}", token.symbol, e);
+                    warn!("Failed to refresh metrics for {}: {}", token.symbol, e);
+                    // Retrying the rest now would only dig the rate limit deeper
+                    if e.downcast_ref::<ApiError>().is_some_and(ApiError::is_rate_limited) {
+                        break;
+                    }
                 }
             }
         }
 
-        Ok(new_tokens_count)
+        Ok(refreshed)
+    }
+
+    /// Fetch a single token by address and push it through the save + analysis pipeline.
+    /// Returns `None` if DEX Screener has no pair for it on `chain`.
+    pub async fn ingest_token(&self, state: &Arc<AppState>, chain: &Chain, address: &str, source: &str) -> Result<Option<Token>> {
+        let token = self.discover_token(state, chain, address, source).await?;
+        if let Some(token) = &token {
+            spawn_analysis(state, token.clone());
+        }
+        Ok(token)
+    }
+
+    /// Fetch and save a single token plus its metrics without analyzing it,
+    /// for callers that want to run (and use) the analysis themselves
+    pub async fn discover_token(&self, state: &Arc<AppState>, chain: &Chain, address: &str, source: &str) -> Result<Option<Token>> {
+        match self.fetch_token_pair(Some(chain), address).await? {
+            Some(dex_token) => Ok(Some(self.save_token(state, &dex_token, source).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Like `discover_token`, for when only the address is known: the most liquid pair on any chain wins
+    pub async fn discover_token_by_address(&self, state: &Arc<AppState>, address: &str, source: &str) -> Result<Option<Token>> {
+        match self.fetch_token_pair(None, address).await? {
+            Some(dex_token) => Ok(Some(self.save_token(state, &dex_token, source).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Fetch a fresh metrics snapshot for a tracked token and store it
+    pub async fn refresh_metrics(&self, state: &AppState, token: &Token) -> Result<Option<TokenMetrics>> {
+        let metrics = self.fetch_metrics(&token.chain, &token.address).await?;
+        if let Some(metrics) = &metrics {
+            state.db.save_token_metrics(metrics).await?;
+        }
+        Ok(metrics)
     }
 
-    async fn fetch_trending_tokens(&self) -> Result<Vec<DexScreenerToken>> {
+    /// Build a fresh metrics snapshot for a token straight from the API (not saved)
+    pub async fn fetch_metrics(&self, chain: &Chain, address: &str) -> Result<Option<TokenMetrics>> {
+        match self.fetch_token_pair(Some(chain), address).await? {
+            Some(dex_token) => Ok(Some(self.convert_to_metrics(&dex_token).await?)),
+            None => Ok(None),
+        }
+    }
+
+    /// The most liquid pair for a token, on `chain` or (if `None`) any chain.
+    /// Falls back to GeckoTerminal's pools when DEX Screener has none and the chain is known.
+    async fn fetch_token_pair(&self, chain: Option<&Chain>, address: &str) -> Result<Option<DexScreenerToken>> {
+        let url = format!("https://api.dexscreener.com/latest/dex/tokens/{}", address);
+        let pairs = self.fetch_pairs(&url).await?;
+
+        let best = most_liquid(pairs
+            .into_iter()
+            .filter(|p| chain.is_none_or(|chain| p.chain_id == *chain))
+            .filter(|p| p.base_token.address.eq_ignore_ascii_case(address)));
+
+        match (best, chain, &self.gecko) {
+            (None, Some(chain), Some(gecko)) => {
+                info!("🦎 DEX Screener has no pair for {} on {}, trying GeckoTerminal", address, chain);
+                match gecko.get_token_pools(chain, address).await {
+                    Ok(pools) => Ok(most_liquid(pools
+                        .into_iter()
+                        .filter(|p| p.base_token.address.eq_ignore_ascii_case(address))
+                        .map(|pool| from_gecko(chain, pool)))),
+                    Err(e) => {
+                        warn!("GeckoTerminal lookup failed for {}: {}", address, e);
+                        Ok(None)
+                    }
+                }
+            }
+            (best, _, _) => Ok(best),
+        }
+    }
+
+    /// Save a token and its metrics
+    async fn save_token(&self, state: &Arc<AppState>, dex_token: &DexScreenerToken, source: &str) -> Result<Token> {
+        let token = self.to_token(dex_token, source);
+        let metrics = self.convert_to_metrics(dex_token).await?;
+
+        // Save the token
+        state.db.save_token(&token).await?;
+        info!("💾 Saved new token: {} ({}) on {}", token.symbol, token.name, token.chain);
+
+        if let Err(e) = state.db.save_token_metrics(&metrics).await {
+            warn!("Failed to save metrics for {}: {}", token.symbol, e);
+        }
+
+        Ok(token)
+    }
+
+    /// Convert DEX Screener data to our Token model
+    fn to_token(&self, dex_token: &DexScreenerToken, source: &str) -> Token {
+        Token {
+            id: None,
+            address: dex_token.base_token.address.clone(),
+            symbol: dex_token.base_token.symbol.clone(),
+            name: dex_token.base_token.name.clone(),
+            chain: dex_token.chain_id.clone(),
+            source: source.to_string(),
+            created_at: Utc::now(),
+            first_seen: Utc::now(),
+            is_active: true,
+            deployed_at: None,
+            deployer: None,
+            creation_checked_at: None,
+        }
+    }
+
+    async fn fetch_trending_tokens(&self, state: &AppState) -> Result<Vec<DexScreenerToken>> {
         // Use only working endpoints based on your tests
         let strategies = vec![
-            ("trending", "https://api.dexscreener.com/latest/dex/tokens/trending"),
-            ("search_sol", "https://api.dexscreener.com/latest/dex/search?q=SOL"),
-            ("search_eth", "https://api.dexscreener.com/latest/dex/search?q=ETH"),
-            ("search_bnb", "https://api.dexscreener.com/latest/dex/search?q=BNB"),
-            ("search_usdc", "https://api.dexscreener.com/latest/dex/search?q=USDC"),
-            ("search_wbtc", "https://api.dexscreener.com/latest/dex/search?q=WBTC"),
-            ("search_solana", "https://api.dexscreener.com/latest/dex/search?q=solana"),
-            ("search_ethereum", "https://api.dexscreener.com/latest/dex/search?q=ethereum"),
+            ("trending", Endpoint::Pairs("https://api.dexscreener.com/latest/dex/tokens/trending")),
+            // Freshly promoted launches the search queries tend to miss
+            ("boosted", Endpoint::TokenList("https://api.dexscreener.com/token-boosts/latest/v1")),
+            ("profiles", Endpoint::TokenList("https://api.dexscreener.com/token-profiles/latest/v1")),
+            ("search_sol", Endpoint::Pairs("https://api.dexscreener.com/latest/dex/search?q=SOL")),
+            ("search_eth", Endpoint::Pairs("https://api.dexscreener.com/latest/dex/search?q=ETH")),
+            ("search_bnb", Endpoint::Pairs("https://api.dexscreener.com/latest/dex/search?q=BNB")),
+            ("search_usdc", Endpoint::Pairs("https://api.dexscreener.com/latest/dex/search?q=USDC")),
+            ("search_wbtc", Endpoint::Pairs("https://api.dexscreener.com/latest/dex/search?q=WBTC")),
+            ("search_solana", Endpoint::Pairs("https://api.dexscreener.com/latest/dex/search?q=solana")),
+            ("search_ethereum", Endpoint::Pairs("https://api.dexscreener.com/latest/dex/search?q=ethereum")),
         ];
         
-        for (name, url) in strategies.iter() {
+        for (name, endpoint) in strategies.iter() {
             info!("🌐 Trying DEX Screener strategy: {}", name);
             
             // Add delay between requests to avoid rate limiting
             tokio::time::sleep(Duration::from_millis(1000)).await; // Increased delay
             
-            match self.try_fetch_from_endpoint_with_retry(url, 2).await { // Reduced retries
+            match self.try_fetch_from_endpoint_with_retry(state, endpoint, 2).await { // Reduced retries
                 Ok(tokens) if !tokens.is_empty() => {
                     info!("✅ Successfully fetched {} tokens using strategy: {}", tokens.len(), name);
-                    // Limit to first 10 tokens to avoid overwhelming the system
-                    return Ok(tokens.into_iter().take(10).collect());
+                    let mut tokens = tokens;
+                    self.sort_candidates(&mut tokens);
+                    return Ok(tokens);
                 }
                 Ok(_) => {
                     warn!("⚠️  Strategy {} returned no tokens, trying next...", name);
                     continue;
                 }
                 Err(e) => {
+                    // Every strategy hits the same API, so there's no point trying the rest now
+                    if e.downcast_ref::<ApiError>().is_some_and(ApiError::is_rate_limited) {
+                        warn!("🚦 DEX Screener is rate limiting us, skipping the remaining strategies this scan");
+                        break;
+                    }
                     warn!("❌ Strategy {} failed: {}, trying next...", name, e);
                     continue;
                 }
@@ -167,49 +454,94 @@ impl DexScreenerScanner {
         Ok(vec![])
     }
 
-    async fn try_fetch_from_endpoint_with_retry(&self, url: &str, retries: u32) -> Result<Vec<DexScreenerToken>> {
+    /// Order candidates by the configured discovery priority; pairs missing the field go last
+    fn sort_candidates(&self, tokens: &mut [DexScreenerToken]) {
+        match self.config.discovery_order {
+            DiscoveryOrder::Newest => {
+                tokens.sort_by_key(|t| std::cmp::Reverse(t.pair_created_at.unwrap_or(i64::MIN)));
+            }
+            DiscoveryOrder::Liquidity => {
+                let liquidity = |t: &DexScreenerToken| t.liquidity.as_ref().and_then(|l| l.usd).unwrap_or(0.0);
+                tokens.sort_by(|a, b| liquidity(b).total_cmp(&liquidity(a)));
+            }
+        }
+    }
+
+    async fn try_fetch_from_endpoint_with_retry(&self, state: &AppState, endpoint: &Endpoint, retries: u32) -> Result<Vec<DexScreenerToken>> {
+        let url = endpoint.url();
         let mut last_error = None;
         
         for attempt in 1..=retries {
-            match self.try_fetch_from_endpoint(url).await {
+            match self.try_fetch_from_endpoint(state, endpoint).await {
                 Ok(tokens) => return Ok(tokens),
                 Err(e) => {
                     warn!("Attempt {}/{} failed for {}: {}", attempt, retries, url, e);
-                    last_error = Some(e);
                     if attempt < retries {
-                        tokio::time::sleep(Duration::from_secs(2)).await;
+                        // When rate limited, the server knows best how long to back off
+                        let delay = e.downcast_ref::<ApiError>()
+                            .and_then(|api| api.retry_after)
+                            .map(|wait| wait.min(MAX_RETRY_BACKOFF))
+                            .unwrap_or_else(|| crate::utils::backoff_delay(attempt, MAX_RETRY_BACKOFF));
+                        debug!("⏳ Retrying {} in {:.1}s", url, delay.as_secs_f64());
+                        tokio::time::sleep(delay).await;
                     }
+                    last_error = Some(e);
                 }
             }
         }
         
+        // An ApiError keeps its status code so callers can tell rate limiting from other failures
         Err(last_error.unwrap_or_else(|| anyhow::anyhow!("All retries failed")))
     }
 
-    async fn try_fetch_from_endpoint(&self, url: &str) -> Result<Vec<DexScreenerToken>> {
-        let response = self.client
-            .get(url)
-            .header("Accept", "application/json")
-            .header("User-Agent", "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36")
-            .header("Referer", "https://dexscreener.com/")
-            .send()
-            .await?;
+    async fn try_fetch_from_endpoint(&self, state: &AppState, endpoint: &Endpoint) -> Result<Vec<DexScreenerToken>> {
+        let mut pairs = match endpoint {
+            Endpoint::Pairs(url) => self.fetch_pairs(url).await?,
+            Endpoint::TokenList(url) => self.fetch_listed_pairs(url).await?,
+        };
 
-        let status = response.status();
-        info!("📡 API Response Status: {}", status);
+        // Pairs created before the persisted cursor were already processed (possibly before a restart)
+        if self.config.scan_cursor_enabled {
+            if let Some(cursor) = state.db.get_scanner_cursor(SCAN_SOURCE).await? {
+                let before = pairs.len();
+                pairs.retain(|p| p.pair_created_at.is_none_or(|created| created > cursor));
+                debug!("⏭️ Skipped {} pairs already processed (cursor {})", before - pairs.len(), cursor);
+            }
+        }
 
-        if !status.is_success() {
-            return Err(anyhow::anyhow!("DEX Screener API error: {} - {}", status, 
-                match status.as_u16() {
-                    429 => "Rate limited - too many requests",
-                    403 => "Forbidden - possible geographic restriction",
-                    404 => "Endpoint not found",
-                    500..=599 => "Server error",
-                    _ => "Unknown error"
-                }));
+        // Apply filtering with more lenient criteria
+        let mut filtered_tokens = Vec::new();
+        let trading = state.trading.read().await.clone();
+        let mut rejections = state.rejections.write().await;
+
+        for token in pairs {
+            let reason = match self.should_track_token(&token, &trading) {
+                Ok(()) => {
+                    filtered_tokens.push(token);
+                    continue;
+                }
+                Err(reason) => reason,
+            };
+
+            debug!("🚫 Rejected {} ({}) on {}: {}",
+                token.base_token.symbol, token.base_token.address, token.chain_id, reason);
+            rejections.record(RejectedToken {
+                symbol: token.base_token.symbol,
+                address: token.base_token.address,
+                chain: token.chain_id,
+                reason,
+                rejected_at: Utc::now(),
+            }, self.config.rejection_log_size);
         }
 
-        let response_text = response.text().await?;
+        info!("🎯 Filtered {} quality tokens from DEX Screener", filtered_tokens.len());
+        
+        Ok(filtered_tokens)
+    }
+
+    /// Fetch and parse the raw pairs from a DEX Screener endpoint
+    async fn fetch_pairs(&self, url: &str) -> Result<Vec<DexScreenerToken>> {
+        let response_text = self.get_text(url).await?;
         info!("📡 Raw response: {}", response_text.chars().take(200).collect::<String>());
 
         // Check if response contains the null pairs issue
@@ -229,15 +561,44 @@ impl DexScreenerScanner {
 
         info!("📊 Raw pairs from API: {}", pairs.len());
 
-        // Apply filtering with more lenient criteria
-        let filtered_tokens: Vec<DexScreenerToken> = pairs
-            .into_iter()
-            .filter(|token| self.should_track_token(token))
-            .collect();
+        Ok(pairs)
+    }
 
-        info!("🎯 Filtered {} quality tokens from DEX Screener", filtered_tokens.len());
-        
-        Ok(filtered_tokens)
+    /// Fetch a token list (profiles, boosts) and look up the most liquid pair of each token on it
+    async fn fetch_listed_pairs(&self, url: &str) -> Result<Vec<DexScreenerToken>> {
+        let response_text = self.get_text(url).await?;
+        let mut listed: Vec<ListedToken> = serde_json::from_str(&response_text)
+            .map_err(|e| anyhow::anyhow!("Failed to parse JSON: {}. Response preview: {}", e, response_text.chars().take(500).collect::<String>()))?;
+
+        // A token boosted several times is listed once per boost
+        let mut seen = HashSet::new();
+        listed.retain(|t| seen.insert((t.chain_id.clone(), t.token_address.to_lowercase())));
+        info!("📋 Token list has {} tokens, looking up their pairs", listed.len());
+
+        let mut pairs = Vec::new();
+        for batch in listed.chunks(PAIR_LOOKUP_BATCH) {
+            let addresses: Vec<&str> = batch.iter().map(|t| t.token_address.as_str()).collect();
+            let url = format!("https://api.dexscreener.com/latest/dex/tokens/{}", addresses.join(","));
+
+            let mut by_token: HashMap<(Chain, String), Vec<DexScreenerToken>> = HashMap::new();
+            for pair in self.fetch_pairs(&url).await? {
+                by_token.entry((pair.chain_id.clone(), pair.base_token.address.to_lowercase())).or_default().push(pair);
+            }
+            pairs.extend(batch.iter().filter_map(|t| {
+                by_token.remove(&(t.chain_id.clone(), t.token_address.to_lowercase()))
+                    .and_then(|found| most_liquid(found.into_iter()))
+            }));
+        }
+
+        Ok(pairs)
+    }
+
+    /// GET a DEX Screener URL, keeping the status of a failed request as an `ApiError`
+    async fn get_text(&self, url: &str) -> Result<String> {
+        self.http.get_json(url).await.map_err(|e| match e.downcast::<HttpError>() {
+            Ok(HttpError { status, retry_after }) => ApiError { status, retry_after }.into(),
+            Err(e) => e,
+        })
     }
 
     // Create test tokens when API fails completely
@@ -246,7 +607,7 @@ impl DexScreenerScanner {
         
         vec![
             DexScreenerToken {
-                chain_id: "solana".to_string(),
+                chain_id: Chain::Solana,
                 dex_id: "raydium".to_string(),
                 url: "https://dexscreener.com/solana/test1".to_string(),
                 base_token: BaseToken {
@@ -267,6 +628,7 @@ impl DexScreenerScanner {
                     base: Some(333.33),
                     quote: Some(50000.0),
                 }),
+                txns: None,
                 volume: Some(Volume {
                     h24: Some(100000.0),
                     h6: Some(25000.0),
@@ -279,79 +641,310 @@ impl DexScreenerScanner {
                     h6: Some(2.0),
                     h24: Some(5.0),
                 }),
+                pair_created_at: None,
             }
         ]
     }
 
-    fn should_track_token(&self, token: &DexScreenerToken) -> bool {
+    /// Run a pair through the tracking filters, stopping at the first one it fails
+    fn should_track_token(&self, token: &DexScreenerToken, trading: &TradingConfig) -> Result<(), RejectReason> {
         // More permissive filtering to get real tokens
-        
-        // Must be on supported chains
-        let supported_chains = vec!["solana", "ethereum", "bsc", "polygon", "arbitrum", "avalanche", "pulsechain"];
-        if !supported_chains.contains(&token.chain_id.as_str()) {
-            return false;
+
+        // Must be on an enabled chain
+        if !trading.enabled_chains.iter().any(|c| c.eq_ignore_ascii_case(token.chain_id.as_str())) {
+            return Err(RejectReason::UnsupportedChain(token.chain_id.clone()));
         }
 
         // Skip obvious scam indicators
         if token.base_token.symbol.len() > 20 || token.base_token.name.len() > 50 {
-            return false;
+            return Err(RejectReason::SuspiciousName);
+        }
+
+        // Stablecoins never moon
+        if self.config.excluded_symbols.contains(&token.base_token.symbol.to_uppercase()) {
+            return Err(RejectReason::ExcludedSymbol(token.base_token.symbol.clone()));
+        }
+
+        // In new-token-only mode, established pairs are ignored (unknown age counts as old)
+        if self.config.new_tokens_only {
+            let age_hours = token.pair_created_at
+                .and_then(DateTime::from_timestamp_millis)
+                .map(|created| Utc::now().signed_duration_since(created).num_hours());
+            match age_hours {
+                Some(hours) if hours <= self.config.new_token_window_hours => {}
+                _ => return Err(RejectReason::TooOld(age_hours)),
+            }
         }
 
         // Skip if price change is too extreme (likely manipulation)
         if let Some(price_change) = &token.price_change {
             if let Some(h24) = price_change.h24 {
                 if h24.abs() > 1000.0 { // More than 10x change in 24h is suspicious
-                    return false;
+                    return Err(RejectReason::ExtremePriceMove(h24));
                 }
             }
         }
 
-        // Very permissive liquidity requirement
+        // Liquidity floor
         if let Some(liquidity) = &token.liquidity {
             if let Some(usd) = liquidity.usd {
-                if usd < 100.0 { // Minimum $100 liquidity
-                    return false;
+                if usd < trading.min_liquidity_for(token.chain_id.as_str()) {
+                    return Err(RejectReason::LowLiquidity(usd));
                 }
             }
         }
 
-        // Very permissive volume requirement  
+        // Volume floor
         if let Some(volume) = &token.volume {
             if let Some(h24) = volume.h24 {
-                if h24 < 10.0 { // Minimum $10 24h volume
-                    return false;
+                if h24 < trading.min_volume_24h_usd {
+                    return Err(RejectReason::LowVolume(h24));
                 }
             }
         }
 
-        true
+        Ok(())
     }
 
-    async fn convert_to_metrics(&self, dex_token: &DexScreenerToken) -> TokenMetrics {
-        TokenMetrics {
+    /// Cross-check the quoted price against the pool and market cap.
+    /// Returns a description of the inconsistency if the numbers don't reconcile.
+    fn check_price_consistency(&self, dex_token: &DexScreenerToken) -> Option<String> {
+        let price = dex_token.price_usd?;
+        let tolerance = 1.0 + self.config.trading.price_consistency_tolerance;
+        let liquidity = dex_token.liquidity.as_ref();
+
+        if price <= 0.0 {
+            return Some(format!("non-positive price ${}", price));
+        }
+
+        // The base side of the pool can't be worth more than the whole pool...
+        if let Some((base, usd)) = liquidity.and_then(|l| Some((l.base?, l.usd?))) {
+            let base_value = base * price;
+            if base_value > usd * tolerance {
+                return Some(format!(
+                    "base-side value ${:.0} ({} tokens × ${}) exceeds total liquidity ${:.0}",
+                    base_value, base, price, usd
+                ));
+            }
+
+            // ...nor more than the entire market cap
+            if let Some(market_cap) = dex_token.market_cap {
+                if base_value > market_cap * tolerance {
+                    return Some(format!(
+                        "pool base-side value ${:.0} exceeds market cap ${:.0}",
+                        base_value, market_cap
+                    ));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Errors if the price, market cap, liquidity or 24h volume isn't a usable number,
+    /// rather than recording the token as worthless
+    async fn convert_to_metrics(&self, dex_token: &DexScreenerToken) -> Result<TokenMetrics> {
+        let inconsistency = self.check_price_consistency(dex_token);
+        if let Some(reason) = &inconsistency {
+            warn!("❓ Inconsistent DEX Screener data for {} ({}): {}",
+                  dex_token.base_token.symbol, dex_token.base_token.address, reason);
+        }
+
+        // DEX Screener knows nothing about holders or supply; Birdeye does for Solana
+        let holders = match (&self.birdeye, &dex_token.chain_id) {
+            (Some(birdeye), Chain::Solana) => birdeye.get_holder_data(&dex_token.base_token.address).await
+                .unwrap_or_else(|e| {
+                    warn!("Failed to fetch Birdeye data for {}: {}", dex_token.base_token.symbol, e);
+                    HolderData::default()
+                }),
+            _ => HolderData::default(),
+        };
+
+        let decimal = |value: Option<f64>| value.and_then(|v| Decimal::try_from(v).ok());
+        let required = |field: &str, value: Option<f64>| value.map(f64_to_decimal).transpose()
+            .with_context(|| format!("Bad {} for {} ({})", field, dex_token.base_token.symbol, dex_token.base_token.address));
+        let volume = dex_token.volume.as_ref();
+        let price_change = dex_token.price_change.as_ref();
+
+        Ok(TokenMetrics {
             id: None,
             token_address: dex_token.base_token.address.clone(),
             timestamp: Utc::now(),
-            price_usd: dex_token.price_usd.map(|p| Decimal::try_from(p).unwrap_or(Decimal::ZERO)),
-            market_cap_usd: dex_token.market_cap.map(|mc| Decimal::try_from(mc).unwrap_or(Decimal::ZERO)),
-            liquidity_usd: dex_token.liquidity.as_ref()
-                .and_then(|l| l.usd)
-                .map(|l| Decimal::try_from(l).unwrap_or(Decimal::ZERO)),
-            volume_24h_usd: dex_token.volume.as_ref()
-                .and_then(|v| v.h24)
-                .map(|v| Decimal::try_from(v).unwrap_or(Decimal::ZERO)),
-            total_supply: None, // DEX Screener doesn't provide this
-            circulating_supply: None,
-            holder_count: None,
-            top_10_holders_percentage: None,
+            price_usd: required("price", dex_token.price_usd)?,
+            market_cap_usd: required("market cap", dex_token.market_cap)?,
+            liquidity_usd: required("liquidity", dex_token.liquidity.as_ref().and_then(|l| l.usd))?,
+            volume_24h_usd: required("24h volume", volume.and_then(|v| v.h24))?,
+            volume_5m_usd: decimal(volume.and_then(|v| v.m5)),
+            volume_1h_usd: decimal(volume.and_then(|v| v.h1)),
+            volume_6h_usd: decimal(volume.and_then(|v| v.h6)),
+            price_change_5m_pct: decimal(price_change.and_then(|p| p.m5)),
+            price_change_1h_pct: decimal(price_change.and_then(|p| p.h1)),
+            price_change_6h_pct: decimal(price_change.and_then(|p| p.h6)),
+            price_change_24h_pct: decimal(price_change.and_then(|p| p.h24)),
+            buy_count_24h: dex_token.txns.as_ref()
+                .and_then(|t| t.h24.as_ref())
+                .and_then(|w| w.buys),
+            total_supply: holders.total_supply,
+            circulating_supply: holders.circulating_supply,
+            holder_count: holders.holder_count,
+            top_10_holders_percentage: holders.top_10_holders_percentage,
             is_honeypot: None, // We'll check this with other tools
             is_mintable: None,
             has_proxy: None,
             contract_verified: None,
+            ownership_renounced: None,
+            owner_privileged: None,
+            buy_tax_pct: None,
+            sell_tax_pct: None,
+            price_consistent: dex_token.price_usd.map(|_| inconsistency.is_none()),
+        })
+    }
+}
+
+/// A non-success HTTP response from DEX Screener
+#[derive(Debug, Clone)]
+pub struct ApiError {
+    pub status: reqwest::StatusCode,
+    /// How long a 429 asked us to wait, if it said
+    pub retry_after: Option<Duration>,
+}
+
+impl ApiError {
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == reqwest::StatusCode::TOO_MANY_REQUESTS
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DEX Screener API error: {} - {}", self.status,
+            match self.status.as_u16() {
+                429 => "Rate limited - too many requests",
+                403 => "Forbidden - possible geographic restriction",
+                404 => "Endpoint not found",
+                500..=599 => "Server error",
+                _ => "Unknown error"
+            })
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+/// Why a pair was turned away by `should_track_token` (or, for its age and address, by the scan)
+#[derive(Debug, Clone, PartialEq)]
+pub enum RejectReason {
+    UnsupportedChain(Chain),
+    SuspiciousName,
+    ExcludedSymbol(String),
+    TooOld(Option<i64>), // Pair age in hours, if known
+    TooNew(i64), // Token age in minutes
+    ExtremePriceMove(f64),
+    LowLiquidity(f64),
+    LowVolume(f64),
+    InvalidAddress,
+}
+
+impl RejectReason {
+    /// Short label the rejection counts are aggregated under
+    pub fn label(&self) -> &'static str {
+        match self {
+            RejectReason::UnsupportedChain(_) => "wrong-chain",
+            RejectReason::SuspiciousName => "suspicious-name",
+            RejectReason::ExcludedSymbol(_) => "stablecoin",
+            RejectReason::TooOld(_) => "too-old",
+            RejectReason::TooNew(_) => "too-new",
+            RejectReason::ExtremePriceMove(_) => "extreme-move",
+            RejectReason::LowLiquidity(_) => "low-liq",
+            RejectReason::LowVolume(_) => "low-volume",
+            RejectReason::InvalidAddress => "bad-address",
         }
     }
 }
 
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectReason::UnsupportedChain(chain) => write!(f, "unsupported chain {}", chain),
+            RejectReason::SuspiciousName => write!(f, "suspiciously long name/symbol"),
+            RejectReason::ExcludedSymbol(symbol) => write!(f, "excluded symbol {}", symbol),
+            RejectReason::TooOld(Some(hours)) => write!(f, "pair is {}h old", hours),
+            RejectReason::TooOld(None) => write!(f, "pair age unknown"),
+            RejectReason::TooNew(minutes) => write!(f, "only {}m old", minutes),
+            RejectReason::ExtremePriceMove(change) => write!(f, "extreme 24h move {:+.0}%", change),
+            RejectReason::LowLiquidity(usd) => write!(f, "liquidity ${:.0} below minimum", usd),
+            RejectReason::LowVolume(usd) => write!(f, "24h volume ${:.0} below minimum", usd),
+            RejectReason::InvalidAddress => write!(f, "malformed address"),
+        }
+    }
+}
+
+/// A pair the filters turned away
+#[derive(Debug, Clone)]
+pub struct RejectedToken {
+    pub symbol: String,
+    pub address: String,
+    pub chain: Chain,
+    pub reason: RejectReason,
+    pub rejected_at: DateTime<Utc>,
+}
+
+/// Recently rejected pairs plus per-reason totals, since startup or (when persisted) all time
+#[derive(Debug, Default)]
+pub struct RejectionLog {
+    recent: VecDeque<RejectedToken>,
+    counts: HashMap<String, u64>,
+    /// Counted since the last `take_unsaved`
+    unsaved: HashMap<&'static str, u64>,
+}
+
+impl RejectionLog {
+    /// Count the reason and keep the pair in the recent list (newest entry per token wins)
+    pub fn record(&mut self, rejected: RejectedToken, capacity: usize) {
+        let label = rejected.reason.label();
+        *self.counts.entry(label.to_string()).or_insert(0) += 1;
+        *self.unsaved.entry(label).or_insert(0) += 1;
+
+        if capacity == 0 {
+            return;
+        }
+
+        self.recent.retain(|r| r.address != rejected.address || r.chain != rejected.chain);
+        self.recent.push_back(rejected);
+        while self.recent.len() > capacity {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Recently rejected pairs, newest first
+    pub fn recent(&self) -> impl Iterator<Item = &RejectedToken> {
+        self.recent.iter().rev()
+    }
+
+    /// Rejection counts per reason, most common first
+    pub fn counts(&self) -> Vec<(String, u64)> {
+        let mut counts: Vec<_> = self.counts.iter().map(|(label, count)| (label.clone(), *count)).collect();
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        counts
+    }
+
+    /// Every rejection counted
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// Add totals carried over from the database
+    pub fn restore(&mut self, counts: Vec<(String, u64)>) {
+        for (label, count) in counts {
+            *self.counts.entry(label).or_insert(0) += count;
+        }
+    }
+
+    /// Counts recorded since the last call, for persisting
+    pub fn take_unsaved(&mut self) -> Vec<(&'static str, u64)> {
+        self.unsaved.drain().collect()
+    }
+}
+
 // DEX Screener API Response Types - FIXED to handle null pairs
 #[derive(Debug, Deserialize)]
 struct DexScreenerResponse {
@@ -360,10 +953,19 @@ struct DexScreenerResponse {
     pairs: Option<Vec<DexScreenerToken>>, // Changed from Vec to Option<Vec>
 }
 
+/// An entry of the token-profiles / token-boosts lists: a token, not a pair
+#[derive(Debug, Deserialize)]
+struct ListedToken {
+    #[serde(rename = "chainId")]
+    chain_id: Chain,
+    #[serde(rename = "tokenAddress")]
+    token_address: String,
+}
+
 #[derive(Debug, Deserialize)]
 struct DexScreenerToken {
     #[serde(rename = "chainId")]
-    chain_id: String,
+    chain_id: Chain,
     #[serde(rename = "dexId")]
     dex_id: String,
     url: String,
@@ -378,9 +980,12 @@ struct DexScreenerToken {
     #[serde(rename = "marketCap")]
     market_cap: Option<f64>,
     liquidity: Option<Liquidity>,
+    txns: Option<Txns>,
     volume: Option<Volume>,
     #[serde(rename = "priceChange")]
     price_change: Option<PriceChange>,
+    #[serde(rename = "pairCreatedAt")]
+    pair_created_at: Option<i64>, // Unix milliseconds
 }
 
 #[derive(Debug, Deserialize)]
@@ -404,6 +1009,16 @@ struct Liquidity {
     quote: Option<f64>,
 }
 
+#[derive(Debug, Deserialize)]
+struct Txns {
+    h24: Option<TxnCounts>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TxnCounts {
+    buys: Option<u32>,
+}
+
 #[derive(Debug, Deserialize)]
 struct Volume {
     h24: Option<f64>,
@@ -420,8 +1035,164 @@ struct PriceChange {
     h24: Option<f64>,
 }
 
+/// Where the scan cursor can move: the newest processed pair that's older than every pair
+/// still waiting to be looked at again, so nothing that's waiting falls behind the cursor
+fn cursor_position(processed: &[i64], oldest_waiting: Option<i64>) -> Option<i64> {
+    processed.iter()
+        .copied()
+        .filter(|created| oldest_waiting.is_none_or(|waiting| *created < waiting))
+        .max()
+}
+
+fn most_liquid(pairs: impl Iterator<Item = DexScreenerToken>) -> Option<DexScreenerToken> {
+    pairs.max_by(|a, b| {
+        let liquidity = |t: &DexScreenerToken| t.liquidity.as_ref().and_then(|l| l.usd).unwrap_or(0.0);
+        liquidity(a).total_cmp(&liquidity(b))
+    })
+}
+
+/// A GeckoTerminal pool in DEX Screener's pair shape, so the rest of the pipeline can't tell them apart
+fn from_gecko(chain: &Chain, pool: GeckoPool) -> DexScreenerToken {
+    DexScreenerToken {
+        chain_id: chain.clone(),
+        dex_id: pool.dex_id,
+        url: pool.url,
+        base_token: BaseToken {
+            address: pool.base_token.address,
+            name: pool.base_token.name,
+            symbol: pool.base_token.symbol,
+        },
+        quote_token: QuoteToken {
+            address: pool.quote_token.address,
+            name: pool.quote_token.name,
+            symbol: pool.quote_token.symbol,
+        },
+        price_native: pool.price_native,
+        price_usd: pool.price_usd,
+        market_cap: pool.market_cap_usd,
+        liquidity: Some(Liquidity { usd: pool.liquidity_usd, base: None, quote: None }),
+        txns: Some(Txns { h24: Some(TxnCounts { buys: pool.buys_24h }) }),
+        volume: Some(Volume {
+            h24: pool.volume_usd.h24,
+            h6: pool.volume_usd.h6,
+            h1: pool.volume_usd.h1,
+            m5: pool.volume_usd.m5,
+        }),
+        price_change: Some(PriceChange {
+            m5: pool.price_change_pct.m5,
+            h1: pool.price_change_pct.h1,
+            h6: pool.price_change_pct.h6,
+            h24: pool.price_change_pct.h24,
+        }),
+        pair_created_at: pool.created_at.map(|t| t.timestamp_millis()),
+    }
+}
+
+/// Analyze a freshly saved token in the background, once one of the
+/// `max_concurrent_analyses` slots is free
+pub fn spawn_analysis(state: &Arc<AppState>, token: Token) {
+    let state = state.clone();
+    tokio::spawn(async move {
+        let _permit = match state.analysis_slots.acquire().await {
+            Ok(permit) => permit,
+            Err(_) => return, // Closed on shutdown
+        };
+        if let Err(e) = analyze_and_signal(state.clone(), token).await {
+            error!("Analysis failed: {}", e);
+        }
+    });
+}
+
 // This function will analyze tokens and generate signals
 async fn analyze_and_signal(state: Arc<AppState>, token: Token) -> Result<()> {
     // Use our token analyzer
-    crate::analyzers::token_analyzer::analyze_token(state, token).await
+    crate::analyzers::token_analyzer::analyze_token(state, token).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::test_config;
+    use serde_json::json;
+
+    /// Answers every request with the same canned body
+    struct Canned(String);
+
+    impl HttpFetch for Canned {
+        async fn get_json(&self, _url: &str) -> Result<String> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn scanner(body: &str) -> DexScreenerScanner<Canned> {
+        let mut config = test_config();
+        config.new_tokens_only = false;
+        config.excluded_symbols = vec!["USDC".to_string()];
+        config.trading.enabled_chains = vec!["solana".to_string()];
+        config.trading.min_liquidity_by_chain.clear();
+        config.trading.min_liquidity_usd = 10000.0;
+        config.trading.min_volume_24h_usd = 1000.0;
+        DexScreenerScanner::with_http(&config, Canned(body.to_string()))
+    }
+
+    fn pair(chain: &str, symbol: &str, liquidity: f64, volume: f64, change_24h: f64) -> serde_json::Value {
+        json!({
+            "chainId": chain,
+            "dexId": "raydium",
+            "url": format!("https://dexscreener.com/{}/{}", chain, symbol),
+            "baseToken": { "address": format!("{}Address", symbol), "name": symbol, "symbol": symbol },
+            "quoteToken": { "address": "So11111111111111111111111111111111111111112", "name": "Wrapped SOL", "symbol": "SOL" },
+            "priceUsd": 0.01,
+            "liquidity": { "usd": liquidity },
+            "volume": { "h24": volume },
+            "priceChange": { "h24": change_24h },
+        })
+    }
+
+    #[tokio::test]
+    async fn null_pairs_are_an_empty_result() {
+        for body in [r#"{"schemaVersion":"1.0.0","pairs":null}"#, r#"{ "schemaVersion": "1.0.0", "pairs": null }"#] {
+            let pairs = scanner(body).fetch_pairs("https://example.test").await.unwrap();
+            assert!(pairs.is_empty(), "{} should give no pairs", body);
+        }
+    }
+
+    #[tokio::test]
+    async fn tracking_filters_turn_away_the_right_pairs() {
+        let body = json!({
+            "schemaVersion": "1.0.0",
+            "pairs": [
+                pair("solana", "GOOD", 50000.0, 20000.0, 35.0),
+                pair("ethereum", "ETHY", 50000.0, 20000.0, 35.0),
+                pair("solana", "USDC", 50000.0, 20000.0, 0.1),
+                pair("solana", "PUMP", 50000.0, 20000.0, 5000.0),
+                pair("solana", "THIN", 500.0, 20000.0, 35.0),
+                pair("solana", "DEAD", 50000.0, 10.0, 35.0),
+            ],
+        });
+        let scanner = scanner(&body.to_string());
+
+        let pairs = scanner.fetch_pairs("https://example.test").await.unwrap();
+        let verdicts: Vec<_> = pairs.iter()
+            .map(|pair| scanner.should_track_token(pair, &scanner.config.trading))
+            .collect();
+
+        assert_eq!(verdicts, vec![
+            Ok(()),
+            Err(RejectReason::UnsupportedChain(Chain::Ethereum)),
+            Err(RejectReason::ExcludedSymbol("USDC".to_string())),
+            Err(RejectReason::ExtremePriceMove(5000.0)),
+            Err(RejectReason::LowLiquidity(500.0)),
+            Err(RejectReason::LowVolume(10.0)),
+        ]);
+    }
+
+    #[test]
+    fn cursor_stops_short_of_pairs_still_waiting() {
+        assert_eq!(cursor_position(&[100, 300, 200], None), Some(300));
+        assert_eq!(cursor_position(&[100, 300, 200], Some(250)), Some(200));
+        assert_eq!(cursor_position(&[100, 300], Some(100)), None);
+        assert_eq!(cursor_position(&[], Some(100)), None);
+    }
 }
@@ -0,0 +1,237 @@
+// src/scanners/geckoterminal.rs
+// Fallback pool data for tokens DEX Screener has no pairs for
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use log::info;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::models::Chain;
+
+const BASE_URL: &str = "https://api.geckoterminal.com/api/v2";
+
+pub struct GeckoTerminalClient {
+    client: Client,
+}
+
+/// One liquidity pool of a token, in the terms DEX Screener uses for a pair
+#[derive(Debug, Clone)]
+pub struct GeckoPool {
+    pub dex_id: String,
+    pub url: String,
+    pub base_token: PoolToken,
+    pub quote_token: PoolToken,
+    pub price_usd: Option<f64>,
+    pub price_native: Option<f64>,
+    pub market_cap_usd: Option<f64>,
+    pub liquidity_usd: Option<f64>,
+    pub buys_24h: Option<u32>,
+    pub volume_usd: Windows,
+    pub price_change_pct: Windows,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PoolToken {
+    pub address: String,
+    pub name: String,
+    pub symbol: String,
+}
+
+/// A figure over the last 5 minutes, 1, 6 and 24 hours
+#[derive(Debug, Clone, Default)]
+pub struct Windows {
+    pub m5: Option<f64>,
+    pub h1: Option<f64>,
+    pub h6: Option<f64>,
+    pub h24: Option<f64>,
+}
+
+impl Default for GeckoTerminalClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GeckoTerminalClient {
+    pub fn new() -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(15))
+            .user_agent("CryptoBot/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self { client }
+    }
+
+    /// The top pools of a token on a chain.
+    /// Empty for unsupported chains and tokens GeckoTerminal doesn't know.
+    pub async fn get_token_pools(&self, chain: &Chain, address: &str) -> Result<Vec<GeckoPool>> {
+        let network = match gecko_network(chain) {
+            Some(network) => network,
+            None => return Ok(Vec::new()),
+        };
+        info!("🦎 Fetching GeckoTerminal pools for {} on {}", address, chain);
+
+        let url = format!(
+            "{}/networks/{}/tokens/{}/pools?include=base_token,quote_token",
+            BASE_URL, network, address
+        );
+        let response = self.client
+            .get(&url)
+            .header("Accept", "application/json;version=20230302")
+            .send()
+            .await?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(Vec::new());
+        }
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("GeckoTerminal API error: {}", response.status()));
+        }
+
+        let body: PoolsResponse = response.json().await?;
+        let tokens: HashMap<&str, &TokenAttributes> = body.included.iter()
+            .map(|t| (t.id.as_str(), &t.attributes))
+            .collect();
+        // Relationship ids are "<network>_<address>"
+        let token = |id: &str| match tokens.get(id) {
+            Some(t) => PoolToken { address: t.address.clone(), name: t.name.clone(), symbol: t.symbol.clone() },
+            None => {
+                let address = id.strip_prefix(&format!("{}_", network)).unwrap_or(id).to_string();
+                PoolToken { name: address.clone(), symbol: address.clone(), address }
+            }
+        };
+
+        Ok(body.data.iter().map(|pool| {
+            let a = &pool.attributes;
+            GeckoPool {
+                dex_id: pool.relationships.dex.as_ref()
+                    .map(|dex| dex.data.id.clone())
+                    .unwrap_or_default(),
+                url: format!("https://www.geckoterminal.com/{}/pools/{}", network, a.address),
+                base_token: token(&pool.relationships.base_token.data.id),
+                quote_token: token(&pool.relationships.quote_token.data.id),
+                price_usd: number(&a.base_token_price_usd),
+                price_native: number(&a.base_token_price_native_currency),
+                market_cap_usd: number(&a.market_cap_usd).or(number(&a.fdv_usd)),
+                liquidity_usd: number(&a.reserve_in_usd),
+                buys_24h: a.transactions.h24.as_ref().and_then(|t| t.buys),
+                volume_usd: a.volume_usd.parsed(),
+                price_change_pct: a.price_change_percentage.parsed(),
+                created_at: a.pool_created_at.as_deref().and_then(|t| t.parse().ok()),
+            }
+        }).collect())
+    }
+}
+
+/// GeckoTerminal network ids for the chains we track
+fn gecko_network(chain: &Chain) -> Option<&'static str> {
+    match chain {
+        Chain::Solana => Some("solana"),
+        Chain::Ethereum => Some("eth"),
+        Chain::Bsc => Some("bsc"),
+        Chain::Polygon => Some("polygon_pos"),
+        Chain::Arbitrum => Some("arbitrum"),
+        Chain::Avalanche => Some("avax"),
+        Chain::Base => Some("base"),
+        Chain::Pulsechain | Chain::Other(_) => None,
+    }
+}
+
+/// GeckoTerminal sends decimals as strings
+fn number(value: &Option<String>) -> Option<f64> {
+    value.as_deref().and_then(|v| v.parse().ok())
+}
+
+// GeckoTerminal API Response Types (JSON:API)
+#[derive(Debug, Deserialize)]
+struct PoolsResponse {
+    data: Vec<PoolData>,
+    #[serde(default)]
+    included: Vec<IncludedToken>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoolData {
+    attributes: PoolAttributes,
+    relationships: Relationships,
+}
+
+#[derive(Debug, Deserialize)]
+struct PoolAttributes {
+    address: String,
+    base_token_price_usd: Option<String>,
+    base_token_price_native_currency: Option<String>,
+    fdv_usd: Option<String>,
+    market_cap_usd: Option<String>,
+    pool_created_at: Option<String>,
+    reserve_in_usd: Option<String>,
+    #[serde(default)]
+    price_change_percentage: RawWindows,
+    #[serde(default)]
+    volume_usd: RawWindows,
+    #[serde(default)]
+    transactions: Transactions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RawWindows {
+    m5: Option<String>,
+    h1: Option<String>,
+    h6: Option<String>,
+    h24: Option<String>,
+}
+
+impl RawWindows {
+    fn parsed(&self) -> Windows {
+        Windows {
+            m5: number(&self.m5),
+            h1: number(&self.h1),
+            h6: number(&self.h6),
+            h24: number(&self.h24),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Transactions {
+    h24: Option<TxnCounts>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TxnCounts {
+    buys: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Relationships {
+    base_token: Relationship,
+    quote_token: Relationship,
+    dex: Option<Relationship>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Relationship {
+    data: RelationshipData,
+}
+
+#[derive(Debug, Deserialize)]
+struct RelationshipData {
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct IncludedToken {
+    id: String,
+    attributes: TokenAttributes,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenAttributes {
+    address: String,
+    name: String,
+    symbol: String,
+}
@@ -1,3 +1,56 @@
 pub mod dex_screener;
+pub mod geckoterminal;
 pub mod pump_fun;
+pub mod twitter_monitor;
 pub mod whale_tracker;
+
+use anyhow::Result;
+use log::{debug, error, info};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+
+use crate::activity::EventKind;
+use crate::utils::{jittered, start_offset};
+use crate::AppState;
+
+/// A token source polled on an interval; `run_scanner` supplies the loop around it
+pub trait Scanner {
+    /// Name used in logs and error events, e.g. "DEX Screener"
+    fn name(&self) -> &str;
+
+    /// One pass over the source, returning how many new tokens were saved
+    async fn scan_once(&self, state: &Arc<AppState>) -> Result<usize>;
+
+    /// Time between passes, before jitter
+    fn interval(&self) -> Duration;
+}
+
+/// Scan until the bot stops: a staggered first pass, then one pass per jittered interval.
+/// Errors are logged and recorded, and the next pass goes ahead as usual.
+pub async fn run_scanner<S: Scanner>(scanner: &S, state: Arc<AppState>) -> Result<()> {
+    let name = scanner.name();
+    info!("🔍 Starting {} scanner...", name);
+    sleep(start_offset(state.config.scan_intervals.start_stagger_secs)).await;
+
+    loop {
+        match scanner.scan_once(&state).await {
+            Ok(0) => debug!("🔍 {}: No new tokens found this scan", name),
+            Ok(count) => info!("✅ {}: Found {} new tokens", name, count),
+            Err(e) => {
+                error!("❌ {} scan error: {}", name, e);
+                state.record_event(EventKind::Error, format!("{} scan error: {}", name, e)).await;
+            }
+        }
+
+        // Check if we should keep running
+        if !*state.running.read().await {
+            info!("🛑 {} scanner stopping...", name);
+            break;
+        }
+
+        sleep(jittered(scanner.interval().as_secs(), state.config.scan_intervals.jitter_pct)).await;
+    }
+
+    Ok(())
+}
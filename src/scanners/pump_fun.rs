@@ -1,25 +1,182 @@
+// src/scanners/pump_fun.rs
+// Watches the pump.fun launchpad for brand new Solana tokens,
+// usually well before they graduate to a DEX and show up on DEX Screener
+
 use anyhow::Result;
-use log::{info, warn};
+use chrono::{TimeZone, Utc};
+use log::{debug, info, error};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
 use std::sync::Arc;
+use std::time::Duration;
 
+use crate::config::Config;
+use crate::models::{Chain, Token, TokenMetrics};
+use crate::scanners::dex_screener::spawn_analysis;
+use crate::scanners::Scanner;
 use crate::AppState;
 
+/// Source name new tokens and the scan cursor are recorded under
+const SCAN_SOURCE: &str = "pump_fun";
+
+/// Newest launches first
+const NEW_COINS_URL: &str = "https://frontend-api.pump.fun/coins?offset=0&limit=50&sort=created_timestamp&order=DESC&includeNsfw=false";
+
+/// pump.fun mints all use 6 decimals
+const TOKEN_DECIMALS: u32 = 6;
+
 pub struct PumpFunScanner {
-    // Will implement pump.fun API integration
+    client: Client,
+    config: Config,
 }
 
 impl PumpFunScanner {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(config: &Config) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent("CryptoBot/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            config: config.clone(),
+        }
     }
 
-    pub async fn start_scanning(&self, _state: Arc<AppState>) -> Result<()> {
-        info!("🚀 Pump.fun scanner will be implemented in future version");
-        
-        // Placeholder for future implementation
-        // This will scan pump.fun for new token launches
-        // and analyze them for early entry opportunities
-        
-        Ok(())
+    async fn scan_new_tokens(&self, state: &Arc<AppState>) -> Result<usize> {
+        let mut coins = self.fetch_new_coins().await?;
+
+        // Launches at or before the persisted cursor were already processed (possibly before a restart)
+        if self.config.scan_cursor_enabled {
+            if let Some(cursor) = state.db.get_scanner_cursor(SCAN_SOURCE).await? {
+                coins.retain(|c| c.created_timestamp > cursor);
+            }
+        }
+
+        let newest_launch = coins.iter().map(|c| c.created_timestamp).max();
+        let mut new_tokens_count = 0;
+
+        for coin in coins {
+            if state.db.get_token(&coin.mint).await?.is_some() {
+                continue; // Skip if we already know about this token
+            }
+            if state.db.is_blacklisted(&coin.mint).await? {
+                debug!("⛔ Skipping blacklisted token {} ({})", coin.symbol, coin.mint);
+                continue;
+            }
+
+            let token = self.to_token(&coin);
+            state.db.save_token(&token).await?;
+            info!("💾 Saved new token: {} ({}) on {}", token.symbol, token.name, token.chain);
+
+            if let Err(e) = state.db.save_token_metrics(&self.convert_to_metrics(&coin)).await {
+                error!("Failed to save metrics for {}: {}", token.symbol, e);
+            }
+
+            spawn_analysis(state, token);
+            new_tokens_count += 1;
+        }
+
+        if let (true, Some(newest_launch)) = (self.config.scan_cursor_enabled, newest_launch) {
+            state.db.advance_scanner_cursor(SCAN_SOURCE, newest_launch).await?;
+        }
+
+        Ok(new_tokens_count)
     }
+
+    async fn fetch_new_coins(&self) -> Result<Vec<PumpFunCoin>> {
+        let response = self.client.get(NEW_COINS_URL).send().await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Pump.fun API error: {}", response.status()));
+        }
+
+        Ok(response.json().await?)
+    }
+
+    /// Convert a pump.fun launch to our Token model
+    fn to_token(&self, coin: &PumpFunCoin) -> Token {
+        Token {
+            id: None,
+            address: coin.mint.clone(),
+            symbol: coin.symbol.clone(),
+            name: coin.name.clone(),
+            chain: Chain::Solana,
+            source: SCAN_SOURCE.to_string(),
+            created_at: Utc.timestamp_millis_opt(coin.created_timestamp).single().unwrap_or_else(Utc::now),
+            first_seen: Utc::now(),
+            is_active: true,
+            deployed_at: None,
+            deployer: None,
+            creation_checked_at: None,
+        }
+    }
+
+    /// Bonding-curve tokens have no pool yet, so only price and market cap are known
+    fn convert_to_metrics(&self, coin: &PumpFunCoin) -> TokenMetrics {
+        let total_supply = coin.total_supply.map(|raw| Decimal::from(raw) / Decimal::from(10u64.pow(TOKEN_DECIMALS)));
+        let market_cap_usd = coin.usd_market_cap.and_then(|mc| Decimal::try_from(mc).ok());
+        let price_usd = match (market_cap_usd, total_supply) {
+            (Some(mc), Some(supply)) if !supply.is_zero() => Some(mc / supply),
+            _ => None,
+        };
+
+        TokenMetrics {
+            id: None,
+            token_address: coin.mint.clone(),
+            timestamp: Utc::now(),
+            price_usd,
+            market_cap_usd,
+            liquidity_usd: None,
+            volume_24h_usd: None,
+            volume_5m_usd: None,
+            volume_1h_usd: None,
+            volume_6h_usd: None,
+            price_change_5m_pct: None,
+            price_change_1h_pct: None,
+            price_change_6h_pct: None,
+            price_change_24h_pct: None,
+            buy_count_24h: None,
+            total_supply,
+            circulating_supply: None,
+            holder_count: None,
+            top_10_holders_percentage: None,
+            is_honeypot: None,
+            is_mintable: Some(false), // Mint authority is revoked at launch
+            has_proxy: None,
+            contract_verified: None,
+            ownership_renounced: None,
+            owner_privileged: None,
+            buy_tax_pct: None,
+            sell_tax_pct: None,
+            price_consistent: None,
+        }
+    }
+}
+
+impl Scanner for PumpFunScanner {
+    fn name(&self) -> &str {
+        "Pump.fun"
+    }
+
+    async fn scan_once(&self, state: &Arc<AppState>) -> Result<usize> {
+        self.scan_new_tokens(state).await
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.scan_intervals.pump_fun)
+    }
+}
+
+/// A coin from the pump.fun frontend API
+#[derive(Debug, Deserialize)]
+struct PumpFunCoin {
+    mint: String,
+    name: String,
+    symbol: String,
+    created_timestamp: i64, // Unix ms
+    total_supply: Option<u64>,
+    usd_market_cap: Option<f64>,
 }
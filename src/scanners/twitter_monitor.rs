@@ -0,0 +1,197 @@
+// src/scanners/twitter_monitor.rs
+// Listens to the crowd:
+// counts how often our most recent tokens are mentioned on Twitter/X
+// and raises a SocialBuzz signal when the chatter suddenly spikes
+
+use anyhow::Result;
+use chrono::Utc;
+use log::{debug, info, warn, error};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
+
+use crate::activity::EventKind;
+use crate::config::Config;
+use crate::models::{SignalType, Token, TradingSignal};
+use crate::AppState;
+
+const SEARCH_URL: &str = "https://api.twitter.com/2/tweets/search/recent";
+
+/// How many of the newest tracked tokens are searched per poll (each costs one API call)
+const TOKENS_PER_POLL: i64 = 10;
+
+/// Previous windows averaged into the baseline a new count is compared against
+const BASELINE_WINDOWS: i64 = 12;
+
+/// A window counts as a spike at this many times the baseline...
+const SPIKE_MULTIPLIER: i64 = 3;
+
+/// ...and only with at least this many mentions, so 0 -> 2 isn't "buzz"
+const MIN_SPIKE_MENTIONS: i64 = 10;
+
+/// Twitter's cap on results per search page; counts saturate here
+const MAX_RESULTS: i64 = 100;
+
+pub struct TwitterMonitor {
+    client: Client,
+    config: Config,
+    bearer_token: String,
+}
+
+impl TwitterMonitor {
+    pub fn new(config: &Config, bearer_token: &str) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent("CryptoBot/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            config: config.clone(),
+            bearer_token: bearer_token.to_string(),
+        }
+    }
+
+    pub async fn start_monitoring(&self, state: Arc<AppState>) -> Result<()> {
+        info!("🐦 Starting Twitter/X monitor...");
+        sleep(crate::utils::start_offset(self.config.scan_intervals.start_stagger_secs)).await;
+
+        loop {
+            match self.poll_mentions(&state).await {
+                Ok(0) => debug!("🐦 No social buzz this poll"),
+                Ok(count) => info!("🐦 Social buzz on {} tokens", count),
+                Err(e) => {
+                    error!("❌ Twitter monitor error: {}", e);
+                    state.record_event(EventKind::Error, format!("Twitter monitor error: {}", e)).await;
+                }
+            }
+
+            // Check if we should keep running
+            if !*state.running.read().await {
+                info!("🛑 Twitter monitor stopping...");
+                break;
+            }
+
+            sleep(crate::utils::jittered(self.config.scan_intervals.twitter_monitoring, self.config.scan_intervals.jitter_pct)).await;
+        }
+
+        Ok(())
+    }
+
+    /// Count this window's mentions of each recent token, returning how many spiked
+    async fn poll_mentions(&self, state: &Arc<AppState>) -> Result<usize> {
+        let window_secs = self.config.scan_intervals.twitter_monitoring as i64;
+        let mut spikes = 0;
+
+        for token in state.db.get_recent_tokens(TOKENS_PER_POLL).await? {
+            let query = match cashtag_query(&token.symbol) {
+                Some(query) => query,
+                None => continue,
+            };
+
+            let mentions = match self.count_mentions(&query, window_secs).await {
+                Ok(mentions) => mentions,
+                Err(e) => {
+                    warn!("Failed to count mentions of {}: {}", token.symbol, e);
+                    continue;
+                }
+            };
+
+            // Baseline from the windows before this one, read before saving it
+            let history = state.db.get_recent_social_mentions(&token.address, BASELINE_WINDOWS).await?;
+            state.db.save_social_mentions(&token.address, mentions, window_secs).await?;
+
+            if let Some(baseline) = detect_spike(mentions, &history) {
+                self.signal_buzz(state, &token, mentions, baseline).await?;
+                spikes += 1;
+            }
+        }
+
+        Ok(spikes)
+    }
+
+    /// Tweets matching `query` posted in the last `window_secs`, capped at `MAX_RESULTS`
+    async fn count_mentions(&self, query: &str, window_secs: i64) -> Result<i64> {
+        let start_time = Utc::now() - chrono::Duration::seconds(window_secs);
+        let response = self.client
+            .get(SEARCH_URL)
+            .bearer_auth(&self.bearer_token)
+            .query(&[
+                ("query", query.to_string()),
+                ("max_results", MAX_RESULTS.to_string()),
+                ("start_time", start_time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)),
+            ])
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Twitter API error: {}", response.status()));
+        }
+
+        let body: SearchResponse = response.json().await?;
+        Ok(body.meta.result_count)
+    }
+
+    async fn signal_buzz(&self, state: &Arc<AppState>, token: &Token, mentions: i64, baseline: i64) -> Result<()> {
+        let reason = format!(
+            "🐦 {} mentions of ${} in the last {}s (usually ~{})",
+            mentions, token.symbol, self.config.scan_intervals.twitter_monitoring, baseline
+        );
+
+        // The further above its baseline, the more confident we are it's real buzz
+        let confidence = Decimal::from(mentions) / Decimal::from(mentions + baseline.max(1));
+
+        let signal = TradingSignal {
+            id: None,
+            token_address: token.address.clone(),
+            signal_type: SignalType::SocialBuzz,
+            confidence: confidence.round_dp(2),
+            reason,
+            target_multiplier: None,
+            created_at: Utc::now(),
+            is_sent: false,
+        };
+        state.save_signal(&signal).await?;
+        state.record_event(EventKind::Signal, format!("Social buzz on {} ({} mentions)", token.symbol, mentions)).await;
+        info!("🐦 Social buzz on {}: {} mentions vs ~{} usual", token.symbol, mentions, baseline);
+
+        Ok(())
+    }
+}
+
+/// Search for the token's cashtag, ignoring retweets.
+/// `None` for symbols that can't be a cashtag (Twitter only allows letters, digits and _).
+fn cashtag_query(symbol: &str) -> Option<String> {
+    let valid = !symbol.is_empty()
+        && symbol.len() <= 15
+        && symbol.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !symbol.chars().all(|c| c.is_ascii_digit());
+
+    valid.then(|| format!("${} -is:retweet", symbol))
+}
+
+/// The baseline (average of previous windows) when `mentions` is a spike over it.
+/// A token without any history yet can't spike: there's nothing to compare against.
+fn detect_spike(mentions: i64, history: &[i64]) -> Option<i64> {
+    if history.is_empty() || mentions < MIN_SPIKE_MENTIONS {
+        return None;
+    }
+
+    let baseline = history.iter().sum::<i64>() / history.len() as i64;
+    (mentions >= baseline.max(1) * SPIKE_MULTIPLIER).then_some(baseline)
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    meta: SearchMeta,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchMeta {
+    #[serde(default)]
+    result_count: i64,
+}
@@ -1,25 +1,432 @@
+// src/scanners/whale_tracker.rs
+// Follows the smart money:
+// polls a block explorer for what our tracked whale wallets bought or sold,
+// records it, and raises WhaleMovement signals when they buy something we know
+// (optionally copying the buy with a simulated trade of our own)
+
 use anyhow::Result;
-use log::{info, warn};
+use chrono::{DateTime, TimeZone, Utc};
+use log::{debug, info, warn, error};
+use reqwest::Client;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashSet;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::sleep;
 
+use crate::activity::EventKind;
+use crate::analyzers::explorer::etherscan_chain_id;
+use crate::analyzers::token_analyzer;
+use crate::config::Config;
+use crate::models::{Chain, SignalType, Token, TradingSignal, TransactionAction, WhaleTransaction, WhaleWallet, WHALE_COPY_TRADE};
+use crate::scanners::dex_screener::DexScreenerScanner;
+use crate::strategies::current_metrics;
 use crate::AppState;
 
+/// How many of a whale's latest transfers are fetched (and remembered for de-duplication) per poll
+const TRANSFERS_PER_POLL: i64 = 50;
+
 pub struct WhaleTracker {
-    // Will implement whale wallet tracking
+    client: Client,
+    config: Config,
 }
 
 impl WhaleTracker {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new(config: &Config) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .user_agent("CryptoBot/1.0")
+            .build()
+            .expect("Failed to create HTTP client");
+
+        Self {
+            client,
+            config: config.clone(),
+        }
+    }
+
+    pub async fn start_tracking(&self, state: Arc<AppState>) -> Result<()> {
+        info!("🐋 Starting whale tracker...");
+        sleep(crate::utils::start_offset(self.config.scan_intervals.start_stagger_secs)).await;
+
+        // Transfers from before we started watching are recorded but not signalled,
+        // so a first poll (or a restart) doesn't replay a whale's whole history as alerts
+        let watching_since = Utc::now() - chrono::Duration::seconds(self.config.scan_intervals.whale_tracking as i64);
+
+        loop {
+            match self.poll_whales(&state, watching_since).await {
+                Ok(0) => debug!("🐋 No new whale movements"),
+                Ok(count) => info!("🐋 Recorded {} new whale transactions", count),
+                Err(e) => {
+                    error!("❌ Whale tracking error: {}", e);
+                    state.record_event(EventKind::Error, format!("Whale tracking error: {}", e)).await;
+                }
+            }
+
+            // Check if we should keep running
+            if !*state.running.read().await {
+                info!("🛑 Whale tracker stopping...");
+                break;
+            }
+
+            sleep(crate::utils::jittered(self.config.scan_intervals.whale_tracking, self.config.scan_intervals.jitter_pct)).await;
+        }
+
+        Ok(())
+    }
+
+    async fn poll_whales(&self, state: &Arc<AppState>, watching_since: DateTime<Utc>) -> Result<usize> {
+        let mut recorded = 0;
+
+        for whale in state.db.get_active_whales().await? {
+            match self.poll_whale(state, &whale, watching_since).await {
+                Ok(count) => recorded += count,
+                Err(e) => warn!("Failed to poll whale {}: {}", whale.address, e),
+            }
+        }
+
+        Ok(recorded)
+    }
+
+    async fn poll_whale(&self, state: &Arc<AppState>, whale: &WhaleWallet, watching_since: DateTime<Utc>) -> Result<usize> {
+        let transfers = match self.fetch_transfers(whale).await? {
+            Some(transfers) => transfers,
+            None => return Ok(0),
+        };
+
+        let seen: HashSet<String> = state.db.get_recent_whale_transactions(&whale.address, TRANSFERS_PER_POLL * 2).await?
+            .into_iter()
+            .map(|tx| tx.transaction_hash)
+            .collect();
+
+        let mut recorded = 0;
+        // Oldest first so signals go out in the order the whale traded
+        for transfer in transfers.into_iter().rev() {
+            if seen.contains(&transfer.hash) {
+                continue;
+            }
+
+            let is_recent_buy = transfer.timestamp >= watching_since && matches!(transfer.action, TransactionAction::Buy);
+            let (token, known) = match state.db.find_token(&transfer.token_address).await? {
+                Some(token) => (token, true),
+                // A buy of an unknown token is a lead worth discovering (and a WHALE ENTRY alert of its own)
+                None if is_recent_buy => {
+                    handle_whale_entry(state, whale, &transfer.token_address).await?;
+                    match state.db.find_token(&transfer.token_address).await? {
+                        Some(token) => (token, false),
+                        None => continue,
+                    }
+                }
+                // We only keep history for tokens we track
+                None => continue,
+            };
+
+            let amount_usd = state.db.get_latest_metrics(&token.address).await?
+                .and_then(|m| m.price_usd)
+                .map(|price| price * transfer.amount_tokens);
+
+            let inserted = state.db.save_whale_transaction(&WhaleTransaction {
+                id: None,
+                whale_address: whale.address.clone(),
+                token_address: token.address.clone(),
+                transaction_hash: transfer.hash.clone(),
+                action: transfer.action.clone(),
+                amount_tokens: transfer.amount_tokens,
+                amount_usd,
+                timestamp: transfer.timestamp,
+            }).await?;
+            // Already recorded by an earlier poll that `seen` no longer reaches
+            if !inserted {
+                continue;
+            }
+            recorded += 1;
+
+            if known && is_recent_buy {
+                self.signal_known_buy(state, whale, &token, &transfer).await?;
+            }
+            if is_recent_buy && state.trading.read().await.copy_whale_trades {
+                if let Err(e) = copy_whale_buy(state, whale, &token).await {
+                    warn!("🐋 Failed to copy {}'s buy of {}: {}", whale.address, token.symbol, e);
+                }
+            }
+        }
+
+        Ok(recorded)
     }
 
-    pub async fn start_tracking(&self, _state: Arc<AppState>) -> Result<()> {
-        info!("🐋 Whale tracker will be implemented in future version");
-        
-        // Placeholder for future implementation
-        // This will track known successful whale wallets
-        // and copy their trades automatically
-        
+    /// A tracked whale buying a token we already follow
+    async fn signal_known_buy(&self, state: &Arc<AppState>, whale: &WhaleWallet, token: &Token, transfer: &Transfer) -> Result<()> {
+        let whale_name = whale.label.clone().unwrap_or_else(|| whale.address.clone());
+        let mut reason = format!("🐋 {} bought {} {} ({})", whale_name, transfer.amount_tokens.round_dp(2), token.symbol, token.name);
+        if let Some(rate) = whale.success_rate {
+            reason.push_str(&format!("\n🎯 Whale success rate: {:.0}%", rate * Decimal::from(100)));
+        }
+
+        let signal = TradingSignal {
+            id: None,
+            token_address: token.address.clone(),
+            signal_type: SignalType::WhaleMovement,
+            confidence: whale.success_rate.unwrap_or(Decimal::new(5, 1)).min(Decimal::ONE),
+            reason,
+            target_multiplier: None,
+            created_at: Utc::now(),
+            is_sent: false,
+        };
+        state.save_signal(&signal).await?;
+        state.record_event(EventKind::Signal, format!("Whale {} bought {}", whale_name, token.symbol)).await;
+        info!("🐋 Whale {} bought {}", whale_name, token.symbol);
+
         Ok(())
     }
+
+    /// The whale's latest token transfers, newest first.
+    /// `None` when its chain has no explorer configured.
+    async fn fetch_transfers(&self, whale: &WhaleWallet) -> Result<Option<Vec<Transfer>>> {
+        if whale.chain == Chain::Solana {
+            return match &self.config.solscan_api_key {
+                Some(key) => Ok(Some(self.fetch_solscan_transfers(&whale.address, key).await?)),
+                None => {
+                    debug!("No SOLSCAN_API_KEY, skipping Solana whale {}", whale.address);
+                    Ok(None)
+                }
+            };
+        }
+
+        let chain_id = match etherscan_chain_id(&whale.chain) {
+            Some(id) => id,
+            None => {
+                debug!("No explorer for chain {}, skipping whale {}", whale.chain, whale.address);
+                return Ok(None);
+            }
+        };
+        match &self.config.etherscan_api_key {
+            Some(key) => Ok(Some(self.fetch_etherscan_transfers(&whale.address, chain_id, key).await?)),
+            None => {
+                debug!("No ETHERSCAN_API_KEY, skipping {} whale {}", whale.chain, whale.address);
+                Ok(None)
+            }
+        }
+    }
+
+    async fn fetch_etherscan_transfers(&self, address: &str, chain_id: u32, api_key: &str) -> Result<Vec<Transfer>> {
+        let url = format!(
+            "https://api.etherscan.io/v2/api?chainid={}&module=account&action=tokentx&address={}&page=1&offset={}&sort=desc&apikey={}",
+            chain_id, address, TRANSFERS_PER_POLL, api_key
+        );
+        let response = self.client.get(&url).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Etherscan API error: {}", response.status()));
+        }
+
+        let body: EtherscanResponse = response.json().await?;
+        let transfers = match body.result {
+            serde_json::Value::Array(items) => items,
+            // Errors come back as status 0 with the message in `result`
+            other if body.status == "0" => return Err(anyhow::anyhow!("Etherscan error: {} ({})", body.message, other)),
+            _ => Vec::new(),
+        };
+
+        let whale = address.to_lowercase();
+        Ok(transfers.into_iter()
+            .filter_map(|item| serde_json::from_value::<EtherscanTransfer>(item).ok())
+            .filter_map(|t| {
+                let action = if t.to.to_lowercase() == whale {
+                    TransactionAction::Buy
+                } else if t.from.to_lowercase() == whale {
+                    TransactionAction::Sell
+                } else {
+                    TransactionAction::Transfer
+                };
+                Some(Transfer {
+                    hash: t.hash,
+                    token_address: t.contract_address,
+                    action,
+                    amount_tokens: scale_amount(t.value.parse().ok()?, t.token_decimal.parse().ok()?),
+                    timestamp: Utc.timestamp_opt(t.time_stamp.parse().ok()?, 0).single()?,
+                })
+            })
+            .collect())
+    }
+
+    async fn fetch_solscan_transfers(&self, address: &str, api_key: &str) -> Result<Vec<Transfer>> {
+        let url = format!(
+            "https://pro-api.solscan.io/v2.0/account/transfer?address={}&page=1&page_size={}&sort_by=block_time&sort_order=desc",
+            address, TRANSFERS_PER_POLL
+        );
+        let response = self.client.get(&url).header("token", api_key).send().await?;
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Solscan API error: {}", response.status()));
+        }
+
+        let body: SolscanResponse = response.json().await?;
+        if !body.success {
+            return Err(anyhow::anyhow!("Solscan API returned success=false"));
+        }
+
+        Ok(body.data.into_iter()
+            .filter_map(|t| {
+                Some(Transfer {
+                    hash: t.trans_id,
+                    token_address: t.token_address,
+                    action: match t.flow.as_str() {
+                        "in" => TransactionAction::Buy,
+                        "out" => TransactionAction::Sell,
+                        _ => TransactionAction::Transfer,
+                    },
+                    amount_tokens: scale_amount(Decimal::try_from(t.amount).ok()?, t.token_decimals),
+                    timestamp: Utc.timestamp_opt(t.block_time, 0).single()?,
+                })
+            })
+            .collect())
+    }
+}
+
+/// Raw on-chain amount to whole tokens
+fn scale_amount(raw: Decimal, decimals: u32) -> Decimal {
+    let mut amount = raw;
+    for _ in 0..decimals {
+        amount /= Decimal::TEN;
+    }
+    amount.normalize()
+}
+
+/// A token transfer in or out of a whale wallet, normalized across explorers
+#[derive(Debug)]
+struct Transfer {
+    hash: String,
+    token_address: String,
+    action: TransactionAction,
+    amount_tokens: Decimal,
+    timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EtherscanResponse {
+    status: String,
+    message: String,
+    result: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EtherscanTransfer {
+    hash: String,
+    from: String,
+    to: String,
+    contract_address: String,
+    value: String,
+    token_decimal: String,
+    time_stamp: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SolscanResponse {
+    success: bool,
+    #[serde(default)]
+    data: Vec<SolscanTransfer>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SolscanTransfer {
+    trans_id: String,
+    token_address: String,
+    flow: String,
+    amount: f64,
+    token_decimals: u32,
+    block_time: i64,
+}
+
+/// Mirror a whale's buy with a simulated trade at the current price, sized by the whale's
+/// success rate. Skipped when we already hold the token or it's blacklisted.
+async fn copy_whale_buy(state: &Arc<AppState>, whale: &WhaleWallet, token: &Token) -> Result<()> {
+    if state.db.is_blacklisted(&token.address).await? {
+        debug!("⛔ Not copying a whale into blacklisted {}", token.symbol);
+        return Ok(());
+    }
+    if state.db.get_active_trades().await?.iter().any(|t| t.token_address == token.address) {
+        debug!("🐋 Already holding {}, not copying the whale's buy", token.symbol);
+        return Ok(());
+    }
+
+    // Refreshed first if stale, so the copy enters at the price the whale paid (or close to it)
+    if current_metrics(state, &token.address).await?.and_then(|m| m.price_usd).is_none() {
+        warn!("🐋 No current price for {}, not copying the whale's buy", token.symbol);
+        return Ok(());
+    }
+
+    let success_rate = whale.success_rate.unwrap_or(Decimal::new(5, 1)).min(Decimal::ONE);
+    let max_investment = Decimal::try_from(state.trading.read().await.max_investment_usd).unwrap_or(Decimal::from(100));
+    let investment = (max_investment * success_rate).round_dp(2);
+    if investment <= Decimal::ZERO {
+        return Ok(());
+    }
+
+    info!("🐋 Copying whale {} into {} with ${}", whale.label.as_deref().unwrap_or(&whale.address), token.symbol, investment);
+    let entry = token_analyzer::TradeEntry {
+        investment,
+        target_multiplier: None,
+        max_hold_hours: None,
+        source: WHALE_COPY_TRADE,
+    };
+    token_analyzer::start_simulated_trade(state, token, &entry).await
+}
+
+/// A proven whale buying a token we've never seen is a lead: discover the token,
+/// analyze it and raise a WHALE ENTRY alert with the whale's track record.
+/// Returns whether an alert was raised.
+pub async fn handle_whale_entry(state: &Arc<AppState>, whale: &WhaleWallet, token_address: &str) -> Result<bool> {
+    let min_success_rate = Decimal::try_from(state.trading.read().await.whale_entry_min_success_rate).unwrap_or(Decimal::ONE);
+    let success_rate = match whale.success_rate {
+        Some(rate) if rate >= min_success_rate => rate,
+        _ => return Ok(false),
+    };
+
+    // Tokens we already track are confirmations, not leads
+    if state.db.find_token(token_address).await?.is_some() {
+        return Ok(false);
+    }
+
+    let scanner = DexScreenerScanner::new(&state.config);
+    let token = match scanner.discover_token(state, &whale.chain, token_address, "whale").await? {
+        Some(token) => token,
+        None => {
+            warn!("🐋 Whale {} bought {} but DEX Screener has no pair for it", whale.address, token_address);
+            return Ok(false);
+        }
+    };
+
+    let whale_name = whale.label.clone().unwrap_or_else(|| whale.address.clone());
+    let mut reason = format!(
+        "🐋 WHALE ENTRY: {} ({:.0}% success rate) bought {} ({})",
+        whale_name,
+        success_rate * Decimal::from(100),
+        token.symbol,
+        token.name
+    );
+    match token_analyzer::analyze_token(state.clone(), token.clone()).await? {
+        Some(result) => reason.push_str(&format!(
+            "\n📊 Score: {}/100, Risk: {:?}, Recommendation: {:?}\n🚩 Flags: {}",
+            result.score,
+            result.risk_level,
+            result.recommendation,
+            if result.flags.is_empty() { "None".to_string() } else { result.flags.join(", ") }
+        )),
+        None => reason.push_str("\n📊 Analysis unavailable"),
+    }
+
+    let signal = TradingSignal {
+        id: None,
+        token_address: token.address.clone(),
+        signal_type: SignalType::WhaleMovement,
+        confidence: success_rate.min(Decimal::ONE),
+        reason,
+        target_multiplier: None,
+        created_at: Utc::now(),
+        is_sent: false,
+    };
+    state.save_signal(&signal).await?;
+    info!("🐋 Whale entry: {} bought new token {}", whale_name, token.symbol);
+
+    Ok(true)
 }
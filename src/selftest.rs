@@ -0,0 +1,98 @@
+// src/selftest.rs
+// The "is everything wired up?" button:
+// pings every external dependency concurrently and reports pass/fail + latency
+
+use anyhow::Result;
+use reqwest::Client;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use teloxide::{prelude::*, Bot};
+use tokio::time::timeout;
+
+use crate::AppState;
+
+/// How long a single check may take before it counts as failed
+const CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of a single dependency check
+#[derive(Debug, Clone)]
+pub struct CheckResult {
+    pub name: &'static str,
+    pub passed: bool,
+    pub latency: Duration,
+    pub detail: Option<String>,
+}
+
+/// Run every dependency check concurrently
+pub async fn run_self_test(state: &Arc<AppState>, bot: &Bot) -> Vec<CheckResult> {
+    let client = Client::builder()
+        .timeout(CHECK_TIMEOUT)
+        .user_agent("CryptoBot/1.0")
+        .build()
+        .unwrap_or_default();
+
+    let (database, telegram, dex_screener, honeypot) = tokio::join!(
+        timed_check("Database", async { state.db.ping().await }),
+        timed_check("Telegram", async {
+            bot.get_me().await?;
+            Ok(())
+        }),
+        timed_check("DEX Screener", http_check(&client, "https://api.dexscreener.com/latest/dex/search?q=SOL")),
+        timed_check("Honeypot API", http_check(
+            &client,
+            "https://api.honeypot.is/v2/IsHoneypot?address=0xdAC17F958D2ee523a2206206994597C13D831ec7&chainID=1",
+        )),
+    );
+
+    vec![database, telegram, dex_screener, honeypot]
+}
+
+/// Format check results as a checklist for Telegram
+pub fn format_results(results: &[CheckResult]) -> String {
+    let passed = results.iter().filter(|r| r.passed).count();
+    let mut message = format!("🩺 Self-Test: {}/{} checks passed\n\n", passed, results.len());
+
+    for result in results {
+        message.push_str(&format!(
+            "{} {} ({}ms)",
+            if result.passed { "✅" } else { "❌" },
+            result.name,
+            result.latency.as_millis()
+        ));
+        if let Some(detail) = &result.detail {
+            message.push_str(&format!("\n   ↳ {}", detail));
+        }
+        message.push('\n');
+    }
+
+    message
+}
+
+async fn timed_check<F>(name: &'static str, check: F) -> CheckResult
+where
+    F: Future<Output = Result<()>>,
+{
+    let started = Instant::now();
+    let outcome = timeout(CHECK_TIMEOUT, check).await;
+    let latency = started.elapsed();
+
+    let (passed, detail) = match outcome {
+        Ok(Ok(())) => (true, None),
+        Ok(Err(e)) => (false, Some(e.to_string())),
+        Err(_) => (false, Some(format!("Timed out after {}s", CHECK_TIMEOUT.as_secs()))),
+    };
+
+    CheckResult { name, passed, latency, detail }
+}
+
+async fn http_check(client: &Client, url: &str) -> Result<()> {
+    let response = client.get(url).send().await?;
+    let status = response.status();
+
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("HTTP {}", status))
+    }
+}
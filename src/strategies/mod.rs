@@ -1,2 +1,63 @@
 pub mod profit_taking;
 pub mod risk_management;
+
+use anyhow::Result;
+use chrono::Utc;
+use log::warn;
+use rust_decimal::Decimal;
+
+use crate::models::{SignalType, SimulatedTrade, TokenMetrics, TradingSignal};
+use crate::scanners::dex_screener::DexScreenerScanner;
+use crate::AppState;
+
+/// Latest metrics for an open position, refreshed from DEX Screener first when the stored
+/// snapshot is older than `max_metrics_age_minutes`. `None` means there is no recent
+/// enough price to act on, so the position should be left alone this round.
+pub async fn current_metrics(state: &AppState, token_address: &str) -> Result<Option<TokenMetrics>> {
+    let latest = state.db.get_latest_metrics(token_address).await?;
+    let max_age = chrono::Duration::minutes(state.trading.read().await.max_metrics_age_minutes);
+
+    match latest {
+        Some(metrics) if Utc::now().signed_duration_since(metrics.timestamp) <= max_age => Ok(Some(metrics)),
+        _ => {
+            let token = match state.db.get_token(token_address).await? {
+                Some(token) => token,
+                None => return Ok(None),
+            };
+
+            let scanner = DexScreenerScanner::new(&state.config);
+            match scanner.refresh_metrics(state, &token).await {
+                Ok(metrics) => Ok(metrics),
+                Err(e) => {
+                    warn!("🕰️ Metrics for {} are stale and refresh failed: {}", token.symbol, e);
+                    Ok(None)
+                }
+            }
+        }
+    }
+}
+
+/// Queue a Sell signal for a trade that was just closed, so exits reach Telegram
+/// through the same signal loop as entries
+pub async fn signal_exit(state: &AppState, trade: &SimulatedTrade, exit_price: Decimal,
+                         multiplier: Decimal, profit_loss: Decimal, reason: &str) -> Result<()> {
+    let reason = format!(
+        "{}\n💵 Exit price: ${}\n📈 Multiplier: {:.2}x\n💰 P/L: ${:.2}",
+        reason, exit_price, multiplier, profit_loss
+    );
+
+    let signal = TradingSignal {
+        id: None,
+        token_address: trade.token_address.clone(),
+        signal_type: SignalType::Sell,
+        // The position is already closed; this reports a fact, not a prediction
+        confidence: Decimal::ONE,
+        reason,
+        target_multiplier: None,
+        created_at: Utc::now(),
+        is_sent: false,
+    };
+    state.save_signal(&signal).await?;
+
+    Ok(())
+}
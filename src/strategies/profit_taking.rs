@@ -3,11 +3,25 @@ use rust_decimal::Decimal;
 use log::info;
 use std::sync::Arc;
 
+use crate::activity::EventKind;
+use crate::config::TradingConfig;
 use crate::models::SimulatedTrade;
+use crate::strategies::{current_metrics, signal_exit};
 use crate::AppState;
 
+/// A sale the profit targets call for
+#[derive(Debug, Clone)]
+pub enum ProfitExit {
+    /// Sell whatever is left
+    Close(String),
+    /// Sell down to `remaining` of the original position, with `targets_hit` ladder levels taken
+    Partial { remaining: Decimal, targets_hit: u32, reason: String },
+}
+
 pub struct ProfitTakingStrategy {
     targets: Vec<Decimal>, // Profit targets (2x, 5x, 10x, etc.)
+    scale_out: Vec<Decimal>, // Share of the original position sold at each target
+    use_signal_targets: bool, // Exit at the trade's own target multiplier when it has one
 }
 
 impl ProfitTakingStrategy {
@@ -16,43 +30,139 @@ impl ProfitTakingStrategy {
             .map(|t| Decimal::try_from(t).unwrap_or(Decimal::from(2)))
             .collect();
         
-        Self { targets }
+        Self { targets, scale_out: Vec::new(), use_signal_targets: false }
+    }
+
+    /// The ladder, scale-out and signal targets from the trading config
+    pub fn from_config(trading: &TradingConfig) -> Self {
+        Self::new(trading.profit_targets.clone())
+            .with_scale_out(trading.scale_out_fractions.clone())
+            .with_signal_targets(trading.use_signal_targets)
     }
 
-    /// Check if any active trades should be closed based on current prices
+    /// Sell `fractions[i]` of the original position at the i-th target instead of everything
+    /// at once; the target after the last fraction sells whatever is left
+    pub fn with_scale_out(mut self, fractions: Vec<f64>) -> Self {
+        self.scale_out = fractions.into_iter()
+            .filter_map(|f| Decimal::try_from(f).ok())
+            .filter(|f| *f > Decimal::ZERO)
+            .collect();
+        self
+    }
+
+    /// Close trades at the target multiplier their buy signal predicted instead of the ladder
+    pub fn with_signal_targets(mut self, enabled: bool) -> Self {
+        self.use_signal_targets = enabled;
+        self
+    }
+
+    /// Check if any active trades should be (partly) sold based on current prices.
+    /// Per-trade price targets close the whole position; the multiplier ladder scales out.
     pub async fn check_profit_targets(&self, state: &Arc<AppState>) -> Result<()> {
         let active_trades = state.db.get_active_trades().await?;
 
         for trade in active_trades {
-            if let Some(current_metrics) = state.db.get_latest_metrics(&trade.token_address).await? {
-                if let Some(current_price) = current_metrics.price_usd {
-                    let multiplier = current_price / trade.entry_price;
-                    
-                    // Check if we hit any profit target
-                    for &target in &self.targets {
-                        if multiplier >= target {
-                            let profit_usd = (current_price - trade.entry_price) * trade.investment_usd / trade.entry_price;
-                            
-                            // Close the trade
-                            if let Some(trade_id) = trade.id {
-                                state.db.close_trade(
-                                    trade_id,
-                                    current_price,
-                                    profit_usd,
-                                    multiplier,
-                                    &format!("{}x target reached", target)
-                                ).await?;
-
-                                info!("🎯 Closed trade for {} at {}x profit (${:.2})", 
-                                      trade.token_address, multiplier, profit_usd);
-                            }
-                            break;
-                        }
-                    }
-                }
+            let trade_id = match trade.id {
+                Some(id) => id,
+                None => continue,
+            };
+
+            // Refreshed first if stale, so we never take profit against an old price
+            let current_price = match current_metrics(state, &trade.token_address).await? {
+                Some(metrics) => match metrics.price_usd {
+                    Some(price) => price,
+                    None => continue,
+                },
+                None => continue,
+            };
+            let multiplier = current_price / trade.entry_price;
+
+            // Manual price levels set with /target
+            let price_target = state.db.get_trade_targets(trade_id).await?
+                .into_iter()
+                .find(|&target| current_price >= target);
+            if let Some(target) = price_target {
+                close_rest(state, &trade, current_price, &format!("${} price target reached", target)).await?;
+                continue;
             }
+
+            let (remaining, reached, reason) = match self.next_exit(&trade, current_price) {
+                Some(ProfitExit::Close(reason)) => {
+                    close_rest(state, &trade, current_price, &reason).await?;
+                    continue;
+                }
+                Some(ProfitExit::Partial { remaining, targets_hit, reason }) => (remaining, targets_hit, reason),
+                None => continue,
+            };
+
+            let sold = trade.remaining_fraction - remaining;
+            let profit_usd = trade.partial_profit_loss(current_price, sold);
+            state.db.partial_close_trade(trade_id, remaining, trade.realized_profit_loss + profit_usd, reached).await?;
+
+            info!("🪜 Sold {:.0}% of {} at {:.2}x (${:.2}): {}, {:.0}% still held",
+                  sold * Decimal::from(100), trade.token_address, multiplier, profit_usd, reason, remaining * Decimal::from(100));
+            state.record_event(EventKind::TradeClosed, format!(
+                "{} partial {:.0}% at {:.2}x (${:.2}): {}", trade.token_address, sold * Decimal::from(100), multiplier, profit_usd, reason
+            )).await;
         }
 
         Ok(())
     }
+
+    /// What the signal target or the multiplier ladder would sell of `trade` at `current_price`
+    pub fn next_exit(&self, trade: &SimulatedTrade, current_price: Decimal) -> Option<ProfitExit> {
+        let multiplier = current_price / trade.entry_price;
+
+        // The signal's own target replaces the ladder for this trade
+        if let (true, Some(target)) = (self.use_signal_targets, trade.target_multiplier) {
+            return (multiplier >= target).then(|| ProfitExit::Close(format!("{}x signal target reached", target)));
+        }
+
+        // Every ladder level reached that hasn't been taken yet
+        let reached = self.targets.iter().filter(|&&target| multiplier >= target).count() as u32;
+        if reached <= trade.targets_hit {
+            return None;
+        }
+        let target = self.targets[reached as usize - 1];
+
+        // Levels past the configured fractions (or none configured) sell everything left
+        let sold: Decimal = (trade.targets_hit..reached)
+            .map(|level| self.scale_out.get(level as usize).copied().unwrap_or(Decimal::ONE))
+            .sum();
+        let remaining = (trade.remaining_fraction - sold).max(Decimal::ZERO);
+        let reason = format!("{}x target reached", target);
+
+        if remaining.is_zero() {
+            Some(ProfitExit::Close(reason))
+        } else {
+            Some(ProfitExit::Partial { remaining, targets_hit: reached, reason })
+        }
+    }
+}
+
+/// Sell whatever is left of a position and close the trade
+async fn close_rest(state: &Arc<AppState>, trade: &SimulatedTrade, current_price: Decimal, reason: &str) -> Result<()> {
+    let trade_id = match trade.id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+    let multiplier = current_price / trade.entry_price;
+    let profit_usd = trade.exit_profit_loss(current_price);
+
+    state.db.close_trade(
+        trade_id,
+        current_price,
+        profit_usd,
+        multiplier,
+        reason
+    ).await?;
+    signal_exit(state, trade, current_price, multiplier, profit_usd, reason).await?;
+
+    info!("🎯 Closed trade for {} at {}x profit (${:.2}): {}", 
+          trade.token_address, multiplier, profit_usd, reason);
+    state.record_event(EventKind::TradeClosed, format!(
+        "{} at {:.2}x (${:.2}): {}", trade.token_address, multiplier, profit_usd, reason
+    )).await;
+
+    Ok(())
 }
@@ -1,14 +1,36 @@
 use anyhow::Result;
 use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use log::{info, warn};
+use std::collections::HashSet;
 use std::sync::Arc;
-use chrono::{Utc, Duration};
+use chrono::{DateTime, Utc, Duration};
 
+use crate::activity::EventKind;
+use crate::config::{TradingConfig, VolatilityMeasure};
+use crate::models::{SignalType, SimulatedTrade, TokenMetrics, TradingSignal};
+use crate::strategies::{current_metrics, signal_exit};
+use crate::utils::price_volatility;
 use crate::AppState;
 
 pub struct RiskManagement {
     stop_loss_pct: Decimal,
     max_hold_time: Duration,
+    volatility_limit: Option<VolatilityLimit>,
+    breakeven_arm_multiplier: Option<Decimal>,
+    trailing_stop_pct: Option<Decimal>,
+    rug_liquidity_drop_pct: Option<f64>,
+    volume_spike_multiple: Option<f64>,
+}
+
+/// How far back the volume baseline reaches, and how long a token stays quiet after a warning
+const VOLUME_SPIKE_WINDOW: Duration = Duration::hours(1);
+
+/// Exit positions that turn into untradeable chop
+struct VolatilityLimit {
+    max_pct: f64,
+    window: Duration,
+    measure: VolatilityMeasure,
 }
 
 impl RiskManagement {
@@ -16,60 +38,373 @@ impl RiskManagement {
         Self {
             stop_loss_pct: Decimal::try_from(stop_loss_pct).unwrap_or(Decimal::try_from(0.5).unwrap()),
             max_hold_time: Duration::hours(max_hold_hours),
+            volatility_limit: None,
+            breakeven_arm_multiplier: None,
+            trailing_stop_pct: None,
+            rug_liquidity_drop_pct: None,
+            volume_spike_multiple: None,
         }
     }
 
+    /// Every check switched on as the trading config says
+    pub fn from_config(trading: &TradingConfig) -> Self {
+        Self::new(trading.stop_loss, trading.max_hold_hours)
+            .with_volatility_limit(trading.max_hold_volatility_pct, trading.volatility_window_minutes, trading.volatility_measure)
+            .with_breakeven_stop(trading.breakeven_arm_multiplier)
+            .with_trailing_stop(trading.trailing_stop_pct)
+            .with_rug_detection(trading.rug_liquidity_drop_pct)
+            .with_volume_spike_detection(trading.volume_spike_multiple)
+    }
+
+    /// Force-close positions whose liquidity drops more than `pct` percent between two snapshots
+    pub fn with_rug_detection(mut self, pct: Option<f64>) -> Self {
+        self.rug_liquidity_drop_pct = pct;
+        self
+    }
+
+    /// Warn when 24h volume jumps to `multiple` times its recent average
+    pub fn with_volume_spike_detection(mut self, multiple: Option<f64>) -> Self {
+        self.volume_spike_multiple = multiple;
+        self
+    }
+
+    /// Lock in gains: close once price falls `pct` (0.2 = 20%) below its peak since entry
+    pub fn with_trailing_stop(mut self, pct: Option<f64>) -> Self {
+        self.trailing_stop_pct = pct.and_then(|p| Decimal::try_from(p).ok());
+        self
+    }
+
+    /// Move the stop up to the entry price once a position reaches `arm_multiplier`,
+    /// so a winner can't turn into a loss. Any tighter stop still applies on top.
+    pub fn with_breakeven_stop(mut self, arm_multiplier: Option<f64>) -> Self {
+        self.breakeven_arm_multiplier = arm_multiplier.and_then(|m| Decimal::try_from(m).ok());
+        self
+    }
+
+    /// Also close positions whose price swings over `window_minutes` exceed `max_pct` percent
+    pub fn with_volatility_limit(mut self, max_pct: Option<f64>, window_minutes: i64, measure: VolatilityMeasure) -> Self {
+        self.volatility_limit = max_pct.map(|max_pct| VolatilityLimit {
+            max_pct,
+            window: Duration::minutes(window_minutes),
+            measure,
+        });
+        self
+    }
+
     /// Check if any trades should be closed due to losses or time limits
     pub async fn check_risk_limits(&self, state: &Arc<AppState>) -> Result<()> {
         let active_trades = state.db.get_active_trades().await?;
         let now = Utc::now();
 
-        for trade in active_trades {
-            let mut should_close = false;
-            let mut close_reason = String::new();
-
-            // Check stop loss
-            if let Some(current_metrics) = state.db.get_latest_metrics(&trade.token_address).await? {
-                if let Some(current_price) = current_metrics.price_usd {
-                    let loss_pct = (trade.entry_price - current_price) / trade.entry_price;
-                    
-                    if loss_pct >= self.stop_loss_pct {
-                        should_close = true;
-                        close_reason = format!("Stop loss triggered ({:.1}% loss)", loss_pct * Decimal::from(100));
-                    }
-                }
+        for mut trade in active_trades {
+            // Never act on a stale price
+            let current_price = match current_metrics(state, &trade.token_address).await? {
+                Some(metrics) => match metrics.price_usd {
+                    Some(price) => price,
+                    None => continue,
+                },
+                None => continue,
+            };
+
+            // Arm the break-even stop once the position is far enough in profit
+            if let (true, Some(trade_id)) = (self.arms_breakeven(&trade, current_price), trade.id) {
+                state.db.arm_breakeven(trade_id).await?;
+                trade.breakeven_armed = true;
+                info!("🛡️ Break-even stop armed for {} at {:.2}x (stop raised to entry ${})",
+                      trade.token_address, current_price / trade.entry_price, trade.entry_price);
             }
 
-            // Check time limit
-            let hold_duration = now.signed_duration_since(trade.entry_time);
-            if hold_duration > self.max_hold_time {
-                should_close = true;
-                close_reason = format!("Max hold time exceeded ({} hours)", hold_duration.num_hours());
+            let recent = match self.volatility_window() {
+                Some(window) => state.db.get_metrics_history(&trade.token_address, now - window).await?,
+                None => Vec::new(),
+            };
+
+            if let Some(reason) = self.exit_reason(&trade, current_price, &recent, now) {
+                close_position(state, &trade, current_price, &reason).await?;
             }
+        }
+
+        Ok(())
+    }
+
+    /// Whether `current_price` is far enough in profit to arm the trade's break-even stop
+    pub fn arms_breakeven(&self, trade: &SimulatedTrade, current_price: Decimal) -> bool {
+        match self.breakeven_arm_multiplier {
+            Some(arm) => !trade.breakeven_armed && current_price / trade.entry_price >= arm,
+            None => false,
+        }
+    }
+
+    /// Why `trade` should be closed at `current_price`, if it should: the hold time limit
+    /// first, then the stop loss and break-even stop, then price swings in `recent` (the
+    /// snapshots inside the volatility window, oldest first)
+    pub fn exit_reason(&self, trade: &SimulatedTrade, current_price: Decimal,
+                       recent: &[TokenMetrics], now: DateTime<Utc>) -> Option<String> {
+        self.hold_time_reason(trade, now)
+            .or_else(|| self.stop_reason(trade, current_price))
+            .or_else(|| self.volatility_reason(recent))
+    }
+
+    /// How far back `exit_reason` wants snapshots for the volatility check, if it's on
+    pub fn volatility_window(&self) -> Option<Duration> {
+        self.volatility_limit.as_ref().map(|limit| limit.window)
+    }
+
+    /// The trade's own hold time limit if it got one at entry, the global one otherwise
+    fn hold_time_reason(&self, trade: &SimulatedTrade, now: DateTime<Utc>) -> Option<String> {
+        let max_hold_time = trade.max_hold_hours.map(Duration::hours).unwrap_or(self.max_hold_time);
+        let hold_duration = now.signed_duration_since(trade.entry_time);
+        (hold_duration > max_hold_time)
+            .then(|| format!("Max hold time exceeded ({} hours)", hold_duration.num_hours()))
+    }
+
+    fn stop_reason(&self, trade: &SimulatedTrade, current_price: Decimal) -> Option<String> {
+        let loss_pct = (trade.entry_price - current_price) / trade.entry_price;
+
+        if loss_pct >= self.stop_loss_pct {
+            Some(format!("Stop loss triggered ({:.1}% loss)", loss_pct * Decimal::from(100)))
+        } else if trade.breakeven_armed && current_price <= trade.entry_price {
+            Some("Break-even stop triggered".to_string())
+        } else {
+            None
+        }
+    }
+
+    fn volatility_reason(&self, recent: &[TokenMetrics]) -> Option<String> {
+        let limit = self.volatility_limit.as_ref()?;
+        let prices: Vec<f64> = recent.iter()
+            .filter_map(|m| m.price_usd.and_then(|p| p.to_f64()))
+            .collect();
+
+        let volatility = price_volatility(&prices, limit.measure)?;
+        (volatility > limit.max_pct)
+            .then(|| format!("Excessive volatility ({:.1}% over {}m)", volatility, limit.window.num_minutes()))
+    }
+
+    /// Warn about and close positions whose liquidity was pulled since entry
+    pub async fn check_liquidity_rugs(&self, state: &Arc<AppState>) -> Result<()> {
+        if self.rug_liquidity_drop_pct.is_none() {
+            return Ok(());
+        }
+
+        for trade in state.db.get_active_trades().await? {
+            let history = state.db.get_metrics_history(&trade.token_address, trade.entry_time).await?;
+            let rug = match self.detect_liquidity_rug(&history) {
+                Some(rug) => rug,
+                None => continue,
+            };
+            // Exit at the last known price; after a rug that's the best we could hope for
+            let current_price = match history.iter().rev().find_map(|m| m.price_usd) {
+                Some(price) => price,
+                None => continue,
+            };
+
+            let signal = TradingSignal {
+                id: None,
+                token_address: trade.token_address.clone(),
+                signal_type: SignalType::Warning,
+                confidence: Decimal::ONE,
+                reason: format!("🧨 Possible rug pull: {}", rug),
+                target_multiplier: None,
+                created_at: Utc::now(),
+                is_sent: false,
+            };
+            state.save_signal(&signal).await?;
+            warn!("🧨 Liquidity pulled on held token {}: {}", trade.token_address, rug);
+
+            close_position(state, &trade, current_price, "Liquidity removed").await?;
+        }
 
-            // Close trade if needed
-            if should_close {
-                if let Some(trade_id) = trade.id {
-                    if let Some(current_metrics) = state.db.get_latest_metrics(&trade.token_address).await? {
-                        if let Some(current_price) = current_metrics.price_usd {
-                            let profit_loss = (current_price - trade.entry_price) * trade.investment_usd / trade.entry_price;
-                            let multiplier = current_price / trade.entry_price;
-
-                            state.db.close_trade(
-                                trade_id,
-                                current_price,
-                                profit_loss,
-                                multiplier,
-                                &close_reason
-                            ).await?;
-
-                            warn!("🛑 Closed trade for {}: {}", trade.token_address, close_reason);
-                        }
-                    }
+        Ok(())
+    }
+
+    /// Describes the first drop of more than `rug_liquidity_drop_pct` percent in liquidity
+    /// between consecutive snapshots (oldest first); snapshots without liquidity are skipped
+    pub fn detect_liquidity_rug(&self, history: &[TokenMetrics]) -> Option<String> {
+        let max_drop_pct = self.rug_liquidity_drop_pct?;
+        let liquidity: Vec<f64> = history.iter()
+            .filter_map(|m| m.liquidity_usd.and_then(|l| l.to_f64()))
+            .collect();
+
+        liquidity.windows(2).find_map(|pair| {
+            let (before, after) = (pair[0], pair[1]);
+            let drop_pct = if before > 0.0 { (before - after) / before * 100.0 } else { 0.0 };
+            (drop_pct > max_drop_pct).then(|| format!(
+                "liquidity fell {:.0}% (${:.0} -> ${:.0})", drop_pct, before, after
+            ))
+        })
+    }
+
+    /// Raise a warning signal for held or watched tokens whose volume suddenly spikes
+    pub async fn check_volume_spikes(&self, state: &Arc<AppState>) -> Result<()> {
+        if self.volume_spike_multiple.is_none() {
+            return Ok(());
+        }
+
+        let mut addresses: Vec<String> = state.db.get_active_trades().await?
+            .into_iter()
+            .map(|trade| trade.token_address)
+            .chain(state.db.get_watchlist().await?.into_iter().map(|entry| entry.token_address))
+            .collect();
+        let mut seen = HashSet::new();
+        addresses.retain(|address| seen.insert(address.clone()));
+
+        let now = Utc::now();
+        for address in addresses {
+            let history = state.db.get_metrics_history(&address, now - VOLUME_SPIKE_WINDOW).await?;
+            let multiple = match self.detect_volume_spike(&history) {
+                Some(multiple) => multiple,
+                None => continue,
+            };
+            // One warning per spike, not one per monitor pass
+            if let Some(last) = state.db.get_last_signal_for_token(&address).await? {
+                if matches!(last.signal_type, SignalType::Warning) && now - last.created_at < VOLUME_SPIKE_WINDOW {
+                    continue;
                 }
             }
+
+            let symbol = state.db.get_token(&address).await?
+                .map(|token| token.symbol)
+                .unwrap_or_else(|| address.clone());
+            // The bigger the jump, the less likely it's noise
+            let confidence = Decimal::try_from(1.0 - 1.0 / multiple).unwrap_or(Decimal::ZERO);
+
+            let signal = TradingSignal {
+                id: None,
+                token_address: address.clone(),
+                signal_type: SignalType::Warning,
+                confidence: confidence.round_dp(2),
+                reason: format!(
+                    "📊 Volume spike: 24h volume is {:.1}x its average over the last {} minutes",
+                    multiple, VOLUME_SPIKE_WINDOW.num_minutes()
+                ),
+                target_multiplier: None,
+                created_at: now,
+                is_sent: false,
+            };
+            state.save_signal(&signal).await?;
+            state.record_event(EventKind::Signal, format!("Volume spike on {} ({:.1}x)", symbol, multiple)).await;
+            info!("📊 Volume spike on {}: {:.1}x its recent average", symbol, multiple);
+        }
+
+        Ok(())
+    }
+
+    /// How many times the latest 24h volume exceeds the average of the snapshots before it
+    /// (oldest first), when that's at least `volume_spike_multiple`.
+    /// Snapshots without volume are skipped.
+    pub fn detect_volume_spike(&self, history: &[TokenMetrics]) -> Option<f64> {
+        let min_multiple = self.volume_spike_multiple?;
+        let volumes: Vec<f64> = history.iter()
+            .filter_map(|m| m.volume_24h_usd.and_then(|v| v.to_f64()))
+            .collect();
+
+        let (current, baseline) = volumes.split_last()?;
+        if baseline.len() < 2 {
+            return None;
+        }
+        let average = baseline.iter().sum::<f64>() / baseline.len() as f64;
+        if average <= 0.0 {
+            return None;
+        }
+
+        let multiple = current / average;
+        (multiple >= min_multiple).then_some(multiple)
+    }
+
+    /// Close trades that have fallen `trailing_stop_pct` below their peak price since entry.
+    /// When an armed break-even stop sits higher, that stop governs instead.
+    pub async fn check_trailing_stops(&self, state: &Arc<AppState>) -> Result<()> {
+        if self.trailing_stop_pct.is_none() {
+            return Ok(());
+        }
+
+        for trade in state.db.get_active_trades().await? {
+            // Never act on a stale price
+            let current_price = match current_metrics(state, &trade.token_address).await? {
+                Some(metrics) => match metrics.price_usd {
+                    Some(price) => price,
+                    None => continue,
+                },
+                None => continue,
+            };
+
+            let history = state.db.get_metrics_history(&trade.token_address, trade.entry_time).await?;
+            if let Some(reason) = self.trailing_stop_reason(&trade, &history, current_price) {
+                close_position(state, &trade, current_price, &reason).await?;
+            }
         }
 
         Ok(())
     }
+
+    /// Why the trailing stop closes `trade` at `current_price`, given the snapshots since entry
+    pub fn trailing_stop_reason(&self, trade: &SimulatedTrade, since_entry: &[TokenMetrics],
+                                current_price: Decimal) -> Option<String> {
+        let trailing_pct = self.trailing_stop_pct?;
+        let peak = since_entry.iter()
+            .filter_map(|m| m.price_usd)
+            .chain([trade.entry_price, current_price])
+            .max()
+            .unwrap_or(trade.entry_price);
+        let stop_price = peak * (Decimal::ONE - trailing_pct);
+
+        if trade.breakeven_armed && trade.entry_price >= stop_price {
+            return None;
+        }
+
+        (current_price <= stop_price)
+            .then(|| format!("Trailing stop ({:.0}% below peak)", trailing_pct * Decimal::from(100)))
+    }
+}
+
+/// Close every active trade on a token right away (e.g. once it's blacklisted), at the
+/// current price or the entry price when no recent price is available.
+/// Returns how many trades were closed.
+pub async fn close_trades_on_token(state: &Arc<AppState>, token_address: &str, reason: &str) -> Result<usize> {
+    let trades: Vec<SimulatedTrade> = state.db.get_active_trades().await?
+        .into_iter()
+        .filter(|trade| trade.token_address == token_address)
+        .collect();
+    if trades.is_empty() {
+        return Ok(0);
+    }
+
+    let price = current_metrics(state, token_address).await?.and_then(|m| m.price_usd);
+    for trade in &trades {
+        let exit_price = price.unwrap_or_else(|| {
+            warn!("No recent price for {}, closing at entry price", token_address);
+            trade.entry_price
+        });
+        close_position(state, trade, exit_price, reason).await?;
+    }
+
+    Ok(trades.len())
+}
+
+/// Sell the rest of a position at `current_price`, recording and signalling the exit
+pub async fn close_position(state: &Arc<AppState>, trade: &SimulatedTrade, current_price: Decimal, reason: &str) -> Result<()> {
+    let trade_id = match trade.id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    let profit_loss = trade.exit_profit_loss(current_price);
+    let multiplier = current_price / trade.entry_price;
+
+    state.db.close_trade(
+        trade_id,
+        current_price,
+        profit_loss,
+        multiplier,
+        reason
+    ).await?;
+    signal_exit(state, trade, current_price, multiplier, profit_loss, reason).await?;
+
+    warn!("🛑 Closed trade for {}: {}", trade.token_address, reason);
+    state.record_event(EventKind::TradeClosed, format!(
+        "{} at {:.2}x (${:.2}): {}", trade.token_address, multiplier, profit_loss, reason
+    )).await;
+
+    Ok(())
 }
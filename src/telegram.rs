@@ -1,26 +1,37 @@
 // src/telegram.rs
 use anyhow::Result;
 use log::{info, error, warn};
+use std::collections::HashMap;
 use std::sync::Arc;
 use teloxide::{
     prelude::*,
-    types::{ParseMode, ChatId},
-    Bot,
+    types::{ParseMode, ChatId, InputFile, InlineKeyboardButton, InlineKeyboardMarkup, MessageId, Recipient},
+    ApiError, Bot, RequestError,
     utils::command::BotCommands,
     dispatching::{dialogue::InMemStorage, UpdateHandler},
 };
-use tokio::time::{sleep, Duration};
+use tokio::time::{sleep, Duration, Instant};
 
+use crate::activity::EventKind;
+use crate::database::TradeOrder;
+use crate::http::HttpError;
 use crate::models::{TradingSignal, SignalType};
+use crate::notifiers::discord::DiscordNotifier;
+use crate::notifiers::format_signal;
 use crate::AppState;
 
 pub struct TelegramBot {
     bot: Bot,
+    dry_run: bool, // Log outgoing messages instead of sending them
 }
 
 impl TelegramBot {
-    pub async fn new(token: &str) -> Result<Self> {
+    pub async fn new(token: &str, dry_run: bool) -> Result<Self> {
         let bot = Bot::new(token);
+        if dry_run {
+            info!("🧪 Dry run: not connecting to Telegram");
+            return Ok(Self { bot, dry_run });
+        }
         
         // Test the bot connection
         match bot.get_me().await {
@@ -33,50 +44,102 @@ impl TelegramBot {
             }
         }
 
-        Ok(Self { bot })
+        Ok(Self { bot, dry_run })
+    }
+
+    /// Look every configured chat up, failing on a channel username that doesn't resolve.
+    /// Usernames are replaced by their numeric ids, so admin checks and delivery
+    /// records see the same chat however it was configured.
+    pub async fn resolve_chats(&self, chats: &[Recipient]) -> Result<Vec<Recipient>> {
+        if self.dry_run {
+            return Ok(chats.to_vec());
+        }
+
+        let mut resolved = Vec::with_capacity(chats.len());
+        for chat in chats {
+            match (self.bot.get_chat(chat.clone()).await, chat) {
+                (Ok(found), Recipient::ChannelUsername(name)) => {
+                    info!("✅ Telegram chat {} resolved to {}", name, found.id);
+                    resolved.push(Recipient::Id(found.id));
+                }
+                (Ok(_), Recipient::Id(_)) => resolved.push(chat.clone()),
+                (Err(e), Recipient::ChannelUsername(name)) => {
+                    return Err(anyhow::anyhow!("Telegram chat {} not found (is the bot a member of it?): {}", name, e));
+                }
+                // A user who hasn't started the bot yet isn't visible; delivery reports it if it stays that way
+                (Err(e), Recipient::Id(id)) => {
+                    warn!("⚠️  Telegram chat {} could not be checked: {}", id, e);
+                    resolved.push(chat.clone());
+                }
+            }
+        }
+
+        Ok(resolved)
     }
 
     pub async fn start(&self, state: Arc<AppState>) -> Result<()> {
         info!("🤖 Starting Telegram bot service...");
 
-        // Get chat ID from config
-        let chat_id = ChatId(state.config.telegram_chat_id);
+        // Send startup message to every configured chat; one unreachable chat doesn't stop the rest
+        for chat in &state.config.telegram_chat_ids {
+            if let Err(e) = self.send_startup_message(chat.clone()).await {
+                warn!("Failed to send startup message to chat {}: {}", chat, e);
+            }
+        }
 
-        // Send startup message
-        self.send_startup_message(chat_id).await?;
+        // Nothing is queued for delivery in a dry run, and polling would hit the API
+        if self.dry_run {
+            info!("🧪 Dry run: Telegram commands and signal delivery disabled");
+            return Ok(());
+        }
 
         // Start signal processor in background
         let state_for_signals = state.clone();
         let bot_for_signals = self.bot.clone();
         tokio::spawn(async move {
-            if let Err(e) = process_trading_signals(bot_for_signals, chat_id, state_for_signals).await {
+            if let Err(e) = process_trading_signals(bot_for_signals, state_for_signals).await {
                 error!("Signal processor error: {}", e);
             }
         });
 
-        // Create the command handler
-        let handler = Update::filter_message()
-            .filter_command::<Command>()
-            .endpoint(answer_command);
+        // Commands, plus the buttons attached to buy signals
+        let handler = dptree::entry()
+            .branch(Update::filter_message().filter_command::<Command>().endpoint(answer_command))
+            .branch(Update::filter_callback_query().endpoint(answer_callback));
 
         // Start the dispatcher
-        Dispatcher::builder(self.bot.clone(), handler)
-            .dependencies(dptree::deps![state])
+        let mut dispatcher = Dispatcher::builder(self.bot.clone(), handler)
+            .dependencies(dptree::deps![state.clone()])
             .default_handler(|upd| async move {
                 log::debug!("Unhandled update: {:?}", upd);
             })
             .error_handler(LoggingErrorHandler::with_custom_text(
                 "An error has occurred in the dispatcher",
             ))
-            .enable_ctrlc_handler()
-            .build()
-            .dispatch()
-            .await;
+            .build();
+
+        // Stop polling for updates once the bot is shutting down
+        let shutdown = dispatcher.shutdown_token();
+        tokio::spawn(async move {
+            loop {
+                sleep(Duration::from_secs(1)).await;
+                if *state.running.read().await {
+                    continue;
+                }
+                // Errors while the dispatcher hasn't started polling yet; try again
+                if let Ok(stopped) = shutdown.shutdown() {
+                    stopped.await;
+                    break;
+                }
+            }
+        });
+
+        dispatcher.dispatch().await;
 
         Ok(())
     }
 
-    async fn send_startup_message(&self, chat_id: ChatId) -> Result<()> {
+    async fn send_startup_message(&self, chat_id: Recipient) -> Result<()> {
         let message = format!(
             "🚀 *Crypto Sniper Bot Started\\!*\n\n\
              ✅ All scanners active\n\
@@ -84,10 +147,14 @@ impl TelegramBot {
              ✅ Database connected\n\n\
              🔍 Monitoring:\n\
              • DEX Screener\n\
-             • Pump\\.fun \\(coming soon\\)\n\
-             • Whale movements \\(coming soon\\)\n\n\
+             • Pump\\.fun\n\
+             • Whale movements\n\n\
              Use /help for commands"
         );
+        if self.dry_run {
+            info!("🧪 Dry run: startup message for chat {}:\n{}", chat_id, message);
+            return Ok(());
+        }
 
         self.bot
             .send_message(chat_id, message)
@@ -96,26 +163,141 @@ impl TelegramBot {
 
         Ok(())
     }
+
+    /// Post a heartbeat, or rewrite `previous` in place when given.
+    /// Returns the message to rewrite next time.
+    async fn send_heartbeat(&self, chat_id: Recipient, text: &str, previous: Option<MessageId>) -> Result<Option<MessageId>> {
+        if self.dry_run {
+            info!("🧪 Dry run: heartbeat for chat {}:\n{}", chat_id, text);
+            return Ok(None);
+        }
+
+        let text = escape_markdown_v2(text);
+        if let Some(message_id) = previous {
+            match self.bot.edit_message_text(chat_id.clone(), message_id, &text).parse_mode(ParseMode::MarkdownV2).await {
+                Ok(_) => return Ok(Some(message_id)),
+                // Deleted or too old to edit; post a fresh one instead
+                Err(e) => warn!("Failed to update heartbeat in chat {}: {}", chat_id, e),
+            }
+        }
+
+        let message = self.bot
+            .send_message(chat_id, text)
+            .parse_mode(ParseMode::MarkdownV2)
+            .await?;
+
+        Ok(Some(message.id))
+    }
+}
+
+/// Every `heartbeat_interval_minutes`, report scanner health to every chat.
+/// A healthy heartbeat rewrites the previous one; once DEX Screener hasn't completed a
+/// scan for `heartbeat_stale_minutes` a new message is posted, so the alarm notifies.
+pub async fn start_heartbeat(state: Arc<AppState>) -> Result<()> {
+    info!("💓 Starting heartbeat...");
+
+    let interval = Duration::from_secs(state.config.heartbeat_interval_minutes * 60);
+    let stale_after = chrono::Duration::minutes(state.config.heartbeat_stale_minutes);
+    let started = chrono::Utc::now();
+    let mut previous: HashMap<Recipient, MessageId> = HashMap::new();
+
+    loop {
+        sleep(interval).await;
+        if !*state.running.read().await {
+            break;
+        }
+
+        let now = chrono::Utc::now();
+        let last_scan = *state.last_successful_scan.read().await;
+        // Before the first scan completes, count from startup
+        let since_scan = now.signed_duration_since(last_scan.unwrap_or(started));
+        let stale = since_scan > stale_after;
+        let tokens_seen = state.db.count_tokens_since(now - chrono::Duration::hours(1)).await?;
+        let active_trades = state.db.get_active_trades().await?.len();
+
+        let mut message = String::new();
+        if stale {
+            message.push_str(&format!(
+                "🚨 Scanner stale: no successful scan for {}\n\n",
+                crate::utils::format_duration(since_scan)
+            ));
+            warn!("🚨 No successful DEX Screener scan for {}", crate::utils::format_duration(since_scan));
+            state.record_event(EventKind::Error, format!(
+                "Scanner stale for {}", crate::utils::format_duration(since_scan)
+            )).await;
+        }
+        message.push_str(&format!(
+            "💓 Heartbeat ({} UTC)\n\n\
+             🔍 Last scan: {}\n\
+             🆕 Tokens seen (1h): {}\n\
+             📈 Active trades: {}",
+            now.format("%Y-%m-%d %H:%M"),
+            match last_scan {
+                Some(_) => format!("{} ago", crate::utils::format_duration(since_scan)),
+                None => "none yet".to_string(),
+            },
+            tokens_seen,
+            active_trades
+        ));
+
+        for chat in &state.config.telegram_chat_ids {
+            let rewrite = if stale { None } else { previous.get(chat).copied() };
+            match state.telegram.send_heartbeat(chat.clone(), &message, rewrite).await {
+                Ok(Some(message_id)) => {
+                    previous.insert(chat.clone(), message_id);
+                }
+                Ok(None) => {}
+                Err(e) => warn!("Failed to send heartbeat to chat {}: {}", chat, e),
+            }
+        }
+    }
+
+    Ok(())
 }
 
-async fn process_trading_signals(bot: Bot, chat_id: ChatId, state: Arc<AppState>) -> Result<()> {
+/// A destination signals are delivered to; each one is tracked and retried independently
+#[derive(Debug, Clone, PartialEq)]
+enum DeliveryChannel {
+    Telegram(Recipient),
+    Discord,
+}
+
+impl DeliveryChannel {
+    fn name(&self) -> String {
+        match self {
+            DeliveryChannel::Telegram(chat_id) => format!("telegram:{}", chat_id),
+            DeliveryChannel::Discord => "discord".to_string(),
+        }
+    }
+}
+
+/// Channels every signal must be delivered to before it counts as sent
+fn delivery_channels(state: &AppState) -> Vec<DeliveryChannel> {
+    let mut channels: Vec<DeliveryChannel> = state.config.telegram_chat_ids.iter()
+        .map(|chat| DeliveryChannel::Telegram(chat.clone()))
+        .collect();
+    if state.config.discord_webhook_url.is_some() {
+        channels.push(DeliveryChannel::Discord);
+    }
+    channels
+}
+
+async fn process_trading_signals(bot: Bot, state: Arc<AppState>) -> Result<()> {
     info!("📡 Starting signal processor...");
 
+    let discord = state.config.discord_webhook_url.as_deref().map(DiscordNotifier::new);
+    if discord.is_some() {
+        info!("💬 Also delivering signals to Discord");
+    }
+
+    let sender = LiveSender { bot: &bot, discord: discord.as_ref() };
     loop {
         // Check for unsent signals
         match state.db.get_unsent_signals().await {
             Ok(signals) => {
                 for signal in signals {
-                    if let Err(e) = send_trading_signal(&bot, chat_id, &signal, &state).await {
-                        error!("Failed to send signal: {}", e);
-                        continue;
-                    }
-
-                    // Mark as sent
-                    if let Some(id) = signal.id {
-                        if let Err(e) = state.db.mark_signal_sent(id).await {
-                            warn!("Failed to mark signal as sent: {}", e);
-                        }
+                    if let Err(e) = deliver_signal(&sender, &signal, &state).await {
+                        error!("Failed to deliver signal: {}", e);
                     }
                 }
             }
@@ -129,13 +311,134 @@ async fn process_trading_signals(bot: Bot, chat_id: ChatId, state: Arc<AppState>
             break;
         }
 
-        sleep(Duration::from_secs(5)).await;
+        sleep(crate::utils::jittered(5, state.config.scan_intervals.jitter_pct)).await;
     }
 
     Ok(())
 }
 
-async fn send_trading_signal(bot: &Bot, chat_id: ChatId, signal: &TradingSignal, state: &Arc<AppState>) -> Result<()> {
+/// Sends a signal to one delivery channel
+trait SignalSender {
+    async fn send(&self, channel: &DeliveryChannel, signal: &TradingSignal, state: &Arc<AppState>) -> Result<()>;
+}
+
+/// The real channels: Telegram chats and the Discord webhook
+struct LiveSender<'a> {
+    bot: &'a Bot,
+    discord: Option<&'a DiscordNotifier>,
+}
+
+impl SignalSender for LiveSender<'_> {
+    async fn send(&self, channel: &DeliveryChannel, signal: &TradingSignal, state: &Arc<AppState>) -> Result<()> {
+        match (channel, self.discord) {
+            (DeliveryChannel::Telegram(chat_id), _) => send_trading_signal(self.bot, chat_id.clone(), signal, state).await,
+            (DeliveryChannel::Discord, Some(discord)) => send_discord_signal(discord, signal, state).await,
+            // Only listed as a channel when the webhook is configured
+            (DeliveryChannel::Discord, None) => Ok(()),
+        }
+    }
+}
+
+/// Longest wait between two send attempts of a signal on one channel
+const MAX_DELIVERY_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Deliver a signal to every channel that hasn't had it yet, and mark it sent once each
+/// channel has either succeeded or been given up on. Transient failures are retried with
+/// backoff until `signal_max_send_attempts` or the grace period runs out; permanent ones
+/// (e.g. chat not found) are given up on right away.
+async fn deliver_signal(sender: &impl SignalSender, signal: &TradingSignal, state: &Arc<AppState>) -> Result<()> {
+    let signal_id = match signal.id {
+        Some(id) => id,
+        None => return Ok(()),
+    };
+
+    // Signals below their delivery threshold are consumed without being sent
+    if !state.trading.read().await.allows_signal(&signal.signal_type, signal.confidence) {
+        info!("🔕 Skipping {:?} signal {} below confidence threshold ({:.2})",
+              signal.signal_type, signal_id, signal.confidence);
+        state.db.mark_signal_sent(signal_id).await?;
+        return Ok(());
+    }
+
+    let deliveries = state.db.get_signal_deliveries(signal_id).await?;
+    let now = chrono::Utc::now();
+    let grace = chrono::Duration::seconds(state.config.signal_delivery_grace_secs as i64);
+    let grace_expired = now.signed_duration_since(signal.created_at) > grace;
+    let mut all_finished = true;
+
+    for channel in delivery_channels(state) {
+        let name = channel.name();
+        let previous = deliveries.iter().find(|d| d.channel == name);
+        if previous.is_some_and(|d| d.is_finished()) {
+            continue;
+        }
+        // Still backing off from the last failure
+        if previous.and_then(|d| d.next_attempt_at).is_some_and(|at| at > now) {
+            all_finished = false;
+            continue;
+        }
+
+        let e = match sender.send(&channel, signal, state).await {
+            Ok(()) => {
+                state.db.record_signal_delivery(signal_id, &name, "delivered", None, None).await?;
+                continue;
+            }
+            Err(e) => e,
+        };
+        let error = e.to_string();
+        let attempts = previous.map_or(0, |d| d.attempts) + 1;
+
+        if !is_transient(&e) || attempts >= state.config.signal_max_send_attempts || grace_expired {
+            warn!("Giving up on {} delivery of signal {} after {} attempt(s): {}", name, signal_id, attempts, error);
+            state.record_event(EventKind::Error, format!(
+                "Gave up on {} delivery of signal {}: {}", name, signal_id, error
+            )).await;
+            state.db.record_signal_delivery(signal_id, &name, "abandoned", Some(&error), None).await?;
+        } else {
+            let delay = retry_after(&e).unwrap_or_else(|| crate::utils::backoff_delay(attempts, MAX_DELIVERY_BACKOFF));
+            warn!("{} delivery of signal {} failed (attempt {}), retrying in {}s: {}",
+                  name, signal_id, attempts, delay.as_secs(), error);
+            let next_attempt_at = now + chrono::Duration::from_std(delay).unwrap_or_else(|_| chrono::Duration::zero());
+            state.db.record_signal_delivery(signal_id, &name, "pending", Some(&error), Some(next_attempt_at)).await?;
+            all_finished = false;
+        }
+    }
+
+    if all_finished {
+        state.db.mark_signal_sent(signal_id).await?;
+    }
+
+    Ok(())
+}
+
+/// Whether a failed send may succeed later: flood control, network trouble and unrecognised
+/// Telegram errors are retried, while e.g. a missing chat or a blocked bot is not.
+/// Discord is retried on rate limits and server errors only.
+/// Failures outside the request itself (e.g. the database) count as transient.
+fn is_transient(e: &anyhow::Error) -> bool {
+    if let Some(http_error) = e.downcast_ref::<HttpError>() {
+        return http_error.is_transient();
+    }
+    match e.downcast_ref::<RequestError>() {
+        Some(RequestError::RetryAfter(_) | RequestError::Network(_) | RequestError::Io(_)) => true,
+        Some(RequestError::Api(api_error)) => matches!(api_error, ApiError::Unknown(_)),
+        Some(RequestError::MigrateToChatId(_) | RequestError::InvalidJson { .. }) => false,
+        None => true,
+    }
+}
+
+/// How long Telegram's flood control (or Discord's rate limit) asked us to wait
+fn retry_after(e: &anyhow::Error) -> Option<Duration> {
+    if let Some(http_error) = e.downcast_ref::<HttpError>() {
+        return http_error.retry_after;
+    }
+    match e.downcast_ref::<RequestError>() {
+        Some(RequestError::RetryAfter(delay)) => Some(*delay),
+        _ => None,
+    }
+}
+
+async fn send_trading_signal(bot: &Bot, chat_id: Recipient, signal: &TradingSignal, state: &Arc<AppState>) -> Result<()> {
     // Get token info for the signal
     let token = match state.db.get_token(&signal.token_address).await? {
         Some(token) => token,
@@ -148,17 +451,20 @@ async fn send_trading_signal(bot: &Bot, chat_id: ChatId, signal: &TradingSignal,
     // Get latest metrics
     let metrics = state.db.get_latest_metrics(&signal.token_address).await?;
 
-    let message = match signal.signal_type {
-        SignalType::Buy => format_buy_signal(&token, signal, &metrics),
-        SignalType::Sell => format_sell_signal(&token, signal, &metrics),
-        SignalType::Warning => format_warning_signal(&token, signal, &metrics),
-        SignalType::WhaleMovement => format_whale_signal(&token, signal, &metrics),
-    };
+    let mut message = format_signal(&token, signal, &metrics).text();
+    let is_buy = matches!(signal.signal_type, SignalType::Buy);
+    if is_buy {
+        message.push_str(&format!("\n\n🎮 Quick Actions:\n/details - Get full analysis\n/watch {} - Add to watchlist", token.address));
+    }
 
     // Send the message with proper escaping for MarkdownV2
-    bot.send_message(chat_id, escape_markdown_v2(&message))
-        .parse_mode(ParseMode::MarkdownV2)
-        .await?;
+    let request = bot.send_message(chat_id, escape_markdown_v2(&message))
+        .parse_mode(ParseMode::MarkdownV2);
+    if is_buy {
+        request.reply_markup(signal_buttons(&token.address)).await?;
+    } else {
+        request.await?;
+    }
 
     info!("📤 Sent {} signal for {}", 
           format!("{:?}", signal.signal_type).to_uppercase(), 
@@ -167,116 +473,86 @@ async fn send_trading_signal(bot: &Bot, chat_id: ChatId, signal: &TradingSignal,
     Ok(())
 }
 
-// Helper function to escape MarkdownV2 special characters
-fn escape_markdown_v2(text: &str) -> String {
-    text.chars()
-        .map(|c| match c {
-            '_' | '*' | '[' | ']' | '(' | ')' | '~' | '`' | '>' | '#' | '+' | '-' | '=' | '|' | '{' | '}' | '.' | '!' => {
-                format!("\\{}", c)
-            }
-            _ => c.to_string(),
-        })
-        .collect()
-}
+async fn send_discord_signal(discord: &DiscordNotifier, signal: &TradingSignal, state: &Arc<AppState>) -> Result<()> {
+    let token = match state.db.get_token(&signal.token_address).await? {
+        Some(token) => token,
+        None => {
+            warn!("Token not found for signal: {}", signal.token_address);
+            return Ok(());
+        }
+    };
+    let metrics = state.db.get_latest_metrics(&signal.token_address).await?;
 
-fn format_buy_signal(token: &crate::models::Token, signal: &TradingSignal, metrics: &Option<crate::models::TokenMetrics>) -> String {
-    let mut message = format!(
-        "🚀 BUY SIGNAL DETECTED!\n\n\
-         💎 {} ({})\n\
-         🔗 {}\n\
-         ⛓️ Chain: {}\n\
-         📍 Source: {}\n\n\
-         📊 Analysis:\n\
-         🎯 Confidence: {:.1}%\n",
-        token.name,
-        token.symbol,
-        token.address,
-        token.chain.to_uppercase(),
-        token.source.to_uppercase(),
-        signal.confidence * rust_decimal::Decimal::from(100)
-    );
+    discord.send_signal(&token, signal, &metrics).await?;
+    info!("📤 Sent {} signal for {} to Discord",
+          format!("{:?}", signal.signal_type).to_uppercase(),
+          token.symbol);
 
-    if let Some(target) = signal.target_multiplier {
-        message.push_str(&format!("🚀 Target: {}x\n", target));
-    }
+    Ok(())
+}
 
-    if let Some(metrics) = metrics {
-        message.push_str("\n💰 Market Data:\n");
-        
-        if let Some(price) = metrics.price_usd {
-            message.push_str(&format!("💵 Price: ${}\n", price));
-        }
-        
-        if let Some(liquidity) = metrics.liquidity_usd {
-            message.push_str(&format!("💧 Liquidity: ${:.0}\n", liquidity));
-        }
-        
-        if let Some(volume) = metrics.volume_24h_usd {
-            message.push_str(&format!("📈 24h Volume: ${:.0}\n", volume));
-        }
-        
-        if let Some(holders) = metrics.holder_count {
-            message.push_str(&format!("👥 Holders: {}\n", holders));
+/// Track / Ignore buttons under a buy signal, answered by `answer_callback`
+fn signal_buttons(address: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup::new([[
+        InlineKeyboardButton::callback("📌 Track", format!("track:{}", address)),
+        InlineKeyboardButton::callback("🚫 Ignore", format!("ignore:{}", address)),
+    ]])
+}
+
+async fn answer_callback(bot: Bot, query: CallbackQuery, state: Arc<AppState>) -> ResponseResult<()> {
+    let (action, address) = match query.data.as_deref().and_then(|data| data.split_once(':')) {
+        Some(parts) => parts,
+        None => {
+            bot.answer_callback_query(query.id).await?;
+            return Ok(());
         }
-    }
+    };
 
-    message.push_str(&format!("\n🧠 Reason:\n{}\n", signal.reason));
-    message.push_str(&format!("\n⏰ Detected: {}", signal.created_at.format("%H:%M:%S UTC")));
-    
-    message.push_str("\n\n🎮 Quick Actions:\n/details - Get full analysis\n/track - Add to watchlist");
+    let confirmation = match action {
+        "track" => match state.db.add_to_watchlist(address, None).await {
+            Ok(true) => "📌 Added to your watchlist".to_string(),
+            Ok(false) => "📌 Already on your watchlist".to_string(),
+            Err(e) => {
+                error!("Failed to watch {}: {}", address, e);
+                "❌ Failed to update watchlist".to_string()
+            }
+        },
+        "ignore" => match state.db.set_ignored(address, true).await {
+            Ok(true) => "🚫 Ignored - no more signals for this token".to_string(),
+            Ok(false) => "❌ Token not found".to_string(),
+            Err(e) => {
+                error!("Failed to ignore {}: {}", address, e);
+                "❌ Failed to ignore token".to_string()
+            }
+        },
+        _ => {
+            warn!("Unknown callback action: {}", action);
+            "❌ Unknown action".to_string()
+        }
+    };
 
-    message
-}
+    bot.answer_callback_query(query.id).text(confirmation.clone()).await?;
 
-fn format_sell_signal(token: &crate::models::Token, signal: &TradingSignal, _metrics: &Option<crate::models::TokenMetrics>) -> String {
-    format!(
-        "💸 SELL SIGNAL\n\n\
-         📉 {} ({})\n\
-         🔗 {}\n\n\
-         ⚠️ Reason:\n{}\n\n\
-         📊 Confidence: {:.1}%\n\
-         ⏰ {}", 
-        token.name,
-        token.symbol,
-        token.address,
-        signal.reason,
-        signal.confidence * rust_decimal::Decimal::from(100),
-        signal.created_at.format("%H:%M:%S UTC")
-    )
-}
+    // Confirm under the signal and drop the buttons so they can't be pressed twice
+    if let Some(message) = &query.message {
+        let original = message.text().unwrap_or_default();
+        bot.edit_message_text(message.chat.id, message.id, format!("{}\n\n{}", original, confirmation))
+            .await?;
+    }
 
-fn format_warning_signal(token: &crate::models::Token, signal: &TradingSignal, _metrics: &Option<crate::models::TokenMetrics>) -> String {
-    format!(
-        "⚠️ WARNING ALERT\n\n\
-         🚨 {} ({})\n\
-         🔗 {}\n\n\
-         ❗ Alert:\n{}\n\n\
-         📊 Confidence: {:.1}%\n\
-         ⏰ {}", 
-        token.name,
-        token.symbol,
-        token.address,
-        signal.reason,
-        signal.confidence * rust_decimal::Decimal::from(100),
-        signal.created_at.format("%H:%M:%S UTC")
-    )
+    Ok(())
 }
 
-fn format_whale_signal(token: &crate::models::Token, signal: &TradingSignal, _metrics: &Option<crate::models::TokenMetrics>) -> String {
-    format!(
-        "🐋 WHALE MOVEMENT DETECTED\n\n\
-         💎 {} ({})\n\
-         🔗 {}\n\n\
-         🔍 Movement:\n{}\n\n\
-         📊 Confidence: {:.1}%\n\
-         ⏰ {}", 
-        token.name,
-        token.symbol,
-        token.address,
-        signal.reason,
-        signal.confidence * rust_decimal::Decimal::from(100),
-        signal.created_at.format("%H:%M:%S UTC")
-    )
+// Helper function to escape MarkdownV2 special characters
+fn escape_markdown_v2(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '_' | '*' | '[' | ']' | '(' | ')' | '~' | '`' | '>' | '#' | '+' | '-' | '=' | '|' | '{' | '}' | '.' | '!' => {
+                format!("\\{}", c)
+            }
+            _ => c.to_string(),
+        })
+        .collect()
 }
 
 #[derive(BotCommands, Clone)]
@@ -292,10 +568,84 @@ enum Command {
     Recent,
     #[command(description = "Show active trades")]
     Trades,
+    #[command(description = "Show unrealized P&L on active trades at current prices")]
+    Holdings,
     #[command(description = "Show wallet balance (simulated)")]
     Balance,
     #[command(description = "Start the bot")]
     Start,
+    #[command(description = "Check every external dependency (admin)")]
+    SelfTest,
+    #[command(description = "Reset simulated trades: /reset [all], then /reset confirm (admin)")]
+    Reset(String),
+    #[command(description = "Show the top 10 closed trades")]
+    Leaderboard,
+    #[command(description = "Show the 5 best closed trades by multiplier")]
+    Best,
+    #[command(description = "Show the 5 worst closed trades by P&L")]
+    Worst,
+    #[command(description = "Show the effective configuration (admin)")]
+    Config,
+    #[command(description = "Change a trading parameter at runtime: /set <key> <value> (admin)")]
+    Set(String),
+    #[command(rename = "metrics-raw", description = "Dump the latest stored metrics for a token")]
+    MetricsRaw(String),
+    #[command(description = "Show a sparkline of a token's recent price: /chart <address>")]
+    Chart(String),
+    #[command(description = "Show recently filtered-out tokens and why")]
+    Rejected,
+    #[command(description = "Show how often each filter rejected a token")]
+    FilterStats,
+    #[command(description = "Re-fetch a token's data from every source and re-analyze it")]
+    Refresh(String),
+    #[command(description = "Set a USD take-profit price on an active trade: /target <address> <price>")]
+    Target(String),
+    #[command(description = "Show the last N bot events: /log [n] (admin)")]
+    Log(String),
+    #[command(description = "Estimate net return after fees: /netreturn <address> <usd> <target_multiplier>")]
+    NetReturn(String),
+    #[command(description = "Show realized P&L per token")]
+    Pnl,
+    #[command(description = "Download every closed trade as a CSV file")]
+    Export,
+    #[command(description = "Analyze any token on demand: /analyze <address>")]
+    Analyze(String),
+    #[command(description = "Add a token to your watchlist: /watch <address> [note]")]
+    Watch(String),
+    #[command(description = "Remove a token from your watchlist: /unwatch <address>")]
+    Unwatch(String),
+    #[command(description = "Show your watchlist with current prices")]
+    Watchlist,
+    #[command(description = "Stop signals for a token but keep tracking its price: /ignore <address>")]
+    Ignore(String),
+    #[command(description = "Resume signals for an ignored token: /unignore <address>")]
+    Unignore(String),
+    #[command(description = "Never track a token or deployer again and close its trades: /blacklist <address> [reason] (admin)")]
+    Blacklist(String),
+    #[command(description = "Take an address off the blacklist: /unblacklist <address> (admin)")]
+    Unblacklist(String),
+}
+
+/// How long a /reset request waits for its confirmation
+const RESET_CONFIRM_WINDOW: Duration = Duration::from_secs(60);
+
+/// A /reset waiting for "/reset confirm"
+pub struct PendingReset {
+    requested_at: Instant,
+    include_signals: bool,
+}
+
+/// Most events a single /log reply may contain
+const MAX_LOG_EVENTS: usize = 50;
+
+/// Most tokens a single /pnl reply lists
+const MAX_PNL_TOKENS: usize = 30;
+
+const ADMIN_ONLY: &str = "⛔ This command is restricted to the bot admin";
+
+/// Admin commands are only accepted from the first configured chat
+fn is_admin(state: &AppState, chat_id: ChatId) -> bool {
+    state.config.telegram_chat_ids.first() == Some(&Recipient::Id(chat_id))
 }
 
 async fn answer_command(bot: Bot, msg: Message, cmd: Command, state: Arc<AppState>) -> ResponseResult<()> {
@@ -315,11 +665,38 @@ async fn answer_command(bot: Bot, msg: Message, cmd: Command, state: Arc<AppStat
              /stats - Trading performance stats\n\
              /recent - Recently discovered tokens\n\
              /trades - Active simulated trades\n\
+             /holdings - Unrealized P&L on active trades\n\
              /balance - Current simulated balance\n\
+             /leaderboard - Best closed trades\n\
+             /best - Top 5 trades by multiplier\n\
+             /worst - Bottom 5 trades by P&L\n\
+             /pnl - Realized P&L per token\n\
+             /export - Closed trades as CSV\n\
+             /metrics-raw <address> - Raw stored metrics\n\
+             /chart <address> - Recent price sparkline\n\
+             /rejected - Recently filtered-out tokens\n\
+             /filterstats - Rejection counts per filter\n\
+             /refresh <address> - Re-fetch data and re-analyze\n\
+             /analyze <address> - Analyze any token now\n\
+             /watch <address> [note] - Add to watchlist\n\
+             /unwatch <address> - Remove from watchlist\n\
+             /watchlist - Watched tokens and prices\n\
+             /ignore <address> - No more signals for a token\n\
+             /unignore <address> - Resume signals for a token\n\
+             /target <address> <price> - Take profit at a USD price\n\
+             /netreturn <address> <usd> <x> - Net return after fees\n\
+             /selftest - Check external dependencies (admin)\n\
+             /config - Show effective configuration (admin)\n\
+             /set <key> <value> - Change a trading parameter (admin)\n\
+             /reset [all] - Reset simulated trades (admin)\n\
+             /blacklist <address> [reason] - Never track it again (admin)\n\
+             /unblacklist <address> - Remove from blacklist (admin)\n\
+             /log [n] - Recent bot activity (admin)\n\
              /help - Show this help message\n\n\
              🔥 The bot automatically scans for tokens and sends signals!".to_string()
         }
         Command::Status => {
+            let filtered = filtered_summary(&*state.rejections.read().await);
             match state.db.get_trading_stats().await {
                 Ok(stats) => format!(
                     "✅ Bot Status: ACTIVE\n\n\
@@ -330,14 +707,16 @@ async fn answer_command(bot: Bot, msg: Message, cmd: Command, state: Arc<AppStat
                      📏 Avg Multiplier: {:.2}x\n\n\
                      🔍 Scanners:\n\
                      ✅ DEX Screener\n\
-                     🔄 Pump.fun (coming soon)\n\
-                     🔄 Whale Tracker (coming soon)",
+                     ✅ Pump.fun\n\
+                     ✅ Whale Tracker\n\n\
+                     {}",
                     stats.total_trades,
                     stats.win_rate,
                     stats.total_profit_usd,
-                    stats.avg_multiplier
+                    stats.avg_multiplier,
+                    filtered
                 ),
-                Err(_) => "✅ Bot Status: ACTIVE\n\n📊 Stats loading...".to_string(),
+                Err(_) => format!("✅ Bot Status: ACTIVE\n\n📊 Stats loading...\n\n{}", filtered),
             }
         }
         Command::Stats => {
@@ -408,13 +787,28 @@ async fn answer_command(bot: Bot, msg: Message, cmd: Command, state: Arc<AppStat
                         let mut response = "📈 Active Trades:\n\n".to_string();
                         for (i, trade) in trades.iter().enumerate() {
                             if let Some(token) = state.db.get_token(&trade.token_address).await.unwrap_or(None) {
+                                let snapshot_age = state.db.get_latest_metrics(&trade.token_address).await
+                                    .unwrap_or(None)
+                                    .map(|m| crate::utils::format_duration(chrono::Utc::now().signed_duration_since(m.timestamp)))
+                                    .unwrap_or_else(|| "?".to_string());
+                                let scaled_out = if trade.remaining_fraction < rust_decimal::Decimal::ONE {
+                                    format!("   🪜 Holding {:.0}% (${:.2} realized)\n",
+                                            trade.remaining_fraction * rust_decimal::Decimal::from(100), trade.realized_profit_loss)
+                                } else {
+                                    String::new()
+                                };
+                                // Analyzer trades are the norm; anything else says where it came from
+                                let origin = if trade.source == crate::models::WHALE_COPY_TRADE { " 🐋 copy trade" } else { "" };
                                 response.push_str(&format!(
-                                    "{}. {}\n   💵 Entry: ${}\n   💰 Investment: ${}\n   ⏰ {}\n\n",
+                                    "{}. {}{}\n   💵 Entry: ${}\n   💰 Investment: ${}\n{}   ⏰ {}\n   🕰️ Price data age: {}\n\n",
                                     i + 1,
                                     token.symbol,
+                                    origin,
                                     trade.entry_price,
                                     trade.investment_usd,
-                                    trade.entry_time.format("%H:%M UTC")
+                                    scaled_out,
+                                    trade.entry_time.format("%H:%M UTC"),
+                                    snapshot_age
                                 ));
                             }
                         }
@@ -428,33 +822,260 @@ async fn answer_command(bot: Bot, msg: Message, cmd: Command, state: Arc<AppStat
             }
         }
         Command::Balance => {
-            match state.db.get_trading_stats().await {
-                Ok(stats) => {
-                    let starting_balance = 1000.0;
-                    let current_balance = starting_balance + stats.total_profit_usd;
+            match (state.db.get_trading_stats().await, state.db.get_active_trades().await) {
+                (Ok(stats), Ok(active_trades)) => {
+                    use rust_decimal::prelude::ToPrimitive;
+
+                    let starting_balance = state.config.starting_balance_usd;
+                    // Scaled-out positions have already returned part of their stake and banked its P&L
+                    let invested: f64 = active_trades.iter()
+                        .filter_map(|t| (t.investment_usd * t.remaining_fraction).to_f64())
+                        .sum();
+                    let realized_open: f64 = active_trades.iter()
+                        .filter_map(|t| t.realized_profit_loss.to_f64())
+                        .sum();
+                    let realized = stats.total_profit_usd + realized_open;
+                    let current_balance = starting_balance + realized;
                     
                     format!(
                         "💰 Simulated Balance\n\n\
                          💵 Current Balance: ${:.2}\n\
                          📊 Starting Balance: ${:.2}\n\
-                         📈 Total P&L: ${:.2}\n\
+                         📈 Realized P&L: ${:.2}\n\
                          📏 ROI: {:.1}%\n\n\
-                         ⚡ Active Trades: ${:.2} invested\n\
+                         ⚡ Active Trades: {} (${:.2} invested)\n\
                          💎 Available: ${:.2}",
                         current_balance,
                         starting_balance,
-                        stats.total_profit_usd,
-                        (stats.total_profit_usd / starting_balance) * 100.0,
-                        stats.total_trades as f64 * 100.0,
-                        current_balance - (stats.total_trades as f64 * 100.0)
+                        realized,
+                        (realized / starting_balance) * 100.0,
+                        active_trades.len(),
+                        invested,
+                        current_balance - invested
                     )
                 }
-                Err(e) => {
+                (Err(e), _) | (_, Err(e)) => {
                     error!("Failed to get balance: {}", e);
                     "❌ Failed to load balance information".to_string()
                 }
             }
         }
+        Command::SelfTest => {
+            if !is_admin(&state, chat_id) {
+                ADMIN_ONLY.to_string()
+            } else {
+                let results = crate::selftest::run_self_test(&state, &bot).await;
+                crate::selftest::format_results(&results)
+            }
+        }
+        Command::Leaderboard => {
+            match state.db.get_top_trades(10, TradeOrder::BestMultiplier).await {
+                Ok(trades) if trades.is_empty() => "🏆 No closed trades yet - the leaderboard is empty".to_string(),
+                Ok(trades) => format_ranked_trades("🏆 Trade Leaderboard", &trades),
+                Err(e) => {
+                    error!("Failed to get top trades: {}", e);
+                    "❌ Failed to load leaderboard".to_string()
+                }
+            }
+        }
+        Command::Best => {
+            match state.db.get_top_trades(5, TradeOrder::BestMultiplier).await {
+                Ok(trades) if trades.is_empty() => "📭 No closed trades yet".to_string(),
+                Ok(trades) => format_ranked_trades("🚀 Best Trades (by multiplier)", &trades),
+                Err(e) => {
+                    error!("Failed to get best trades: {}", e);
+                    "❌ Failed to load trades".to_string()
+                }
+            }
+        }
+        Command::Worst => {
+            match state.db.get_top_trades(5, TradeOrder::WorstProfitLoss).await {
+                Ok(trades) if trades.is_empty() => "📭 No closed trades yet".to_string(),
+                Ok(trades) => format_ranked_trades("💀 Worst Trades (by P&L)", &trades),
+                Err(e) => {
+                    error!("Failed to get worst trades: {}", e);
+                    "❌ Failed to load trades".to_string()
+                }
+            }
+        }
+        Command::Pnl => {
+            match state.db.get_closed_trades_grouped().await {
+                Ok(tokens) => {
+                    if tokens.is_empty() {
+                        "📭 No closed trades yet".to_string()
+                    } else {
+                        let mut response = "💹 Realized P&L by Token\n\n".to_string();
+                        for (symbol, pnl) in tokens.iter().take(MAX_PNL_TOKENS) {
+                            let emoji = if pnl.realized_profit_usd > 0.0 { "🟢" } else { "🔴" };
+                            response.push_str(&format!(
+                                "{} {}: ${:.2}\n   💵 Invested: ${:.2}\n   ✅ {} won / ❌ {} lost\n\n",
+                                emoji,
+                                symbol,
+                                pnl.realized_profit_usd,
+                                pnl.invested_usd,
+                                pnl.wins,
+                                pnl.losses
+                            ));
+                        }
+                        if tokens.len() > MAX_PNL_TOKENS {
+                            response.push_str(&format!("…and {} more tokens", tokens.len() - MAX_PNL_TOKENS));
+                        }
+                        response
+                    }
+                }
+                Err(e) => {
+                    error!("Failed to get per-token P&L: {}", e);
+                    "❌ Failed to load P&L".to_string()
+                }
+            }
+        }
+        Command::Export => match send_trades_csv(&bot, chat_id, &state).await {
+            // The file itself is the reply
+            Ok(None) => return Ok(()),
+            Ok(Some(message)) => message,
+            Err(e) => {
+                error!("Failed to export trades: {}", e);
+                "❌ Failed to export trades".to_string()
+            }
+        },
+        Command::Config => {
+            if !is_admin(&state, chat_id) {
+                ADMIN_ONLY.to_string()
+            } else {
+                // Trading parameters as currently in effect, including /set changes
+                let mut config = state.config.clone();
+                config.trading = state.trading.read().await.clone();
+                config.redacted_view()
+            }
+        }
+        Command::Set(args) => {
+            if !is_admin(&state, chat_id) {
+                ADMIN_ONLY.to_string()
+            } else {
+                handle_set(&state, &args).await
+            }
+        }
+        Command::MetricsRaw(address) => {
+            let address = address.trim();
+            if address.is_empty() {
+                "Usage: /metrics-raw <token address>".to_string()
+            } else {
+                match state.db.get_latest_metrics(address).await {
+                    Ok(Some(metrics)) => format_raw_metrics(&metrics),
+                    Ok(None) => format!("📭 No metrics stored for {}", address),
+                    Err(e) => {
+                        error!("Failed to get metrics for {}: {}", address, e);
+                        "❌ Failed to load metrics".to_string()
+                    }
+                }
+            }
+        }
+        Command::Chart(address) => handle_chart(&state, address.trim()).await,
+        Command::Rejected => {
+            let rejections = state.rejections.read().await;
+            let mut recent = rejections.recent().take(15).peekable();
+            if recent.peek().is_none() {
+                "🚫 No tokens have been filtered out yet".to_string()
+            } else {
+                let mut response = "🚫 Recently Rejected Tokens:\n\n".to_string();
+                for (i, rejected) in recent.enumerate() {
+                    response.push_str(&format!(
+                        "{}. {} on {}\n   🔗 {}\n   ❌ {}\n   ⏰ {}\n\n",
+                        i + 1,
+                        rejected.symbol,
+                        rejected.chain,
+                        rejected.address,
+                        rejected.reason,
+                        rejected.rejected_at.format("%H:%M UTC")
+                    ));
+                }
+                response
+            }
+        }
+        Command::FilterStats => {
+            let counts = state.rejections.read().await.counts();
+            if counts.is_empty() {
+                "📊 No tokens have been filtered out yet".to_string()
+            } else {
+                let total: u64 = counts.iter().map(|(_, count)| count).sum();
+                let since = if state.config.persist_rejection_counts { "all time" } else { "since startup" };
+                let mut response = format!("📊 Filter Rejections ({}): {}\n\n", since, total);
+                for (label, count) in counts {
+                    response.push_str(&format!("• {}: {}\n", label, count));
+                }
+                response
+            }
+        }
+        Command::Refresh(address) => {
+            let address = address.trim();
+            // Rate-limited per user, falling back to the chat for anonymous senders
+            let user_key = msg.from().map(|user| user.id.0.to_string()).unwrap_or_else(|| chat_id.to_string());
+
+            if address.is_empty() {
+                "Usage: /refresh <token address>".to_string()
+            } else if !state.refresh_limiter.lock().await.check_rate_limit(&user_key).await {
+                format!("⏳ Refresh limit reached ({} per hour), try again later", state.config.refresh_rate_limit_per_hour)
+            } else {
+                handle_refresh(&state, address).await
+            }
+        }
+        Command::Target(args) => handle_target(&state, &args).await,
+        Command::NetReturn(args) => handle_net_return(&state, &args).await,
+        Command::Analyze(address) => {
+            let address = address.trim();
+            // Shares the /refresh budget: both hit the external APIs on demand
+            let user_key = msg.from().map(|user| user.id.0.to_string()).unwrap_or_else(|| chat_id.to_string());
+
+            if address.is_empty() {
+                "Usage: /analyze <token address>".to_string()
+            } else if !crate::utils::is_valid_solana_address(address) && !crate::utils::is_valid_ethereum_address(address) {
+                "❌ That doesn't look like a token address.\n\
+                 Send a Solana address (32-44 base58 characters) or an EVM address (0x followed by 40 hex digits)".to_string()
+            } else if !state.refresh_limiter.lock().await.check_rate_limit(&user_key).await {
+                format!("⏳ Analysis limit reached ({} per hour), try again later", state.config.refresh_rate_limit_per_hour)
+            } else {
+                handle_analyze(&state, address).await
+            }
+        }
+        Command::Watch(args) => {
+            // Adding an unknown token hits DEX Screener, so it shares the /refresh budget
+            let user_key = msg.from().map(|user| user.id.0.to_string()).unwrap_or_else(|| chat_id.to_string());
+            handle_watch(&state, &args, &user_key).await
+        }
+        Command::Unwatch(address) => handle_unwatch(&state, address.trim()).await,
+        Command::Watchlist => handle_watchlist(&state).await,
+        Command::Holdings => handle_holdings(&state).await,
+        Command::Ignore(address) => handle_ignore(&state, address.trim(), true).await,
+        Command::Unignore(address) => handle_ignore(&state, address.trim(), false).await,
+        Command::Blacklist(args) => {
+            if !is_admin(&state, chat_id) {
+                ADMIN_ONLY.to_string()
+            } else {
+                handle_blacklist(&state, &args).await
+            }
+        }
+        Command::Unblacklist(address) => {
+            if !is_admin(&state, chat_id) {
+                ADMIN_ONLY.to_string()
+            } else {
+                handle_unblacklist(&state, address.trim()).await
+            }
+        }
+        Command::Log(args) => {
+            if !is_admin(&state, chat_id) {
+                ADMIN_ONLY.to_string()
+            } else {
+                let n = args.trim().parse().unwrap_or(10).clamp(1, MAX_LOG_EVENTS);
+                crate::activity::format_events(state.activity.read().await.recent(n))
+            }
+        }
+        Command::Reset(args) => {
+            if !is_admin(&state, chat_id) {
+                ADMIN_ONLY.to_string()
+            } else {
+                handle_reset(&state, args.trim()).await
+            }
+        }
     };
 
     bot.send_message(chat_id, escape_markdown_v2(&response))
@@ -463,3 +1084,672 @@ async fn answer_command(bot: Bot, msg: Message, cmd: Command, state: Arc<AppStat
 
     Ok(())
 }
+
+/// Send every closed trade as a CSV document. Returns a message to reply with instead
+/// when there is nothing to export.
+async fn send_trades_csv(bot: &Bot, chat_id: ChatId, state: &AppState) -> Result<Option<String>> {
+    let trades = state.db.get_all_closed_trades().await?;
+    if trades.is_empty() {
+        return Ok(Some("📭 No closed trades to export yet".to_string()));
+    }
+
+    let csv = crate::utils::trades_to_csv(&trades);
+    let file_name = format!("trades_{}.csv", chrono::Utc::now().format("%Y%m%d_%H%M%S"));
+    bot.send_document(chat_id, InputFile::memory(csv.into_bytes()).file_name(file_name))
+        .caption(format!("📤 {} closed trades", trades.len()))
+        .await?;
+
+    Ok(None)
+}
+
+/// Numbered closed trades with entry/exit, multiplier, P&L and holding time
+fn format_ranked_trades(title: &str, trades: &[(String, crate::models::SimulatedTrade)]) -> String {
+    let mut response = format!("{}\n\n", title);
+    for (i, (symbol, trade)) in trades.iter().enumerate() {
+        let held = trade.exit_time
+            .map(|exit| crate::utils::format_duration(exit.signed_duration_since(trade.entry_time)))
+            .unwrap_or_else(|| "?".to_string());
+        response.push_str(&format!(
+            "{}. {} - {:.2}x\n   💵 ${} → ${}\n   💰 P&L: ${:.2}\n   ⏱️ Held {}\n\n",
+            i + 1,
+            symbol,
+            trade.multiplier.unwrap_or_default(),
+            trade.entry_price,
+            trade.exit_price.map(|p| p.to_string()).unwrap_or_else(|| "?".to_string()),
+            trade.profit_loss.unwrap_or_default(),
+            held
+        ));
+    }
+    response
+}
+
+/// Every stored metrics field, including the empty ones
+fn format_raw_metrics(metrics: &crate::models::TokenMetrics) -> String {
+    let fields = match serde_json::to_value(metrics) {
+        Ok(serde_json::Value::Object(fields)) => fields,
+        _ => return "❌ Failed to render metrics".to_string(),
+    };
+
+    let mut response = format!(
+        "🧾 Raw metrics (snapshot age {})\n\n",
+        crate::utils::format_duration(chrono::Utc::now().signed_duration_since(metrics.timestamp))
+    );
+    for (field, value) in fields {
+        let value = match value {
+            serde_json::Value::Null => "None".to_string(),
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        };
+        response.push_str(&format!("{}: {}\n", field, value));
+    }
+
+    response
+}
+
+/// Most price points a /chart sparkline covers
+const CHART_POINTS: usize = 50;
+
+async fn handle_chart(state: &AppState, address: &str) -> String {
+    use rust_decimal::prelude::ToPrimitive;
+
+    if address.is_empty() {
+        return "Usage: /chart <token address>".to_string();
+    }
+
+    let token = match state.db.find_token(address).await {
+        Ok(Some(token)) => token,
+        Ok(None) => return format!("📭 Unknown token {}", address),
+        Err(e) => {
+            error!("Failed to look up {}: {}", address, e);
+            return "❌ Failed to load token".to_string();
+        }
+    };
+    let history = match state.db.get_metrics_history(&token.address, chrono::Utc::now() - chrono::Duration::days(7)).await {
+        Ok(history) => history,
+        Err(e) => {
+            error!("Failed to get metrics history for {}: {}", token.address, e);
+            return "❌ Failed to load price history".to_string();
+        }
+    };
+
+    let prices: Vec<rust_decimal::Decimal> = history.iter().filter_map(|m| m.price_usd).collect();
+    let prices = &prices[prices.len().saturating_sub(CHART_POINTS)..];
+    let (min, max, current) = match (prices.iter().min(), prices.iter().max(), prices.last()) {
+        (Some(min), Some(max), Some(current)) => (min, max, current),
+        _ => return format!("📭 No price history stored for {}", token.symbol),
+    };
+    let points: Vec<f64> = prices.iter().filter_map(|p| p.to_f64()).collect();
+
+    format!(
+        "📈 {} - last {} prices\n\n{}\n\n🔻 Min: {}\n🔺 Max: {}\n💵 Now: {}",
+        token.symbol,
+        prices.len(),
+        crate::utils::sparkline(&points),
+        crate::utils::format_price(*min),
+        crate::utils::format_price(*max),
+        crate::utils::format_price(*current)
+    )
+}
+
+async fn handle_refresh(state: &Arc<AppState>, address: &str) -> String {
+    let token = match state.db.get_token(address).await {
+        Ok(Some(token)) => token,
+        Ok(None) => return format!("📭 {} is not a tracked token", address),
+        Err(e) => {
+            error!("Failed to get token {}: {}", address, e);
+            return "❌ Failed to load token".to_string();
+        }
+    };
+
+    match crate::analyzers::token_analyzer::refresh_token(state, &token).await {
+        Ok(Some(result)) => format_analysis(&format!("🔄 Refreshed {} ({})", token.name, token.symbol), &result),
+        Ok(None) => format!("⚠️ Could not refresh {}: no current market data or analysis failed", token.symbol),
+        Err(e) => {
+            error!("Failed to refresh {}: {}", token.symbol, e);
+            "❌ Failed to refresh token".to_string()
+        }
+    }
+}
+
+async fn handle_analyze(state: &Arc<AppState>, address: &str) -> String {
+    let token = match state.db.find_token(address).await {
+        Ok(Some(token)) => token,
+        Ok(None) => {
+            // Not seen yet: pull it from DEX Screener first
+            let scanner = crate::scanners::dex_screener::DexScreenerScanner::new(&state.config);
+            match scanner.discover_token_by_address(state, address, "telegram").await {
+                Ok(Some(token)) => token,
+                Ok(None) => return format!("📭 DEX Screener has no trading pair for {}", address),
+                Err(e) => {
+                    error!("Failed to fetch token {}: {}", address, e);
+                    return "❌ Failed to fetch token from DEX Screener".to_string();
+                }
+            }
+        }
+        Err(e) => {
+            error!("Failed to get token {}: {}", address, e);
+            return "❌ Failed to load token".to_string();
+        }
+    };
+
+    let analyzer = crate::analyzers::token_analyzer::TokenAnalyzer::new(state).await;
+    match analyzer.analyze_token(state, &token).await {
+        Ok(result) => format_analysis(&format!("🔬 {} ({}) on {}", token.name, token.symbol, token.chain), &result),
+        Err(e) => {
+            error!("Failed to analyze {}: {}", token.symbol, e);
+            "❌ Analysis failed".to_string()
+        }
+    }
+}
+
+async fn handle_watch(state: &Arc<AppState>, args: &str, user_key: &str) -> String {
+    const USAGE: &str = "Usage: /watch <token address> [note]";
+
+    let args = args.trim();
+    let (address, note) = match args.split_once(char::is_whitespace) {
+        Some((address, note)) => (address, Some(note.trim()).filter(|n| !n.is_empty())),
+        None => (args, None),
+    };
+    if address.is_empty() {
+        return USAGE.to_string();
+    }
+    if !crate::utils::is_valid_solana_address(address) && !crate::utils::is_valid_ethereum_address(address) {
+        return format!("❌ That doesn't look like a token address\n\n{}", USAGE);
+    }
+
+    let token = match state.db.find_token(address).await {
+        Ok(Some(token)) => token,
+        Ok(None) => {
+            if !state.refresh_limiter.lock().await.check_rate_limit(user_key).await {
+                return format!("⏳ Lookup limit reached ({} per hour), try again later", state.config.refresh_rate_limit_per_hour);
+            }
+            // Not seen by the scanners: pull it from DEX Screener so it can be followed
+            let scanner = crate::scanners::dex_screener::DexScreenerScanner::new(&state.config);
+            match scanner.discover_token_by_address(state, address, "telegram").await {
+                Ok(Some(token)) => token,
+                Ok(None) => return format!("📭 DEX Screener has no trading pair for {}", address),
+                Err(e) => {
+                    error!("Failed to fetch token {}: {}", address, e);
+                    return "❌ Failed to fetch token from DEX Screener".to_string();
+                }
+            }
+        }
+        Err(e) => {
+            error!("Failed to get token {}: {}", address, e);
+            return "❌ Failed to load token".to_string();
+        }
+    };
+
+    match state.db.add_to_watchlist(&token.address, note).await {
+        Ok(true) => format!("👀 Watching {} ({})", token.symbol, token.address),
+        Ok(false) => format!("👀 {} is already on your watchlist", token.symbol),
+        Err(e) => {
+            error!("Failed to add {} to the watchlist: {}", token.address, e);
+            "❌ Failed to update watchlist".to_string()
+        }
+    }
+}
+
+async fn handle_unwatch(state: &AppState, address: &str) -> String {
+    if address.is_empty() {
+        return "Usage: /unwatch <token address>".to_string();
+    }
+
+    // Stored under the token's canonical address, which may differ in case
+    let address = match state.db.find_token(address).await {
+        Ok(Some(token)) => token.address,
+        _ => address.to_string(),
+    };
+
+    match state.db.remove_from_watchlist(&address).await {
+        Ok(true) => format!("🙈 Stopped watching {}", address),
+        Ok(false) => format!("📭 {} is not on your watchlist", address),
+        Err(e) => {
+            error!("Failed to remove {} from the watchlist: {}", address, e);
+            "❌ Failed to update watchlist".to_string()
+        }
+    }
+}
+
+/// Softer than the blacklist: the token keeps being tracked, it just raises no signals
+async fn handle_ignore(state: &AppState, address: &str, ignored: bool) -> String {
+    if address.is_empty() {
+        return format!("Usage: /{} <token address>", if ignored { "ignore" } else { "unignore" });
+    }
+
+    let token = match state.db.find_token(address).await {
+        Ok(Some(token)) => token,
+        Ok(None) => return format!("📭 Unknown token {}", address),
+        Err(e) => {
+            error!("Failed to look up {}: {}", address, e);
+            return "❌ Failed to load token".to_string();
+        }
+    };
+
+    match state.db.set_ignored(&token.address, ignored).await {
+        Ok(_) if ignored => format!("🚫 Ignoring {} - no more signals, price still tracked", token.symbol),
+        Ok(_) => format!("🔔 Signals for {} resumed", token.symbol),
+        Err(e) => {
+            error!("Failed to update ignore flag for {}: {}", token.address, e);
+            "❌ Failed to update token".to_string()
+        }
+    }
+}
+
+async fn handle_blacklist(state: &Arc<AppState>, args: &str) -> String {
+    let (address, reason) = match args.trim().split_once(char::is_whitespace) {
+        Some((address, reason)) => (address, Some(reason.trim())),
+        None => (args.trim(), None),
+    };
+    if address.is_empty() {
+        return "Usage: /blacklist <token or deployer address> [reason]".to_string();
+    }
+
+    let added = match state.db.add_blacklist(address, reason).await {
+        Ok(added) => added,
+        Err(e) => {
+            error!("Failed to blacklist {}: {}", address, e);
+            return "❌ Failed to update blacklist".to_string();
+        }
+    };
+    let mut response = if added {
+        format!("⛔ Blacklisted {}", address)
+    } else {
+        format!("⛔ {} was already blacklisted", address)
+    };
+
+    // Trades are stored under the token's canonical address, which may differ in case
+    if let Ok(Some(token)) = state.db.find_token(address).await {
+        match crate::strategies::risk_management::close_trades_on_token(state, &token.address, "Blacklisted").await {
+            Ok(0) => {}
+            Ok(closed) => response.push_str(&format!("\n🛑 Closed {} active trade(s) on {}", closed, token.symbol)),
+            Err(e) => {
+                error!("Failed to close trades on blacklisted {}: {}", token.address, e);
+                response.push_str("\n❌ Failed to close its active trades");
+            }
+        }
+    }
+
+    response
+}
+
+async fn handle_unblacklist(state: &AppState, address: &str) -> String {
+    if address.is_empty() {
+        return "Usage: /unblacklist <address>".to_string();
+    }
+
+    match state.db.remove_blacklist(address).await {
+        Ok(true) => format!("✅ Removed {} from the blacklist", address),
+        Ok(false) => format!("📭 {} is not blacklisted", address),
+        Err(e) => {
+            error!("Failed to remove {} from the blacklist: {}", address, e);
+            "❌ Failed to update blacklist".to_string()
+        }
+    }
+}
+
+async fn handle_watchlist(state: &AppState) -> String {
+    let entries = match state.db.get_watchlist().await {
+        Ok(entries) => entries,
+        Err(e) => {
+            error!("Failed to get watchlist: {}", e);
+            return "❌ Failed to load watchlist".to_string();
+        }
+    };
+    if entries.is_empty() {
+        return "📭 Your watchlist is empty - add tokens with /watch <address>".to_string();
+    }
+
+    let mut response = "👀 Watchlist:\n\n".to_string();
+    for (i, entry) in entries.iter().enumerate() {
+        let symbol = match state.db.get_token(&entry.token_address).await {
+            Ok(Some(token)) => token.symbol,
+            _ => "?".to_string(),
+        };
+        let price = match state.db.get_latest_metrics(&entry.token_address).await.unwrap_or(None) {
+            Some(metrics) => format!(
+                "{} ({} ago)",
+                metrics.price_usd.map(crate::utils::format_price).unwrap_or_else(|| "?".to_string()),
+                crate::utils::format_duration(chrono::Utc::now().signed_duration_since(metrics.timestamp))
+            ),
+            None => "no data yet".to_string(),
+        };
+
+        response.push_str(&format!("{}. {}\n   🔗 {}\n   💵 {}\n", i + 1, symbol, entry.token_address, price));
+        if let Some(note) = &entry.note {
+            response.push_str(&format!("   📝 {}\n", note));
+        }
+        response.push('\n');
+    }
+
+    response
+}
+
+/// The /status line on what the filters turned away, with the three most common reasons
+fn filtered_summary(rejections: &crate::scanners::dex_screener::RejectionLog) -> String {
+    let breakdown: Vec<String> = rejections.counts().iter()
+        .take(3)
+        .map(|(label, count)| format!("{} {}", count, label))
+        .collect();
+    if breakdown.is_empty() {
+        return "🚫 No tokens filtered yet".to_string();
+    }
+    format!("🚫 Filtered {} tokens ({})", rejections.total(), breakdown.join(", "))
+}
+
+/// Active trades valued at their latest stored price, biggest unrealized gain first
+async fn handle_holdings(state: &AppState) -> String {
+    use rust_decimal::Decimal;
+
+    let trades = match state.db.get_active_trades().await {
+        Ok(trades) => trades,
+        Err(e) => {
+            error!("Failed to get active trades: {}", e);
+            return "❌ Failed to load active trades".to_string();
+        }
+    };
+    if trades.is_empty() {
+        return "📭 No active trades".to_string();
+    }
+
+    // (symbol, trade, current price, unrealized P&L on the part still held)
+    let mut priced = Vec::new();
+    let mut unpriced = Vec::new();
+    for trade in trades {
+        let symbol = match state.db.get_token(&trade.token_address).await {
+            Ok(Some(token)) => token.symbol,
+            _ => trade.token_address.clone(),
+        };
+        let price = state.db.get_latest_metrics(&trade.token_address).await
+            .unwrap_or(None)
+            .and_then(|m| m.price_usd)
+            .filter(|p| *p > Decimal::ZERO);
+        match price {
+            Some(price) => {
+                let unrealized = trade.partial_profit_loss(price, trade.remaining_fraction);
+                priced.push((symbol, trade, price, unrealized));
+            }
+            None => unpriced.push(symbol),
+        }
+    }
+    priced.sort_by_key(|holding| std::cmp::Reverse(holding.3));
+
+    let mut response = "💼 Holdings:\n\n".to_string();
+    let mut total_held = Decimal::ZERO;
+    let mut total_unrealized = Decimal::ZERO;
+    for (i, (symbol, trade, price, unrealized)) in priced.iter().enumerate() {
+        let held = trade.investment_usd * trade.remaining_fraction;
+        let emoji = if *unrealized > Decimal::ZERO { "🟢" } else { "🔴" };
+        response.push_str(&format!(
+            "{}. {} {}: {:.2}x\n   💵 Entry: {} → Now: {}\n   💰 ${:.2} held, ${:.2} unrealized\n",
+            i + 1,
+            emoji,
+            symbol,
+            price / trade.entry_price,
+            crate::utils::format_price(trade.entry_price),
+            crate::utils::format_price(*price),
+            held,
+            unrealized
+        ));
+        if trade.remaining_fraction < Decimal::ONE {
+            response.push_str(&format!("   🪜 ${:.2} already realized\n", trade.realized_profit_loss));
+        }
+        response.push('\n');
+        total_held += held;
+        total_unrealized += unrealized;
+    }
+    if !unpriced.is_empty() {
+        response.push_str(&format!("❓ No price yet: {}\n\n", unpriced.join(", ")));
+    }
+
+    let roi = if total_held > Decimal::ZERO { total_unrealized / total_held * Decimal::from(100) } else { Decimal::ZERO };
+    response.push_str(&format!(
+        "📊 Portfolio: ${:.2} held, ${:.2} unrealized ({:.1}%)",
+        total_held, total_unrealized, roi
+    ));
+    response
+}
+
+/// Score, risk, recommendation and flags under a heading
+fn format_analysis(title: &str, result: &crate::models::AnalysisResult) -> String {
+    let mut response = format!(
+        "{}\n\n\
+         📊 Score: {:.1}/100\n\
+         ⚠️ Risk: {:?}\n\
+         💡 Recommendation: {:?}\n",
+        title,
+        result.score,
+        result.risk_level,
+        result.recommendation
+    );
+    if !result.flags.is_empty() {
+        response.push_str("\n🚩 Flags:\n");
+        for flag in &result.flags {
+            response.push_str(&format!("{}\n", flag));
+        }
+    }
+    response
+}
+
+async fn handle_set(state: &AppState, args: &str) -> String {
+    let usage = format!(
+        "Usage: /set <key> <value> (\"off\" disables optional ones)\n\nSettable: {}",
+        crate::config::SETTABLE_TRADING_FIELDS.join(", ")
+    );
+
+    let mut parts = args.split_whitespace();
+    let (key, value) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(key), Some(value), None) => (key.to_lowercase(), value),
+        _ => return usage,
+    };
+
+    let mut trading = state.trading.write().await;
+    match trading.set(&key, value) {
+        Ok(()) => {
+            info!("⚙️ Trading parameter {} set to {}", key, value);
+            format!("✅ {} set to {} (until restart)", key, value)
+        }
+        Err(e) => format!("❌ {}", e),
+    }
+}
+
+async fn handle_target(state: &AppState, args: &str) -> String {
+    const USAGE: &str = "Usage: /target <token address> <price in USD>";
+
+    let mut parts = args.split_whitespace();
+    let (address, price) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(address), Some(price), None) => (address, price),
+        _ => return USAGE.to_string(),
+    };
+    let price: rust_decimal::Decimal = match price.trim_start_matches('$').parse() {
+        Ok(price) => price,
+        Err(_) => return format!("❌ Invalid price: {}\n\n{}", price, USAGE),
+    };
+
+    let trade = match state.db.get_active_trades().await {
+        Ok(trades) => trades.into_iter()
+            .filter(|t| t.token_address == address)
+            .find_map(|t| t.id.map(|id| (t, id))),
+        Err(e) => {
+            error!("Failed to get active trades: {}", e);
+            return "❌ Failed to load active trades".to_string();
+        }
+    };
+    let (trade, trade_id) = match trade {
+        Some(found) => found,
+        None => return format!("📭 No active trade for {}", address),
+    };
+
+    if price <= trade.entry_price {
+        return format!("❌ Target ${} must be above the entry price ${}", price, trade.entry_price);
+    }
+
+    match state.db.add_trade_target(trade_id, price).await {
+        Ok(()) => format!(
+            "🎯 Target set at ${} ({:.2}x from entry ${})",
+            price,
+            price / trade.entry_price,
+            trade.entry_price
+        ),
+        Err(e) => {
+            error!("Failed to save target for {}: {}", address, e);
+            "❌ Failed to save target".to_string()
+        }
+    }
+}
+
+async fn handle_net_return(state: &AppState, args: &str) -> String {
+    use rust_decimal::prelude::ToPrimitive;
+    const USAGE: &str = "Usage: /netreturn <token address> <usd> <target multiplier>";
+
+    let parts: Vec<&str> = args.split_whitespace().collect();
+    let (address, usd, target) = match parts.as_slice() {
+        [address, usd, target] => match (usd.trim_start_matches('$').parse::<f64>(), target.trim_end_matches('x').parse::<f64>()) {
+            (Ok(usd), Ok(target)) if usd > 0.0 && target > 0.0 => (*address, usd, target),
+            _ => return USAGE.to_string(),
+        },
+        _ => return USAGE.to_string(),
+    };
+
+    let token = match state.db.get_token(address).await {
+        Ok(Some(token)) => token,
+        Ok(None) => return format!("📭 {} is not a tracked token", address),
+        Err(e) => {
+            error!("Failed to get token {}: {}", address, e);
+            return "❌ Failed to load token".to_string();
+        }
+    };
+    let metrics = state.db.get_latest_metrics(address).await.unwrap_or(None);
+    let metric = |value: Option<rust_decimal::Decimal>| value.and_then(|v| v.to_f64());
+
+    let costs = crate::utils::RoundTripCosts {
+        gas_usd: state.trading.read().await.gas_cost_for(token.chain.as_str()),
+        buy_tax_pct: metric(metrics.as_ref().and_then(|m| m.buy_tax_pct)).unwrap_or(0.0),
+        sell_tax_pct: metric(metrics.as_ref().and_then(|m| m.sell_tax_pct)).unwrap_or(0.0),
+        pool_liquidity_usd: metric(metrics.as_ref().and_then(|m| m.liquidity_usd)),
+    };
+    let taxes_known = metrics.as_ref().is_some_and(|m| m.buy_tax_pct.is_some() || m.sell_tax_pct.is_some());
+
+    let trip = crate::utils::estimate_round_trip(usd, target, &costs);
+    let breakeven = crate::utils::breakeven_multiplier(usd, &costs)
+        .map(|m| format!("{:.2}x", m))
+        .unwrap_or_else(|| "over 1000x".to_string());
+
+    format!(
+        "🧮 Net Return: {} on {}\n\n\
+         💵 Buy ${:.2}, sell at {:.2}x\n\n\
+         ⛽ Gas: ${:.2} per swap\n\
+         💧 Entry slippage: {:.2}%\n\
+         💧 Exit slippage: {:.2}%\n\
+         🧾 Taxes: {:.1}% buy / {:.1}% sell{}\n\n\
+         💰 Proceeds: ${:.2}\n\
+         📈 Net: ${:.2} ({:.2}x)\n\
+         ⚖️ Break-even: {}{}",
+        token.symbol,
+        token.chain,
+        usd,
+        target,
+        costs.gas_usd,
+        trip.entry_slippage_pct,
+        trip.exit_slippage_pct,
+        costs.buy_tax_pct,
+        costs.sell_tax_pct,
+        if taxes_known { "" } else { " (unknown, assumed 0)" },
+        trip.proceeds_usd,
+        trip.net_usd,
+        trip.proceeds_usd / usd,
+        breakeven,
+        if costs.pool_liquidity_usd.is_none() { "\n\n⚠️ Liquidity unknown, slippage not modeled" } else { "" }
+    )
+}
+
+async fn handle_reset(state: &AppState, args: &str) -> String {
+    if args != "confirm" {
+        let include_signals = args == "all";
+        *state.pending_reset.write().await = Some(PendingReset {
+            requested_at: Instant::now(),
+            include_signals,
+        });
+
+        return format!(
+            "⚠️ This will permanently delete all simulated trades{}.\n\
+             Discovered tokens and metrics are kept.\n\n\
+             Send /reset confirm within {}s to proceed.",
+            if include_signals { " and trading signals" } else { "" },
+            RESET_CONFIRM_WINDOW.as_secs()
+        );
+    }
+
+    let pending = state.pending_reset.write().await.take();
+    match pending {
+        Some(pending) if pending.requested_at.elapsed() <= RESET_CONFIRM_WINDOW => {
+            match state.db.reset_simulation(pending.include_signals).await {
+                Ok(deleted) => format!("🧹 Simulation reset: {} trades deleted", deleted),
+                Err(e) => {
+                    error!("Failed to reset simulation: {}", e);
+                    "❌ Failed to reset simulation data".to_string()
+                }
+            }
+        }
+        Some(_) => "⌛ Reset request expired, send /reset again".to_string(),
+        None => "❓ Nothing to confirm, send /reset first".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::{test_config, test_state, token};
+    use rust_decimal::Decimal;
+    use std::collections::HashSet;
+    use std::sync::Mutex;
+
+    /// Rate-limits the channels in `failing` (asking for an immediate retry) and records every attempt
+    #[derive(Default)]
+    struct FlakySender {
+        failing: Mutex<HashSet<String>>,
+        attempts: Mutex<Vec<String>>,
+    }
+
+    impl SignalSender for FlakySender {
+        async fn send(&self, channel: &DeliveryChannel, _signal: &TradingSignal, _state: &Arc<AppState>) -> Result<()> {
+            let name = channel.name();
+            self.attempts.lock().unwrap().push(name.clone());
+            if self.failing.lock().unwrap().contains(&name) {
+                return Err(HttpError { status: reqwest::StatusCode::TOO_MANY_REQUESTS, retry_after: Some(Duration::ZERO) }.into());
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_channel_is_retried_alone_until_every_channel_has_the_signal() {
+        let mut config = test_config();
+        config.telegram_chat_ids = vec![Recipient::Id(ChatId(1)), Recipient::Id(ChatId(2))];
+        let state = test_state(config).await;
+        state.db.save_token(&token("TOKEN", "TKN")).await.unwrap();
+        state.db.save_trading_signal(&TradingSignal {
+            id: None,
+            token_address: "TOKEN".to_string(),
+            signal_type: SignalType::Buy,
+            confidence: Decimal::new(95, 2),
+            reason: "test".to_string(),
+            target_multiplier: None,
+            created_at: chrono::Utc::now(),
+            is_sent: false,
+        }).await.unwrap();
+        let signal = state.db.get_unsent_signals().await.unwrap().remove(0);
+
+        let sender = FlakySender::default();
+        sender.failing.lock().unwrap().insert("telegram:2".to_string());
+        deliver_signal(&sender, &signal, &state).await.unwrap();
+        assert_eq!(*sender.attempts.lock().unwrap(), ["telegram:1", "telegram:2"]);
+        assert_eq!(state.db.get_unsent_signals().await.unwrap().len(), 1, "one channel is still pending");
+
+        sender.failing.lock().unwrap().clear();
+        sender.attempts.lock().unwrap().clear();
+        deliver_signal(&sender, &signal, &state).await.unwrap();
+        assert_eq!(*sender.attempts.lock().unwrap(), ["telegram:2"]);
+        assert!(state.db.get_unsent_signals().await.unwrap().is_empty());
+    }
+}
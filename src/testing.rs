@@ -0,0 +1,105 @@
+// src/testing.rs
+// Fixtures shared by the unit tests: an in-memory database, a config that doesn't
+// depend on the developer's environment, and an AppState wired to both
+
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock, Semaphore};
+
+use crate::activity::ActivityLog;
+use crate::config::Config;
+use crate::database::Database;
+use crate::models::{Chain, Token, TokenMetrics};
+use crate::telegram::TelegramBot;
+use crate::utils::RateLimiter;
+use crate::AppState;
+
+/// A migrated SQLite database that lives as long as its single connection
+pub async fn memory_db() -> Database {
+    let db = Database::new("sqlite::memory:", 1).await.expect("in-memory database");
+    db.migrate().await.expect("migrations");
+    db
+}
+
+/// The configuration every test starts from: defaults, one Telegram chat, no network side effects
+pub fn test_config() -> Config {
+    std::env::set_var("TELEGRAM_TOKEN", "123:test");
+    std::env::set_var("TELEGRAM_CHAT_IDS", "1");
+    let mut config = Config::load().expect("test config");
+    config.dry_run = false;
+    config.discord_webhook_url = None;
+    config
+}
+
+/// Shared state around `config` and a fresh in-memory database
+pub async fn test_state(config: Config) -> Arc<AppState> {
+    let telegram = TelegramBot::new(&config.telegram_token, true).await.expect("offline bot");
+
+    Arc::new(AppState {
+        trading: RwLock::new(config.trading.clone()),
+        db: memory_db().await,
+        telegram,
+        running: RwLock::new(true),
+        pending_reset: RwLock::new(None),
+        rejections: RwLock::new(Default::default()),
+        refresh_limiter: Mutex::new(RateLimiter::new(100, Duration::from_secs(3600))),
+        honeypot_limiter: Mutex::new(RateLimiter::new(100, Duration::from_secs(60))),
+        analysis_slots: Semaphore::new(1),
+        last_successful_scan: RwLock::new(None),
+        activity: RwLock::new(ActivityLog::new(50, Vec::new())),
+        config,
+    })
+}
+
+/// A Solana token with the given address and symbol
+pub fn token(address: &str, symbol: &str) -> Token {
+    Token {
+        id: None,
+        address: address.to_string(),
+        symbol: symbol.to_string(),
+        name: format!("{} Token", symbol),
+        chain: Chain::Solana,
+        source: "test".to_string(),
+        created_at: Utc::now(),
+        first_seen: Utc::now(),
+        is_active: true,
+        deployed_at: None,
+        deployer: None,
+        creation_checked_at: None,
+    }
+}
+
+/// An empty snapshot of `address` taken now
+pub fn metrics(address: &str) -> TokenMetrics {
+    TokenMetrics {
+        id: None,
+        token_address: address.to_string(),
+        timestamp: Utc::now(),
+        price_usd: None,
+        market_cap_usd: None,
+        liquidity_usd: None,
+        volume_24h_usd: None,
+        buy_count_24h: None,
+        total_supply: None,
+        circulating_supply: None,
+        holder_count: None,
+        top_10_holders_percentage: None,
+        is_honeypot: None,
+        is_mintable: None,
+        has_proxy: None,
+        contract_verified: None,
+        ownership_renounced: None,
+        owner_privileged: None,
+        buy_tax_pct: None,
+        sell_tax_pct: None,
+        price_consistent: None,
+        volume_5m_usd: None,
+        volume_1h_usd: None,
+        volume_6h_usd: None,
+        price_change_5m_pct: None,
+        price_change_1h_pct: None,
+        price_change_6h_pct: None,
+        price_change_24h_pct: None,
+    }
+}
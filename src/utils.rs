@@ -1,7 +1,12 @@
 // src/utils.rs
 use anyhow::Result;
 use rust_decimal::Decimal;
+use rand::Rng;
 use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::config::VolatilityMeasure;
+use crate::models::{Chain, SimulatedTrade};
 
 /// Format large numbers in a human-readable way
 pub fn format_number(num: f64) -> String {
@@ -29,6 +34,19 @@ pub fn format_price(price: Decimal) -> String {
     }
 }
 
+/// Format a duration compactly, e.g. "2d 4h", "3h 15m", "42m"
+pub fn format_duration(duration: chrono::Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+
+    if minutes >= 24 * 60 {
+        format!("{}d {}h", minutes / (24 * 60), (minutes / 60) % 24)
+    } else if minutes >= 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
 /// Calculate percentage change
 pub fn calculate_percentage_change(old_price: Decimal, new_price: Decimal) -> Decimal {
     if old_price == Decimal::ZERO {
@@ -37,6 +55,74 @@ pub fn calculate_percentage_change(old_price: Decimal, new_price: Decimal) -> De
     ((new_price - old_price) / old_price) * Decimal::from(100)
 }
 
+/// Convert an API float to a Decimal, refusing NaN, infinities and values
+/// too large for a Decimal instead of quietly turning them into zero
+pub fn f64_to_decimal(value: f64) -> Result<Decimal> {
+    if !value.is_finite() {
+        return Err(anyhow::anyhow!("{} is not a finite number", value));
+    }
+    Decimal::try_from(value).map_err(|_| anyhow::anyhow!("{:e} is out of range for a Decimal", value))
+}
+
+/// Price volatility as a percentage, using the given measure.
+/// Returns None when there are too few points to say anything.
+pub fn price_volatility(prices: &[f64], measure: VolatilityMeasure) -> Option<f64> {
+    if prices.len() < 3 {
+        return None;
+    }
+
+    match measure {
+        VolatilityMeasure::StdDev => {
+            let mean = prices.iter().sum::<f64>() / prices.len() as f64;
+            if mean <= 0.0 {
+                return None;
+            }
+            let variance = prices.iter().map(|p| (p - mean).powi(2)).sum::<f64>() / prices.len() as f64;
+            Some(variance.sqrt() / mean * 100.0)
+        }
+        VolatilityMeasure::Range => {
+            let min = prices.iter().cloned().fold(f64::INFINITY, f64::min);
+            let max = prices.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+            if min <= 0.0 {
+                return None;
+            }
+            Some((max - min) / min * 100.0)
+        }
+    }
+}
+
+/// Largest peak-to-trough fall as a percentage of the peak
+pub fn max_drawdown(prices: &[f64]) -> f64 {
+    let mut peak = f64::NEG_INFINITY;
+    let mut drawdown: f64 = 0.0;
+    for &price in prices {
+        peak = peak.max(price);
+        if peak > 0.0 {
+            drawdown = drawdown.max((peak - price) / peak * 100.0);
+        }
+    }
+    drawdown
+}
+
+/// A one-line chart of the values, scaled between their min and max.
+/// A flat series sits at mid height; empty input gives an empty string.
+pub fn sparkline(values: &[f64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let min = values.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = values.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let range = max - min;
+
+    values.iter().map(|&value| {
+        if range > 0.0 {
+            let level = ((value - min) / range * (BARS.len() - 1) as f64).round() as usize;
+            BARS[level.min(BARS.len() - 1)]
+        } else {
+            BARS[BARS.len() / 2 - 1]
+        }
+    }).collect()
+}
+
 /// Validate Solana address format
 pub fn is_valid_solana_address(address: &str) -> bool {
     // Basic validation - Solana addresses are base58 encoded and 32-44 characters
@@ -52,6 +138,12 @@ pub fn is_valid_ethereum_address(address: &str) -> bool {
     address[2..].chars().all(|c| c.is_ascii_hexdigit())
 }
 
+/// Validate an address against its chain's format (base58 on Solana, 0x hex on EVM chains).
+/// Chains we have no format for can't be checked, so their addresses pass.
+pub fn is_valid_address(chain: &Chain, address: &str) -> bool {
+    chain.address_validator().is_none_or(|is_valid| is_valid(address))
+}
+
 /// Rate limiter for API calls
 pub struct RateLimiter {
     requests: HashMap<String, Vec<std::time::Instant>>,
@@ -84,3 +176,191 @@ impl RateLimiter {
         }
     }
 }
+
+/// Spread a loop interval by a random ±`jitter_pct` so periodic loops drift apart
+pub fn jittered(secs: u64, jitter_pct: f64) -> Duration {
+    let jitter = jitter_pct.clamp(0.0, 1.0);
+    if jitter == 0.0 {
+        return Duration::from_secs(secs);
+    }
+
+    let factor = rand::thread_rng().gen_range(1.0 - jitter..=1.0 + jitter);
+    Duration::from_secs_f64(secs as f64 * factor)
+}
+
+/// A random delay of 0..`max_secs` before a loop's first run, so loops started together spread out
+pub fn start_offset(max_secs: u64) -> Duration {
+    if max_secs == 0 {
+        return Duration::ZERO;
+    }
+
+    Duration::from_secs_f64(rand::thread_rng().gen_range(0.0..=max_secs as f64))
+}
+
+/// Exponential backoff before retry `attempt` (1-based): 2s, 4s, 8s... capped at `max`,
+/// with ±20% random jitter so concurrent retries don't line up
+pub fn backoff_delay(attempt: u32, max: Duration) -> Duration {
+    let base = Duration::from_secs(2u64.saturating_pow(attempt.max(1)));
+    let factor = rand::thread_rng().gen_range(0.8..=1.2);
+    base.mul_f64(factor).min(max)
+}
+
+/// How long a `Retry-After` header asks us to wait: either delta-seconds or an HTTP date
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let wait = at.signed_duration_since(chrono::Utc::now());
+    Some(wait.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Closed trades as CSV: a header row, then one line per trade
+pub fn trades_to_csv(trades: &[SimulatedTrade]) -> String {
+    let mut csv = String::from("token,entry_price,exit_price,entry_time,exit_time,investment_usd,profit_loss,multiplier,exit_reason\n");
+
+    for trade in trades {
+        let fields = [
+            trade.token_address.clone(),
+            trade.entry_price.to_string(),
+            trade.exit_price.map(|p| p.to_string()).unwrap_or_default(),
+            trade.entry_time.to_rfc3339(),
+            trade.exit_time.map(|t| t.to_rfc3339()).unwrap_or_default(),
+            trade.investment_usd.to_string(),
+            trade.profit_loss.map(|p| p.round_dp(2).to_string()).unwrap_or_default(),
+            trade.multiplier.map(|m| m.round_dp(4).to_string()).unwrap_or_default(),
+            trade.exit_reason.clone().unwrap_or_default(),
+        ];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Costs that eat into a simulated buy-then-sell
+#[derive(Debug, Clone, Copy)]
+pub struct RoundTripCosts {
+    pub gas_usd: f64,               // Per swap
+    pub buy_tax_pct: f64,
+    pub sell_tax_pct: f64,
+    pub pool_liquidity_usd: Option<f64>, // Total pool liquidity, for price impact
+}
+
+/// What a round trip actually nets
+#[derive(Debug, Clone, Copy)]
+pub struct RoundTrip {
+    pub entry_slippage_pct: f64,
+    pub exit_slippage_pct: f64,
+    pub proceeds_usd: f64,
+    pub net_usd: f64,
+}
+
+/// Buy with `usd`, sell at `target_multiplier` times the entry price.
+/// Price impact follows a constant-product pool whose quote side is half the liquidity
+/// (and grows with sqrt of the price move by the time we sell).
+pub fn estimate_round_trip(usd: f64, target_multiplier: f64, costs: &RoundTripCosts) -> RoundTrip {
+    let quote_reserve = costs.pool_liquidity_usd.map(|l| l / 2.0).filter(|q| *q > 0.0);
+    let impact = |amount: f64, reserve: Option<f64>| match reserve {
+        Some(reserve) if amount > 0.0 => amount / (reserve + amount),
+        _ => 0.0,
+    };
+
+    let spent = (usd - costs.gas_usd).max(0.0);
+    let entry_slippage = impact(spent, quote_reserve);
+    let position = spent * (1.0 - entry_slippage) * (1.0 - costs.buy_tax_pct / 100.0);
+
+    let exit_value = position * target_multiplier;
+    let exit_slippage = impact(exit_value, quote_reserve.map(|q| q * target_multiplier.max(0.0).sqrt()));
+    let proceeds = (exit_value * (1.0 - exit_slippage) * (1.0 - costs.sell_tax_pct / 100.0) - costs.gas_usd).max(0.0);
+
+    RoundTrip {
+        entry_slippage_pct: entry_slippage * 100.0,
+        exit_slippage_pct: exit_slippage * 100.0,
+        proceeds_usd: proceeds,
+        net_usd: proceeds - usd,
+    }
+}
+
+/// Smallest price multiplier at which a round trip of `usd` gets its money back (None if above 1000x)
+pub fn breakeven_multiplier(usd: f64, costs: &RoundTripCosts) -> Option<f64> {
+    let (mut low, mut high) = (0.0, 1000.0);
+    if estimate_round_trip(usd, high, costs).net_usd < 0.0 {
+        return None;
+    }
+
+    for _ in 0..60 {
+        let mid = (low + high) / 2.0;
+        if estimate_round_trip(usd, mid, costs).net_usd >= 0.0 {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+
+    Some(high)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sparkline_scales_between_min_and_max() {
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(sparkline(&[5.0]), "▄");
+        assert_eq!(sparkline(&[2.0, 2.0, 2.0]), "▄▄▄");
+        assert_eq!(sparkline(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]), "▁▂▃▄▅▆▇█");
+    }
+
+    #[test]
+    fn f64_to_decimal_refuses_values_it_cannot_represent() {
+        assert!(f64_to_decimal(f64::NAN).is_err());
+        assert!(f64_to_decimal(f64::INFINITY).is_err());
+        assert!(f64_to_decimal(1e300).is_err());
+        assert_eq!(f64_to_decimal(0.00001234).unwrap(), Decimal::new(1234, 8));
+    }
+
+    #[test]
+    fn solana_addresses_must_be_base58_of_the_right_length() {
+        let wrapped_sol = "So11111111111111111111111111111111111111112";
+        assert!(is_valid_address(&Chain::Solana, wrapped_sol));
+
+        for excluded in ['0', 'O', 'I', 'l'] {
+            let address = format!("{}{}", &wrapped_sol[..42], excluded);
+            assert!(!is_valid_address(&Chain::Solana, &address), "{} should be rejected", address);
+        }
+        assert!(!is_valid_address(&Chain::Solana, &"1".repeat(31)));
+        assert!(is_valid_address(&Chain::Solana, &"1".repeat(32)));
+        assert!(is_valid_address(&Chain::Solana, &"1".repeat(44)));
+        assert!(!is_valid_address(&Chain::Solana, &"1".repeat(45)));
+    }
+
+    #[test]
+    fn evm_addresses_must_be_0x_and_40_hex_digits() {
+        assert!(is_valid_address(&Chain::Ethereum, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        assert!(is_valid_address(&Chain::Bsc, "0xfb6115445bff7b52feb98650c87f44907e58f802"));
+
+        assert!(!is_valid_address(&Chain::Ethereum, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeZ"));
+        assert!(!is_valid_address(&Chain::Ethereum, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAe"));
+        assert!(!is_valid_address(&Chain::Ethereum, "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed0"));
+        assert!(!is_valid_address(&Chain::Ethereum, "005aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+    }
+
+    #[test]
+    fn unknown_chains_accept_any_address() {
+        assert!(is_valid_address(&Chain::Other("sui".to_string()), "0x2::sui::SUI"));
+    }
+}
@@ -0,0 +1,132 @@
+// src/webhook.rs
+// Lets upstream tools push tokens they discovered into our pipeline:
+// POST /submit {"chain": "...", "address": "..."} with "Authorization: Bearer <WEBHOOK_TOKEN>"
+
+use anyhow::Result;
+use axum::{
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, StatusCode},
+    routing::post,
+    Json, Router,
+};
+use log::{info, warn};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::models::Chain;
+use crate::scanners::dex_screener::DexScreenerScanner;
+use crate::utils::RateLimiter;
+use crate::AppState;
+
+struct WebhookContext {
+    state: Arc<AppState>,
+    scanner: DexScreenerScanner,
+    limiter: Mutex<RateLimiter>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Submission {
+    chain: String,
+    address: String,
+}
+
+pub async fn start_webhook_server(state: Arc<AppState>) -> Result<()> {
+    let bind_addr = state.config.webhook_bind_addr.clone()
+        .ok_or_else(|| anyhow::anyhow!("WEBHOOK_BIND_ADDR is not set"))?;
+
+    let context = Arc::new(WebhookContext {
+        scanner: DexScreenerScanner::new(&state.config),
+        limiter: Mutex::new(RateLimiter::new(
+            state.config.webhook_rate_limit_per_minute,
+            Duration::from_secs(60),
+        )),
+        state,
+    });
+
+    let shutdown_state = context.state.clone();
+    let app = Router::new()
+        .route("/submit", post(submit))
+        .with_state(context);
+
+    let listener = tokio::net::TcpListener::bind(&bind_addr).await?;
+    info!("🪝 Webhook listening on {}", bind_addr);
+
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(async move {
+            while *shutdown_state.running.read().await {
+                tokio::time::sleep(Duration::from_secs(1)).await;
+            }
+            info!("🛑 Webhook stopping...");
+        })
+        .await?;
+
+    Ok(())
+}
+
+async fn submit(
+    State(context): State<Arc<WebhookContext>>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    Json(submission): Json<Submission>,
+) -> (StatusCode, Json<Value>) {
+    // Authenticate
+    let expected = context.state.config.webhook_token.as_deref().unwrap_or_default();
+    let provided = headers
+        .get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|h| h.strip_prefix("Bearer "));
+    if expected.is_empty() || provided != Some(expected) {
+        warn!("🪝 Rejected unauthenticated submission from {}", client.ip());
+        return reply(StatusCode::UNAUTHORIZED, "unauthorized");
+    }
+
+    // Rate limit per client
+    if !context.limiter.lock().await.check_rate_limit(&client.ip().to_string()).await {
+        return reply(StatusCode::TOO_MANY_REQUESTS, "rate limited");
+    }
+
+    // Validate
+    let address = submission.address.trim();
+    let (chain, is_valid_address) = match submission.chain.parse::<Chain>() {
+        Ok(chain) => match chain.address_validator() {
+            Some(validator) => (chain, validator),
+            None => return reply(StatusCode::BAD_REQUEST, "unsupported chain"),
+        },
+        Err(_) => return reply(StatusCode::BAD_REQUEST, "unsupported chain"),
+    };
+    if !is_valid_address(address) {
+        return reply(StatusCode::BAD_REQUEST, "invalid address for chain");
+    }
+
+    let state = &context.state;
+    match state.db.get_token(address).await {
+        Ok(Some(token)) => return (StatusCode::OK, Json(json!({ "status": "known", "symbol": token.symbol }))),
+        Ok(None) => {}
+        Err(e) => {
+            warn!("🪝 Token lookup failed for {}: {}", address, e);
+            return reply(StatusCode::INTERNAL_SERVER_ERROR, "database error");
+        }
+    }
+
+    // Fetch metrics, save and analyze through the normal pipeline
+    match context.scanner.ingest_token(state, &chain, address, "external").await {
+        Ok(Some(token)) => {
+            info!("🪝 Accepted external submission: {} ({}) on {}", token.symbol, token.address, chain);
+            (StatusCode::ACCEPTED, Json(json!({ "status": "accepted", "symbol": token.symbol })))
+        }
+        Ok(None) => reply(StatusCode::NOT_FOUND, "no trading pair found for token"),
+        Err(e) => {
+            warn!("🪝 Failed to ingest {}: {}", address, e);
+            reply(StatusCode::BAD_GATEWAY, "failed to fetch token data")
+        }
+    }
+}
+
+fn reply(status: StatusCode, message: &str) -> (StatusCode, Json<Value>) {
+    let key = if status.is_success() { "status" } else { "error" };
+    (status, Json(json!({ key: message })))
+}